@@ -77,21 +77,21 @@ fill_metadata
 	// metadata.set_tag(
 	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 0).set_tag(
 		ExifTag::UnknownSTRING("test1".to_string(), 0x010d, ExifTagGroup::GENERIC)
-	);
+	).unwrap();
 
 	metadata.set_tag(
 		ExifTag::ImageDescription("-w 1000 -h 1000 --x_mid=0 --y_mid=0 -z 0.5 -i 1000 -c 8".to_string())
-	);
+	).unwrap();
 
 	metadata.set_tag(
 		ExifTag::ISO(vec![2022])
-	);
+	).unwrap();
 
 	metadata.set_tag(
 		ExifTag::UnknownSTRING("test2".to_string(), 0x010c, ExifTagGroup::GENERIC)
-	);
+	).unwrap();
 
 	metadata.set_tag(
 		ExifTag::FNumber(vec![1.4.into()])
-	)
+	).unwrap();
 }
@@ -0,0 +1,176 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::error::Error;
+use std::fmt;
+use std::io::ErrorKind;
+
+/// The error type returned by the public functions of `Metadata` for reading,
+/// writing and clearing metadata. Allows distinguishing the different kinds
+/// of failures without having to look at the error message.
+///
+/// Internally, little_exif still uses `std::io::Error`, so conversions in
+/// both directions are available. Converting a `LittleExifError` into a
+/// `std::io::Error` keeps the variant, which is restored when converting it
+/// back. This also means that `?` can be used on the public functions in a
+/// function returning a `std::io::Error`.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::error::LittleExifError;
+/// use little_exif::metadata::Metadata;
+///
+/// match Metadata::new_from_path(std::path::Path::new("image.jpg"))
+/// {
+///     Ok(metadata)                              => println!("{:?}", metadata),
+///     Err(LittleExifError::InvalidSignature(_)) => println!("Not a JPEG!"),
+///     Err(error)                                => println!("{}", error),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum
+LittleExifError
+{
+	/// The data does not start with the signature expected for its file type
+	InvalidSignature(String),
+
+	/// A checksum stored in the file (e.g. the CRC of a PNG chunk) does not
+	/// match the data it is supposed to protect
+	ChecksumMismatch(String),
+
+	/// The file does not contain the requested metadata
+	NoMetadata(String),
+
+	/// The file type or the requested operation for this file type is not
+	/// supported
+	UnsupportedFormat(String),
+
+	/// The data ended unexpectedly
+	Truncated(String),
+
+	/// Any other error, e.g. an I/O error while accessing a file
+	Io(std::io::Error),
+}
+
+impl
+LittleExifError
+{
+	/// Returns the `std::io::ErrorKind` corresponding to this error, which
+	/// is the kind of the `std::io::Error` this error converts into
+	pub fn
+	kind
+	(
+		&self
+	)
+	-> ErrorKind
+	{
+		match self
+		{
+			LittleExifError::InvalidSignature(_)  => ErrorKind::InvalidData,
+			LittleExifError::ChecksumMismatch(_)  => ErrorKind::InvalidData,
+			LittleExifError::NoMetadata(_)        => ErrorKind::Other,
+			LittleExifError::UnsupportedFormat(_) => ErrorKind::Unsupported,
+			LittleExifError::Truncated(_)         => ErrorKind::UnexpectedEof,
+			LittleExifError::Io(error)            => error.kind(),
+		}
+	}
+}
+
+impl
+fmt::Display
+for
+LittleExifError
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		match self
+		{
+			LittleExifError::InvalidSignature(message)
+				=> write!(f, "{}", message),
+			LittleExifError::ChecksumMismatch(message)
+				=> write!(f, "{}", message),
+			LittleExifError::NoMetadata(message)
+				=> write!(f, "{}", message),
+			LittleExifError::UnsupportedFormat(message)
+				=> write!(f, "{}", message),
+			LittleExifError::Truncated(message)
+				=> write!(f, "{}", message),
+			LittleExifError::Io(error)
+				=> write!(f, "{}", error),
+		}
+	}
+}
+
+impl
+Error
+for
+LittleExifError
+{
+	fn
+	source
+	(
+		&self
+	)
+	-> Option<&(dyn Error + 'static)>
+	{
+		match self
+		{
+			LittleExifError::Io(error) => Some(error),
+			_                          => None,
+		}
+	}
+}
+
+impl
+From<std::io::Error>
+for
+LittleExifError
+{
+	fn
+	from
+	(
+		error: std::io::Error
+	)
+	-> Self
+	{
+		// Restore the variant of an error that got converted into a
+		// std::io::Error for passing it through the internal functions
+		if matches!(error.get_ref(), Some(inner) if inner.is::<LittleExifError>())
+		{
+			return *error.into_inner().unwrap().downcast::<LittleExifError>().unwrap();
+		}
+
+		match error.kind()
+		{
+			ErrorKind::UnexpectedEof => LittleExifError::Truncated(error.to_string()),
+			ErrorKind::Unsupported   => LittleExifError::UnsupportedFormat(error.to_string()),
+			_                        => LittleExifError::Io(error),
+		}
+	}
+}
+
+impl
+From<LittleExifError>
+for
+std::io::Error
+{
+	fn
+	from
+	(
+		error: LittleExifError
+	)
+	-> Self
+	{
+		match error
+		{
+			LittleExifError::Io(error) => error,
+			_                          => std::io::Error::new(error.kind(), error),
+		}
+	}
+}
@@ -0,0 +1,261 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+/// A date and time as stored in the EXIF datetime tags (e.g.
+/// `DateTimeOriginal`) in the format "YYYY:MM:DD HH:MM:SS".
+/// If available, the fractional seconds (from the `SubSecTime...` tags) and
+/// the offset to UTC (from the `OffsetTime...` tags) are stored as well.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+ExifDateTime
+{
+	pub year:           u16,
+	pub month:          u8,
+	pub day:            u8,
+	pub hour:           u8,
+	pub minute:         u8,
+	pub second:         u8,
+
+	/// Fractional seconds in nanoseconds
+	pub subsec_nanos:   Option<u32>,
+
+	/// Offset to UTC in minutes, e.g. -330 for "-05:30"
+	pub offset_minutes: Option<i32>,
+}
+
+/// Parses the given ASCII digits into a number. Returns `None` if one of the
+/// characters is not a digit.
+fn
+parse_digits
+(
+	digits: &[u8]
+)
+-> Option<u32>
+{
+	let mut value = 0u32;
+	for digit in digits
+	{
+		if !digit.is_ascii_digit()
+		{
+			return None;
+		}
+		value = value * 10 + (digit - b'0') as u32;
+	}
+	return Some(value);
+}
+
+/// Converts the given date into the number of days since 1970-01-01
+fn
+days_from_civil
+(
+	year:  i64,
+	month: i64,
+	day:   i64
+)
+-> i64
+{
+	// Shift the year so that it starts in March, which puts the leap day at
+	// the end of the year
+	let year         = if month <= 2 { year - 1 } else { year };
+	let era          = year.div_euclid(400);
+	let year_of_era  = year - era * 400;
+	let day_of_year  = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+	let day_of_era   = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	return era * 146097 + day_of_era - 719468;
+}
+
+/// Converts the given number of days since 1970-01-01 into a date, i.e. the
+/// inverse of `days_from_civil`
+fn
+civil_from_days
+(
+	days: i64
+)
+-> (i64, i64, i64)
+{
+	let days        = days + 719468;
+	let era         = days.div_euclid(146097);
+	let day_of_era  = days - era * 146097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day         = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month       = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	let year        = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+	return (year, month, day);
+}
+
+impl
+ExifDateTime
+{
+	/// Parses a string in the EXIF datetime format "YYYY:MM:DD HH:MM:SS".
+	/// Returns `None` if the string is not exactly 19 bytes long (not
+	/// counting a NUL terminator), does not follow this format or contains
+	/// out of range values like a 13th month.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_datetime::ExifDateTime;
+	///
+	/// let datetime = ExifDateTime::from_exif_string("2024:02:29 13:37:00").unwrap();
+	/// assert_eq!(datetime.month, 2);
+	/// assert!(ExifDateTime::from_exif_string("2024-02-29 13:37:00").is_none());
+	/// ```
+	pub fn
+	from_exif_string
+	(
+		datetime: &str
+	)
+	-> Option<ExifDateTime>
+	{
+		let bytes = datetime.trim_end_matches('\0').as_bytes();
+
+		if bytes.len() != 19
+		{
+			return None;
+		}
+
+		// Check the separators
+		for (position, separator) in [(4, b':'), (7, b':'), (10, b' '), (13, b':'), (16, b':')]
+		{
+			if bytes[position] != separator
+			{
+				return None;
+			}
+		}
+
+		let year   = parse_digits(&bytes[ 0.. 4])?;
+		let month  = parse_digits(&bytes[ 5.. 7])?;
+		let day    = parse_digits(&bytes[ 8..10])?;
+		let hour   = parse_digits(&bytes[11..13])?;
+		let minute = parse_digits(&bytes[14..16])?;
+		let second = parse_digits(&bytes[17..19])?;
+
+		// Allow a leap second
+		if !(1..=12).contains(&month) || !(1..=31).contains(&day)
+		|| hour > 23 || minute > 59 || second > 60
+		{
+			return None;
+		}
+
+		return Some(ExifDateTime {
+			year:           year   as u16,
+			month:          month  as u8,
+			day:            day    as u8,
+			hour:           hour   as u8,
+			minute:         minute as u8,
+			second:         second as u8,
+			subsec_nanos:   None,
+			offset_minutes: None,
+		});
+	}
+
+	/// Converts the datetime to UTC by subtracting its offset to UTC, which
+	/// may change the date as well. The offset of the result is set to zero.
+	/// Returns `None` if the offset is unknown or the resulting year is out
+	/// of range.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_datetime::ExifDateTime;
+	///
+	/// let mut datetime = ExifDateTime::from_exif_string("2024:03:01 01:30:00").unwrap();
+	/// assert!(datetime.to_utc().is_none());
+	///
+	/// datetime.offset_minutes = Some(120);
+	/// let utc = datetime.to_utc().unwrap();
+	/// assert_eq!((utc.month, utc.day, utc.hour, utc.minute), (2, 29, 23, 30));
+	/// ```
+	pub fn
+	to_utc
+	(
+		&self
+	)
+	-> Option<ExifDateTime>
+	{
+		let offset_minutes = self.offset_minutes?;
+
+		let local_minutes = days_from_civil(self.year as i64, self.month as i64, self.day as i64) * 1440
+			+ self.hour   as i64 * 60
+			+ self.minute as i64;
+		let utc_minutes   = local_minutes - offset_minutes as i64;
+
+		let (year, month, day) = civil_from_days(utc_minutes.div_euclid(1440));
+		let minute_of_day      = utc_minutes.rem_euclid(1440);
+
+		return Some(ExifDateTime {
+			year:           u16::try_from(year).ok()?,
+			month:          month as u8,
+			day:            day   as u8,
+			hour:           (minute_of_day / 60) as u8,
+			minute:         (minute_of_day % 60) as u8,
+			second:         self.second,
+			subsec_nanos:   self.subsec_nanos,
+			offset_minutes: Some(0),
+		});
+	}
+
+	/// Parses the digits of a `SubSecTime...` tag (e.g. "123" for 0.123
+	/// seconds) into nanoseconds. Trailing spaces are ignored, digits beyond
+	/// nanosecond precision are cut off.
+	pub(crate) fn
+	parse_subsec
+	(
+		subsec: &str
+	)
+	-> Option<u32>
+	{
+		let digits = subsec.trim_end_matches(|c| c == '\0' || c == ' ').as_bytes();
+
+		if digits.is_empty()
+		{
+			return None;
+		}
+
+		let mut nanos = parse_digits(&digits[..std::cmp::min(9, digits.len())])?;
+		for _ in digits.len()..9
+		{
+			nanos *= 10;
+		}
+		return Some(nanos);
+	}
+
+	/// Parses the value of an `OffsetTime...` tag in the format "+HH:MM" or
+	/// "-HH:MM" into minutes. A "Z" is interpreted as UTC.
+	pub(crate) fn
+	parse_offset
+	(
+		offset: &str
+	)
+	-> Option<i32>
+	{
+		let bytes = offset.trim_end_matches(|c| c == '\0' || c == ' ').as_bytes();
+
+		if bytes == b"Z"
+		{
+			return Some(0);
+		}
+
+		if bytes.len() != 6 || bytes[3] != b':'
+		{
+			return None;
+		}
+
+		let sign = match bytes[0]
+		{
+			b'+' =>  1,
+			b'-' => -1,
+			_    => return None,
+		};
+
+		let hours   = parse_digits(&bytes[1..3])? as i32;
+		let minutes = parse_digits(&bytes[4..6])? as i32;
+
+		if hours > 23 || minutes > 59
+		{
+			return None;
+		}
+
+		return Some(sign * (hours * 60 + minutes));
+	}
+}
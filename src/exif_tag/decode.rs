@@ -70,14 +70,13 @@ decode_tag_with_format_exceptions
 					// So, we try to convert the string by taking its first
 					// character. If it is 0x00 or 0x30 ("0") we set it to 0,
 					// if it is 0x01 or 0x31 ("1") we set it to 1, and
-					// otherwise we panic and tell the user to open a ticket.
+					// otherwise we return an error.
 
-					let first_char = raw_data[0];
-					let int8u_data = match first_char
+					let int8u_data = match raw_data.first()
 					{
-						0x00 | 0x30 => vec![0u8],
-						0x01 | 0x31 => vec![1u8],
-						_ => panic!("Problem while decoding GPSAltitudeRef. Please open a new issue for little_exif!")
+						Some(0x00 | 0x30) => vec![0u8],
+						Some(0x01 | 0x31) => vec![1u8],
+						_ => return io_error!(InvalidData, format!("Problem while decoding GPSAltitudeRef from STRING data: {:?}", raw_data)),
 					};
 
 					return Ok(ExifTag::from_u16_with_data(
@@ -110,4 +109,77 @@ decode_tag_with_format_exceptions
 			group
 		).unwrap());
 	}
-}
\ No newline at end of file
+}
+impl
+ExifTag
+{
+	/// Constructs a tag from the raw bytes of its value, e.g. as taken from
+	/// the entry of an IFD, which is the inverse of `value_as_u8_vec`.
+	/// The `data` needs to hold at least `count` components of the given 
+	/// format. Values of up to 4 bytes are stored inline in an IFD entry, so
+	/// in this case the 4 bytes of the entry's value field can be passed
+	/// directly, as any bytes beyond the value are ignored.
+	/// If the format differs from the format of the known tag, the value gets
+	/// converted if possible (e.g. INT16U to INT32U). Unknown tags result in
+	/// one of the `Unknown...` variants.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::endian::Endian;
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::exif_tag_format::ExifTagFormat;
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// let tag = ExifTag::from_raw(
+	///     0x8827,                     // ISO
+	///     ExifTagGroup::EXIF,
+	///     ExifTagFormat::INT16U,
+	///     1,
+	///     &[0x64, 0x00, 0x00, 0x00],  // Inline value field of the entry
+	///     &Endian::Little
+	/// ).unwrap();
+	///
+	/// assert_eq!(tag, ExifTag::ISO(vec![100]));
+	/// assert_eq!(tag.value_as_u8_vec(&Endian::Little), vec![0x64, 0x00]);
+	/// ```
+	pub fn
+	from_raw
+	(
+		hex:    u16,
+		group:  ExifTagGroup,
+		format: ExifTagFormat,
+		count:  u32,
+		data:   &[u8],
+		endian: &Endian
+	)
+	-> Result<ExifTag, std::io::Error>
+	{
+		let byte_count = format.bytes_per_component() as u64 * count as u64;
+		if (data.len() as u64) < byte_count
+		{
+			return io_error!(
+				InvalidData, 
+				format!("Expected {} bytes of raw data for tag {:#06x}, but got {}", byte_count, hex, data.len())
+			);
+		}
+
+		let raw_data = data[..byte_count as usize].to_vec();
+
+		match ExifTag::from_u16(hex, &group)
+		{
+			Ok(raw_tag) => decode_tag_with_format_exceptions(
+				&raw_tag,
+				format,
+				&raw_data,
+				endian,
+				hex,
+				&group
+			),
+			Err(_) => match ExifTag::from_u16_with_data(hex, &format, &raw_data, endian, &group)
+			{
+				Ok(tag)      => Ok(tag),
+				Err(message) => io_error!(InvalidData, message),
+			},
+		}
+	}
+}
@@ -0,0 +1,208 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use super::ExifTag;
+
+impl
+ExifTag
+{
+	/// Gets a short, human-readable description of an EXIF tag, e.g. for 
+	/// displaying it in a user interface. For unknown tags, an empty string
+	/// is returned.
+	/// Note: This match is kept exhaustive on purpose, so that new tags can't
+	/// be added to the table in mod.rs without a description.
+	/// 
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// 
+	/// assert_eq!(ExifTag::FNumber(Vec::new()).description(), "F number of the lens");
+	/// ```
+	pub fn
+	description
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			ExifTag::GPSVersionID(_)                => "Version of the GPS IFD",
+			ExifTag::GPSLatitudeRef(_)              => "North or south latitude",
+			ExifTag::GPSLatitude(_)                 => "Latitude in degrees, minutes and seconds",
+			ExifTag::GPSLongitudeRef(_)             => "East or west longitude",
+			ExifTag::GPSLongitude(_)                => "Longitude in degrees, minutes and seconds",
+			ExifTag::GPSAltitudeRef(_)              => "Altitude above or below sea level",
+			ExifTag::GPSAltitude(_)                 => "Altitude in meters",
+			ExifTag::GPSTimeStamp(_)                => "Time of the GPS fix (UTC)",
+			ExifTag::GPSSatellites(_)               => "Satellites used for the measurement",
+			ExifTag::GPSStatus(_)                   => "Status of the GPS receiver",
+			ExifTag::GPSMeasureMode(_)              => "2D or 3D measurement",
+			ExifTag::GPSDOP(_)                      => "Dilution of precision of the measurement",
+			ExifTag::GPSSpeedRef(_)                 => "Unit of the speed",
+			ExifTag::GPSSpeed(_)                    => "Speed of the GPS receiver",
+			ExifTag::GPSTrackRef(_)                 => "Reference for the direction of movement",
+			ExifTag::GPSTrack(_)                    => "Direction of movement",
+			ExifTag::GPSImgDirectionRef(_)          => "Reference for the image direction",
+			ExifTag::GPSImgDirection(_)             => "Direction of the image when captured",
+			ExifTag::GPSMapDatum(_)                 => "Geodetic survey data used",
+			ExifTag::GPSDestLatitudeRef(_)          => "North or south latitude of the destination",
+			ExifTag::GPSDestLatitude(_)             => "Latitude of the destination",
+			ExifTag::GPSDestLongitudeRef(_)         => "East or west longitude of the destination",
+			ExifTag::GPSDestLongitude(_)            => "Longitude of the destination",
+			ExifTag::GPSDestBearingRef(_)           => "Reference for the bearing to the destination",
+			ExifTag::GPSDestBearing(_)              => "Bearing to the destination",
+			ExifTag::GPSDestDistanceRef(_)          => "Unit of the distance to the destination",
+			ExifTag::GPSDestDistance(_)             => "Distance to the destination",
+			ExifTag::GPSProcessingMethod(_)         => "Name of the method used for finding the location",
+			ExifTag::GPSAreaInformation(_)          => "Name of the GPS area",
+			ExifTag::GPSDateStamp(_)                => "Date of the GPS fix (UTC)",
+			ExifTag::GPSDifferential(_)             => "Whether differential correction was applied",
+			ExifTag::GPSHPositioningError(_)        => "Horizontal positioning error in meters",
+
+			ExifTag::InteroperabilityIndex(_)       => "Interoperability rule set",
+			ExifTag::InteroperabilityVersion(_)     => "Version of the interoperability rule set",
+			ExifTag::RelatedImageFileFormat(_)      => "File format of the related image",
+			ExifTag::RelatedImageWidth(_)           => "Width of the related image",
+			ExifTag::RelatedImageHeight(_)          => "Height of the related image",
+
+			ExifTag::ImageWidth(_)                  => "Number of columns of image data",
+			ExifTag::ImageHeight(_)                 => "Number of rows of image data",
+			ExifTag::BitsPerSample(_)               => "Number of bits per component",
+			ExifTag::Compression(_)                 => "Compression scheme of the image data",
+			ExifTag::PhotometricInterpretation(_)   => "Color space of the image data",
+			ExifTag::CellWidth(_)                   => "Width of the dithering or halftoning matrix",
+			ExifTag::CellHeight(_)                  => "Height of the dithering or halftoning matrix",
+			ExifTag::ImageDescription(_)            => "Title or description of the image",
+			ExifTag::Make(_)                        => "Manufacturer of the camera",
+			ExifTag::Model(_)                       => "Model of the camera",
+			ExifTag::Orientation(_)                 => "Orientation of the image",
+			ExifTag::SamplesPerPixel(_)             => "Number of components per pixel",
+			ExifTag::RowsPerStrip(_)                => "Number of rows per strip",
+			ExifTag::XResolution(_)                 => "Number of pixels per resolution unit in width direction",
+			ExifTag::YResolution(_)                 => "Number of pixels per resolution unit in height direction",
+			ExifTag::PlanarConfiguration(_)         => "Chunky or planar storage of the components",
+			ExifTag::ResolutionUnit(_)              => "Unit of the X and Y resolution",
+			ExifTag::TransferFunction(_)            => "Transfer function of the image",
+			ExifTag::Software(_)                    => "Software used for creating the image",
+			ExifTag::ModifyDate(_)                  => "Date and time of the last modification",
+			ExifTag::Artist(_)                      => "Person who created the image",
+			ExifTag::WhitePoint(_)                  => "Chromaticity of the white point",
+			ExifTag::PrimaryChromaticities(_)       => "Chromaticities of the primary colors",
+			ExifTag::ColorMap(_)                    => "Color map of a palette-color image",
+			ExifTag::YCbCrCoefficients(_)           => "Coefficients for converting RGB to YCbCr",
+			ExifTag::YCbCrSubSampling(_)            => "Subsampling ratio of the chrominance components",
+			ExifTag::YCbCrPositioning(_)            => "Position of the chrominance components",
+			ExifTag::ReferenceBlackWhite(_)         => "Reference black and white point values",
+			ExifTag::Copyright(_)                   => "Copyright holder of the image",
+			ExifTag::ExposureTime(_)                => "Exposure time in seconds",
+			ExifTag::FNumber(_)                     => "F number of the lens",
+			ExifTag::ExifOffset(_)                  => "Offset to the Exif IFD",
+			ExifTag::ExposureProgram(_)             => "Program used for setting the exposure",
+			ExifTag::SpectralSensitivity(_)         => "Spectral sensitivity of each channel",
+			ExifTag::GPSInfo(_)                     => "Offset to the GPS IFD",
+			ExifTag::ISO(_)                         => "ISO speed rating",
+			ExifTag::OECF(_)                        => "Opto-electronic conversion function",
+			ExifTag::SensitivityType(_)             => "Parameter used for the ISO value",
+			ExifTag::StandardOutputSensitivity(_)   => "Standard output sensitivity",
+			ExifTag::RecommendedExposureIndex(_)    => "Recommended exposure index",
+			ExifTag::ISOSpeed(_)                    => "ISO speed value",
+			ExifTag::ISOSpeedLatitudeyyy(_)         => "ISO speed latitude yyy value",
+			ExifTag::ISOSpeedLatitudezzz(_)         => "ISO speed latitude zzz value",
+			ExifTag::ExifVersion(_)                 => "Version of the Exif standard",
+			ExifTag::DateTimeOriginal(_)            => "Date and time when the image was captured",
+			ExifTag::CreateDate(_)                  => "Date and time when the image was digitized",
+			ExifTag::OffsetTime(_)                  => "Time zone offset of ModifyDate",
+			ExifTag::OffsetTimeOriginal(_)          => "Time zone offset of DateTimeOriginal",
+			ExifTag::OffsetTimeDigitized(_)         => "Time zone offset of CreateDate",
+			ExifTag::ComponentsConfiguration(_)     => "Order of the components",
+			ExifTag::CompressedBitsPerPixel(_)      => "Compression mode in bits per pixel",
+			ExifTag::ShutterSpeedValue(_)           => "Shutter speed in APEX units",
+			ExifTag::ApertureValue(_)               => "Aperture in APEX units",
+			ExifTag::BrightnessValue(_)             => "Brightness in APEX units",
+			ExifTag::ExposureCompensation(_)        => "Exposure bias in APEX units",
+			ExifTag::MaxApertureValue(_)            => "Smallest F number of the lens in APEX units",
+			ExifTag::SubjectDistance(_)             => "Distance to the subject in meters",
+			ExifTag::MeteringMode(_)                => "Metering mode",
+			ExifTag::LightSource(_)                 => "Kind of light source",
+			ExifTag::Flash(_)                       => "Status of the flash",
+			ExifTag::FocalLength(_)                 => "Focal length of the lens in millimeters",
+			ExifTag::SubjectArea(_)                 => "Location and area of the main subject",
+			ExifTag::MakerNote(_)                   => "Manufacturer specific data",
+			ExifTag::UserComment(_)                 => "Comments of the user",
+			ExifTag::SubSecTime(_)                  => "Fractions of seconds of ModifyDate",
+			ExifTag::SubSecTimeOriginal(_)          => "Fractions of seconds of DateTimeOriginal",
+			ExifTag::SubSecTimeDigitized(_)         => "Fractions of seconds of CreateDate",
+			ExifTag::AmbientTemperature(_)          => "Ambient temperature in degrees Celsius",
+			ExifTag::Humidity(_)                    => "Ambient relative humidity in percent",
+			ExifTag::Pressure(_)                    => "Ambient air pressure in hectopascal",
+			ExifTag::WaterDepth(_)                  => "Water depth in meters",
+			ExifTag::Acceleration(_)                => "Acceleration in milligal",
+			ExifTag::CameraElevationAngle(_)        => "Elevation angle of the camera in degrees",
+			ExifTag::XPTitle(_)                     => "Title of the image (Windows)",
+			ExifTag::XPComment(_)                   => "Comment on the image (Windows)",
+			ExifTag::XPAuthor(_)                    => "Author of the image (Windows)",
+			ExifTag::XPKeywords(_)                  => "Keywords describing the image (Windows)",
+			ExifTag::XPSubject(_)                   => "Subject of the image (Windows)",
+			ExifTag::FlashpixVersion(_)             => "Supported Flashpix version",
+			ExifTag::ColorSpace(_)                  => "Color space",
+			ExifTag::ExifImageWidth(_)              => "Width of the image",
+			ExifTag::ExifImageHeight(_)             => "Height of the image",
+			ExifTag::RelatedSoundFile(_)            => "Name of an audio file related to the image",
+			ExifTag::InteropOffset(_)               => "Offset to the Interoperability IFD",
+			ExifTag::FlashEnergy(_)                 => "Strobe energy in beam candle power seconds",
+			ExifTag::SpatialFrequencyResponse(_)    => "Spatial frequency table and response values",
+			ExifTag::FocalPlaneXResolution(_)       => "Pixels per focal plane resolution unit in width direction",
+			ExifTag::FocalPlaneYResolution(_)       => "Pixels per focal plane resolution unit in height direction",
+			ExifTag::FocalPlaneResolutionUnit(_)    => "Unit of the focal plane resolution",
+			ExifTag::SubjectLocation(_)             => "Location of the main subject",
+			ExifTag::ExposureIndex(_)               => "Exposure index",
+			ExifTag::SensingMethod(_)               => "Type of the image sensor",
+			ExifTag::FileSource(_)                  => "Source of the image",
+			ExifTag::SceneType(_)                   => "Type of the scene",
+			ExifTag::CFAPattern(_)                  => "Color filter array pattern of the sensor",
+			ExifTag::CustomRendered(_)              => "Use of special processing",
+			ExifTag::ExposureMode(_)                => "Exposure mode",
+			ExifTag::WhiteBalance(_)                => "White balance mode",
+			ExifTag::DigitalZoomRatio(_)            => "Digital zoom ratio",
+			ExifTag::FocalLengthIn35mmFormat(_)     => "Equivalent focal length for 35mm film",
+			ExifTag::SceneCaptureType(_)            => "Type of the captured scene",
+			ExifTag::GainControl(_)                 => "Degree of overall image gain adjustment",
+			ExifTag::Contrast(_)                    => "Contrast processing",
+			ExifTag::Saturation(_)                  => "Saturation processing",
+			ExifTag::Sharpness(_)                   => "Sharpness processing",
+			ExifTag::DeviceSettingDescription(_)    => "Picture-taking conditions of the camera",
+			ExifTag::SubjectDistanceRange(_)        => "Range of the distance to the subject",
+			ExifTag::ImageUniqueID(_)               => "Unique identifier of the image",
+			ExifTag::OwnerName(_)                   => "Name of the camera owner",
+			ExifTag::SerialNumber(_)                => "Serial number of the camera body",
+			ExifTag::LensInfo(_)                    => "Focal length and F number range of the lens",
+			ExifTag::LensMake(_)                    => "Manufacturer of the lens",
+			ExifTag::LensModel(_)                   => "Model of the lens",
+			ExifTag::LensSerialNumber(_)            => "Serial number of the lens",
+			ExifTag::CompositeImage(_)              => "Whether the image is a composite image",
+			ExifTag::CompositeImageCount(_)         => "Number of source images of a composite image",
+			ExifTag::CompositeImageExposureTimes(_) => "Exposure times of the source images",
+			ExifTag::Gamma(_)                       => "Gamma coefficient",
+
+			ExifTag::StripOffsets(_, _)             => "Image data of the strips",
+			ExifTag::StripByteCounts(_)             => "Number of bytes per strip",
+
+			ExifTag::ThumbnailOffset(_, _)          => "Image data of the thumbnail",
+			ExifTag::ThumbnailLength(_)             => "Number of bytes of the thumbnail",
+
+			ExifTag::UnknownINT8U(_, _, _)
+			| ExifTag::UnknownSTRING(_, _, _)
+			| ExifTag::UnknownINT16U(_, _, _)
+			| ExifTag::UnknownINT32U(_, _, _)
+			| ExifTag::UnknownRATIONAL64U(_, _, _)
+			| ExifTag::UnknownINT8S(_, _, _)
+			| ExifTag::UnknownUNDEF(_, _, _)
+			| ExifTag::UnknownINT16S(_, _, _)
+			| ExifTag::UnknownINT32S(_, _, _)
+			| ExifTag::UnknownRATIONAL64S(_, _, _)
+			| ExifTag::UnknownFLOAT(_, _, _)
+			| ExifTag::UnknownDOUBLE(_, _, _)       => "",
+		}
+	}
+}
@@ -2,14 +2,20 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 pub(crate) mod decode;
+pub(crate) mod description;
 pub(super) mod set_value_to;
 
+#[cfg(feature = "serde")]
+mod serialization;
+
 use paste::paste;
 
 use crate::endian::Endian;
 use crate::u8conversion::*;
 use crate::exif_tag_format::*;
 use crate::ifd::ExifTagGroup;
+use crate::rational::iR64;
+use crate::rational::uR64;
 
 #[allow(non_camel_case_types)]
 #[derive(PartialEq)]
@@ -37,6 +43,9 @@ macro_rules! build_tag_enum {
 		/// These are the currently supported tags by little_exif. 
 		/// Note that for tags that are unknown at the moment a fallback
 		/// solution is provided using the `Unknown...` variants. 
+		/// They carry the value (with its format given by the variant), the
+		/// hex value and the group of the tag, so that unknown tags are kept
+		/// when decoding and written back unchanged when encoding.
 		#[derive(PartialEq, Debug, Clone)]
 		pub enum
 		ExifTag
@@ -102,16 +111,57 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the name of an EXIF tag, e.g. "FNumber". For unknown tags,
+			/// "Unknown" is returned.
+			/// 
+			/// # Examples
+			/// ```
+			/// use little_exif::exif_tag::ExifTag;
+			/// 
+			/// assert_eq!(ExifTag::FNumber(Vec::new()).name(), "FNumber");
+			/// ```
+			pub fn
+			name
+			(
+				&self
+			)
+			-> &'static str
+			{
+				match *self
+				{
+					$(
+						ExifTag::$tag(_) => stringify!($tag),
+					)*
+
+					ExifTag::StripOffsets(       _, _,       ) => "StripOffsets",
+					ExifTag::StripByteCounts(    _,          ) => "StripByteCounts",
+
+					ExifTag::ThumbnailOffset(    _, _,       ) => "ThumbnailOffset",
+					ExifTag::ThumbnailLength(    _,          ) => "ThumbnailLength",
+
+					_                                          => "Unknown",
+				}
+			}
+
 			/// Gets the tag for a given hex value. 
 			/// The tag is initialized with new, empty data.
 			/// If the hex value is unknown, an error is returned.
+			/// As hex values are only unique within a group (e.g. 0x0001 is the
+			/// `GPSLatitudeRef` in the GPS IFD but the `InteroperabilityIndex`
+			/// in the Interoperability IFD), the group is required as well.
 			/// 
 			/// # Examples
-			/// ```no_run
+			/// ```
 			/// use little_exif::exif_tag::ExifTag;
 			/// use little_exif::ifd::ExifTagGroup;
 			/// 
 			/// let tag = ExifTag::from_u16(0x010e, &ExifTagGroup::GENERIC).unwrap();
+			/// assert_eq!(tag, ExifTag::ImageDescription(String::new()));
+			///
+			/// let gps_tag     = ExifTag::from_u16(0x0001, &ExifTagGroup::GPS).unwrap();
+			/// let interop_tag = ExifTag::from_u16(0x0001, &ExifTagGroup::INTEROP).unwrap();
+			/// assert_eq!(gps_tag,     ExifTag::GPSLatitudeRef(String::new()));
+			/// assert_eq!(interop_tag, ExifTag::InteroperabilityIndex(String::new()));
 			/// ```
 			pub fn
 			from_u16
@@ -184,24 +234,41 @@ macro_rules! build_tag_enum {
 					(0x0201, _) => Ok(ExifTag::ThumbnailOffset(<INT32U as U8conversion<INT32U>>::from_u8_vec(&raw_data, endian), Vec::new())),
 					(0x0202, _) => Ok(ExifTag::ThumbnailLength(<INT32U as U8conversion<INT32U>>::from_u8_vec(&raw_data, endian),           )),
 
-					_ => {
-						// In this case, the given hex_value represents a tag that is unknown
-						match *format
-						{
-							ExifTagFormat::INT8U       => Ok(ExifTag::UnknownINT8U(      <INT8U       as U8conversion<INT8U      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::STRING      => Ok(ExifTag::UnknownSTRING(     <STRING      as U8conversion<STRING     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT16U      => Ok(ExifTag::UnknownINT16U(     <INT16U      as U8conversion<INT16U     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT32U      => Ok(ExifTag::UnknownINT32U(     <INT32U      as U8conversion<INT32U     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::RATIONAL64U => Ok(ExifTag::UnknownRATIONAL64U(<RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT8S       => Ok(ExifTag::UnknownINT8S(      <INT8S       as U8conversion<INT8S      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::UNDEF       => Ok(ExifTag::UnknownUNDEF(      <UNDEF       as U8conversion<UNDEF      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT16S      => Ok(ExifTag::UnknownINT16S(     <INT16S      as U8conversion<INT16S     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT32S      => Ok(ExifTag::UnknownINT32S(     <INT32S      as U8conversion<INT32S     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::RATIONAL64S => Ok(ExifTag::UnknownRATIONAL64S(<RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::FLOAT       => Ok(ExifTag::UnknownFLOAT(      <FLOAT       as U8conversion<FLOAT      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::DOUBLE      => Ok(ExifTag::UnknownDOUBLE(     <DOUBLE      as U8conversion<DOUBLE     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-						}
-					},
+					// In this case, the given hex_value represents a tag that is unknown
+					_ => Ok(Self::unknown_from_u8_vec(hex_value, format, raw_data, endian, group)),
+				}
+			}
+
+			/// Creates one of the `Unknown...` variants for the given hex value
+			/// and format, regardless of whether the hex value belongs to a 
+			/// known tag. This allows preserving the raw value of tags that 
+			/// can't be decoded otherwise, e.g. a known tag that is stored 
+			/// using a format that can't be converted to the expected one.
+			pub(crate) fn
+			unknown_from_u8_vec
+			(
+				hex_value: u16,
+				format:    &ExifTagFormat,
+				raw_data:  &Vec<u8>,
+				endian:    &Endian,
+				group:     &ExifTagGroup,
+			)
+			-> ExifTag
+			{
+				match *format
+				{
+					ExifTagFormat::INT8U       => ExifTag::UnknownINT8U(      <INT8U       as U8conversion<INT8U      >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::STRING      => ExifTag::UnknownSTRING(     <STRING      as U8conversion<STRING     >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::INT16U      => ExifTag::UnknownINT16U(     <INT16U      as U8conversion<INT16U     >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::INT32U      => ExifTag::UnknownINT32U(     <INT32U      as U8conversion<INT32U     >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::RATIONAL64U => ExifTag::UnknownRATIONAL64U(<RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::INT8S       => ExifTag::UnknownINT8S(      <INT8S       as U8conversion<INT8S      >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::UNDEF       => ExifTag::UnknownUNDEF(      <UNDEF       as U8conversion<UNDEF      >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::INT16S      => ExifTag::UnknownINT16S(     <INT16S      as U8conversion<INT16S     >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::INT32S      => ExifTag::UnknownINT32S(     <INT32S      as U8conversion<INT32S     >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::RATIONAL64S => ExifTag::UnknownRATIONAL64S(<RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::FLOAT       => ExifTag::UnknownFLOAT(      <FLOAT       as U8conversion<FLOAT      >>::from_u8_vec(raw_data, endian), hex_value, *group),
+					ExifTagFormat::DOUBLE      => ExifTag::UnknownDOUBLE(     <DOUBLE      as U8conversion<DOUBLE     >>::from_u8_vec(raw_data, endian), hex_value, *group),
 				}
 			}
 
@@ -400,7 +467,7 @@ macro_rules! build_tag_enum {
 						},
 					)*
 
-					ExifTag::StripOffsets(       _, value      ) => value.len() as u32,
+					ExifTag::StripOffsets( offsets, value      ) => if value.is_empty() { offsets.len() as u32 } else { value.len() as u32 },
 					ExifTag::StripByteCounts(value,            ) => value.len() as u32,
 
 					ExifTag::ThumbnailOffset(    _, _          ) => 1,
@@ -421,6 +488,42 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the number of components the specification requires for
+			/// the tag, independent of the data currently stored in it, e.g.
+			/// 3 for `GPSLatitude` or 1 for `FNumber`. For `STRING` tags with
+			/// a fixed length (e.g. `GPSLatitudeRef`) this includes the NUL
+			/// terminator, see `number_of_components`. 
+			/// Returns `None` for tags with a variable number of components
+			/// (e.g. `ImageDescription`) and for `Unknown...` tags.
+			///
+			/// # Examples
+			/// ```
+			/// use little_exif::exif_tag::ExifTag;
+			///
+			/// assert_eq!(ExifTag::GPSLatitude(Vec::new()).expected_count(),          Some(3));
+			/// assert_eq!(ExifTag::FNumber(Vec::new()).expected_count(),              Some(1));
+			/// assert_eq!(ExifTag::ImageDescription(String::new()).expected_count(), None);
+			/// ```
+			pub fn
+			expected_count
+			(
+				&self
+			)
+			-> Option<u32>
+			{
+				match self
+				{
+					$(
+						ExifTag::$tag(_) => $component_number,
+					)*
+
+					ExifTag::ThumbnailOffset(_, _) => Some(1),
+					ExifTag::ThumbnailLength(_   ) => Some(1),
+
+					_                              => None,
+				}
+			}
+
 			/// Checks if the format type of the tag is `STRING`.
 			/// Needed for generating the EXIF data to know whether to add a 
 			/// NUL terminator at the end
@@ -538,6 +641,9 @@ build_tag_enum![
 	// Tag                        Tag ID  Format         Nr. Components     Writable   Group                             Required by        bilevel grayscale palette-color full-color
 	(InteroperabilityIndex,       0x0001, STRING,        Some::<u32>(4),    true,      INTEROP),
 	(InteroperabilityVersion,     0x0002, UNDEF,         None::<u32>,       true,      INTEROP),
+	(RelatedImageFileFormat,      0x1000, STRING,        None::<u32>,       true,      INTEROP),
+	(RelatedImageWidth,           0x1001, INT16U,        Some::<u32>(1),    true,      INTEROP),
+	(RelatedImageHeight,          0x1002, INT16U,        Some::<u32>(1),    true,      INTEROP),
 
 	(ImageWidth,                  0x0100, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 	(ImageHeight,                 0x0101, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
@@ -641,6 +747,12 @@ build_tag_enum![
 	(Acceleration,                0x9404, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
 	(CameraElevationAngle,        0x9405, RATIONAL64S,   Some::<u32>(1),    true,      EXIF),
 
+	(XPTitle,                     0x9c9b, INT8U,         None::<u32>,       true,      GENERIC),                        // UTF-16LE encoded, see metadata::xp
+	(XPComment,                   0x9c9c, INT8U,         None::<u32>,       true,      GENERIC),
+	(XPAuthor,                    0x9c9d, INT8U,         None::<u32>,       true,      GENERIC),
+	(XPKeywords,                  0x9c9e, INT8U,         None::<u32>,       true,      GENERIC),
+	(XPSubject,                   0x9c9f, INT8U,         None::<u32>,       true,      GENERIC),
+
 	(FlashpixVersion,             0xa000, UNDEF,         Some::<u32>(4),    true,      EXIF),
 	(ColorSpace,                  0xa001, INT16U,        Some::<u32>(1),    true,      EXIF),
 	(ExifImageWidth,              0xa002, INT32U,        Some::<u32>(1),    true,      EXIF),
@@ -726,4 +838,428 @@ impl ExifTag
 			_ => TagType::VALUE
 		}
 	}
-}
\ No newline at end of file
+}
+impl ExifTag
+{
+	/// Gets the value of a `RATIONAL64U` tag (e.g. `FNumber`, `ExposureTime`,
+	/// `FocalLength` or `GPSLatitude`) as pairs of numerator and denominator.
+	/// Returns `None` for all other formats, including `RATIONAL64S`.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::rational::uR64;
+	///
+	/// let tag = ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 250 }]);
+	/// assert_eq!(tag.as_rational_vec(), Some(vec![(1, 250)]));
+	/// assert_eq!(ExifTag::ISO(vec![100]).as_rational_vec(), None);
+	/// ```
+	pub fn
+	as_rational_vec
+	(
+		&self
+	)
+	-> Option<Vec<(u32, u32)>>
+	{
+		if self.format() != ExifTagFormat::RATIONAL64U
+		{
+			return None;
+		}
+
+		// Converting back and forth avoids having to list all rational tags
+		let rationals = <RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(
+			&self.value_as_u8_vec(&Endian::Little), 
+			&Endian::Little
+		);
+
+		return Some(rationals.iter().map(|rational| (rational.nominator, rational.denominator)).collect());
+	}
+
+	/// Gets the value of a `RATIONAL64S` tag (e.g. `ShutterSpeedValue` or
+	/// `BrightnessValue`) as pairs of numerator and denominator.
+	/// Returns `None` for all other formats, including `RATIONAL64U`.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::rational::iR64;
+	///
+	/// let tag = ExifTag::ShutterSpeedValue(vec![iR64 { nominator: -1, denominator: 3 }]);
+	/// assert_eq!(tag.as_srational_vec(), Some(vec![(-1, 3)]));
+	/// assert_eq!(ExifTag::ISO(vec![100]).as_srational_vec(), None);
+	/// ```
+	pub fn
+	as_srational_vec
+	(
+		&self
+	)
+	-> Option<Vec<(i32, i32)>>
+	{
+		if self.format() != ExifTagFormat::RATIONAL64S
+		{
+			return None;
+		}
+
+		let rationals = <RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(
+			&self.value_as_u8_vec(&Endian::Little), 
+			&Endian::Little
+		);
+
+		return Some(rationals.iter().map(|rational| (rational.nominator, rational.denominator)).collect());
+	}
+
+	/// Gets the value of an integer tag whose values fit into an `i32`, i.e.
+	/// the signed formats `INT8S`, `INT16S` and `INT32S` as well as `INT8U`
+	/// and `INT16U`. Returns `None` for all other formats.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// let tag = ExifTag::UnknownINT16S(vec![-42, 1337], 0xc000, ExifTagGroup::GENERIC);
+	/// assert_eq!(tag.as_i32_vec(), Some(vec![-42, 1337]));
+	/// assert_eq!(ExifTag::ISO(vec![100]).as_i32_vec(), Some(vec![100]));
+	/// ```
+	pub fn
+	as_i32_vec
+	(
+		&self
+	)
+	-> Option<Vec<i32>>
+	{
+		let raw_data = self.value_as_u8_vec(&Endian::Little);
+
+		return match self.format()
+		{
+			ExifTagFormat::INT8U  => Some(<INT8U  as U8conversion<INT8U >>::from_u8_vec(&raw_data, &Endian::Little).into_iter().map(i32::from).collect()),
+			ExifTagFormat::INT16U => Some(<INT16U as U8conversion<INT16U>>::from_u8_vec(&raw_data, &Endian::Little).into_iter().map(i32::from).collect()),
+			ExifTagFormat::INT8S  => Some(<INT8S  as U8conversion<INT8S >>::from_u8_vec(&raw_data, &Endian::Little).into_iter().map(i32::from).collect()),
+			ExifTagFormat::INT16S => Some(<INT16S as U8conversion<INT16S>>::from_u8_vec(&raw_data, &Endian::Little).into_iter().map(i32::from).collect()),
+			ExifTagFormat::INT32S => Some(<INT32S as U8conversion<INT32S>>::from_u8_vec(&raw_data, &Endian::Little)),
+			_                     => None,
+		};
+	}
+
+	/// Gets the value of a `RATIONAL64U` or `RATIONAL64S` tag as floating
+	/// point numbers by dividing each numerator by its denominator. 
+	/// A denominator of zero results in (negative) infinity if 
+	/// `zero_denominator_as_infinity` is set, otherwise `None` is returned.
+	/// The values of `FLOAT` and `DOUBLE` tags are returned as they are.
+	/// Returns `None` for all other formats.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::rational::uR64;
+	///
+	/// let tag = ExifTag::FNumber(vec![uR64 { nominator: 28, denominator: 10 }]);
+	/// assert_eq!(tag.as_f64_vec(false), Some(vec![2.8]));
+	///
+	/// let tag = ExifTag::FNumber(vec![uR64 { nominator: 28, denominator: 0 }]);
+	/// assert_eq!(tag.as_f64_vec(false), None);
+	/// assert_eq!(tag.as_f64_vec(true),  Some(vec![f64::INFINITY]));
+	/// ```
+	pub fn
+	as_f64_vec
+	(
+		&self,
+		zero_denominator_as_infinity: bool
+	)
+	-> Option<Vec<f64>>
+	{
+		let pairs: Vec<(f64, f64)> = match self.format()
+		{
+			ExifTagFormat::RATIONAL64U => self.as_rational_vec()?.iter()
+				.map(|(nominator, denominator)| (*nominator as f64, *denominator as f64))
+				.collect(),
+
+			ExifTagFormat::RATIONAL64S => self.as_srational_vec()?.iter()
+				.map(|(nominator, denominator)| (*nominator as f64, *denominator as f64))
+				.collect(),
+
+			ExifTagFormat::FLOAT => return Some(
+				<FLOAT as U8conversion<FLOAT>>::from_u8_vec(&self.value_as_u8_vec(&Endian::Little), &Endian::Little)
+					.into_iter()
+					.map(f64::from)
+					.collect()
+			),
+
+			ExifTagFormat::DOUBLE => return Some(
+				<DOUBLE as U8conversion<DOUBLE>>::from_u8_vec(&self.value_as_u8_vec(&Endian::Little), &Endian::Little)
+			),
+
+			_ => return None,
+		};
+
+		let mut values = Vec::new();
+		for (nominator, denominator) in pairs
+		{
+			if denominator == 0.0
+			{
+				if !zero_denominator_as_infinity
+				{
+					return None;
+				}
+				values.push(if nominator < 0.0 { f64::NEG_INFINITY } else { f64::INFINITY });
+				continue;
+			}
+			values.push(nominator / denominator);
+		}
+
+		return Some(values);
+	}
+
+	/// Gets the EXIF field type of the tag as written to its IFD entry, e.g.
+	/// 3 for `INT16U` or 5 for `RATIONAL64U`. Shorthand for 
+	/// `self.format().as_u16()`, as companion to `as_u16` (the tag id).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::ISO(vec![100]).type_code(), 3);
+	/// ```
+	pub fn
+	type_code
+	(
+		&self
+	)
+	-> u16
+	{
+		return self.format().as_u16();
+	}
+
+	/// Gets the number of components as written to the IFD entry of the tag,
+	/// see `number_of_components` for details (e.g. regarding the NUL 
+	/// terminator of strings).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::Model("Camera".to_string()).count(), 7);
+	/// ```
+	pub fn
+	count
+	(
+		&self
+	)
+	-> u32
+	{
+		return self.number_of_components();
+	}
+
+	/// Gets the raw on-disk representation of the tag for debugging, i.e.
+	/// the 12 byte IFD entry (tag, format, number of components and value or
+	/// offset) followed by the value area if the value does not fit into the
+	/// entry. Unlike `value_as_u8_vec`, this includes the type and count 
+	/// fields as well as the NUL padding of strings.
+	/// As the position of the value area depends on the surrounding data, 
+	/// its offset in the entry is given as zero. The same applies to offsets
+	/// to other IFDs and image data, which are computed when encoding.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::endian::Endian;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let tag = ExifTag::ISO(vec![100]);
+	/// assert_eq!(
+	///     tag.debug_bytes(&Endian::Little), 
+	///     vec![0x27, 0x88, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00]
+	/// );
+	///
+	/// // The string and its NUL terminator don't fit into the entry
+	/// let tag = ExifTag::Model("Camera".to_string());
+	/// assert_eq!(tag.debug_bytes(&Endian::Big).len(), 12 + 7);
+	/// ```
+	pub fn
+	debug_bytes
+	(
+		&self,
+		endian: &Endian
+	)
+	-> Vec<u8>
+	{
+		let number_of_components = self.count();
+		let byte_count           = number_of_components as usize * self.format().bytes_per_component() as usize;
+
+		// Strings get padded with NUL bytes to the number of components, see
+		// `number_of_components`
+		let mut value = self.value_as_u8_vec(endian);
+		if value.len() < byte_count
+		{
+			value.resize(byte_count, 0x00);
+		}
+
+		let mut debug_bytes = Vec::new();
+		debug_bytes.extend(to_u8_vec_macro!(u16, &self.as_u16(),          endian));
+		debug_bytes.extend(to_u8_vec_macro!(u16, &self.type_code(),       endian));
+		debug_bytes.extend(to_u8_vec_macro!(u32, &number_of_components,   endian));
+
+		if byte_count > 4
+		{
+			debug_bytes.extend([0x00; 4]);
+			debug_bytes.extend(value);
+		}
+		else
+		{
+			value.resize(4, 0x00);
+			debug_bytes.extend(value);
+		}
+
+		return debug_bytes;
+	}
+
+	/// Formats the bytes returned by `debug_bytes` as hex dump, e.g. for bug
+	/// reports. The first line contains the IFD entry, followed by the value
+	/// area (if any) with 16 bytes per line, prefixed by their position.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::endian::Endian;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let tag = ExifTag::Model("Camera".to_string());
+	/// assert_eq!(
+	///     tag.hex_dump(&Endian::Little), 
+	///     "entry: 10 01 02 00 07 00 00 00 00 00 00 00\n0000:  43 61 6d 65 72 61 00"
+	/// );
+	/// ```
+	pub fn
+	hex_dump
+	(
+		&self,
+		endian: &Endian
+	)
+	-> String
+	{
+		let format_hex = |bytes: &[u8]| bytes.iter()
+			.map(|byte| format!("{:02x}", byte))
+			.collect::<Vec<String>>()
+			.join(" ");
+
+		let debug_bytes     = self.debug_bytes(endian);
+		let (entry, values) = debug_bytes.split_at(12);
+
+		let mut lines = vec![format!("entry: {}", format_hex(entry))];
+		for (index, line) in values.chunks(16).enumerate()
+		{
+			lines.push(format!("{:04x}:  {}", index * 16, format_hex(line)));
+		}
+
+		return lines.join("\n");
+	}
+
+	/// Creates a `RATIONAL64U` tag (e.g. `ExposureTime` or `GPSLatitude`)
+	/// from pairs of numerator and denominator. The tag to create is given
+	/// by `tag`, e.g. `ExifTag::GPSLatitude(Vec::new())` or an `Unknown...`
+	/// tag with the desired hex value and group. Its value gets ignored.
+	/// As the values are stored as numbers (and not as bytes), they get 
+	/// written using the byte order of the metadata they are stored in.
+	/// Returns an error if the tag is not a `RATIONAL64U` tag or the number
+	/// of values does not match the number of components expected for the
+	/// tag (if known).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let tag = ExifTag::new_rational(
+	///     &ExifTag::GPSLatitude(Vec::new()), 
+	///     vec![(48, 1), (12, 1), (3456, 100)]
+	/// ).unwrap();
+	/// assert_eq!(tag.as_rational_vec(), Some(vec![(48, 1), (12, 1), (3456, 100)]));
+	///
+	/// // GPSLatitude requires exactly three values
+	/// assert!(ExifTag::new_rational(&ExifTag::GPSLatitude(Vec::new()), vec![(48, 1)]).is_err());
+	/// ```
+	pub fn
+	new_rational
+	(
+		tag:    &ExifTag,
+		values: Vec<(u32, u32)>
+	)
+	-> Result<ExifTag, String>
+	{
+		let rationals: RATIONAL64U = values.iter()
+			.map(|(nominator, denominator)| uR64 { nominator: *nominator, denominator: *denominator })
+			.collect();
+
+		return Self::new_with_rational_data(
+			tag, 
+			ExifTagFormat::RATIONAL64U, 
+			values.len(), 
+			&rationals.to_u8_vec(&Endian::Little)
+		);
+	}
+
+	/// Same as `new_rational`, but for `RATIONAL64S` tags (e.g. 
+	/// `ExposureCompensation` or `ShutterSpeedValue`).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let tag = ExifTag::new_srational(&ExifTag::ExposureCompensation(Vec::new()), vec![(-2, 3)]).unwrap();
+	/// assert_eq!(tag.as_f64_vec(false), Some(vec![-2.0 / 3.0]));
+	/// ```
+	pub fn
+	new_srational
+	(
+		tag:    &ExifTag,
+		values: Vec<(i32, i32)>
+	)
+	-> Result<ExifTag, String>
+	{
+		let rationals: RATIONAL64S = values.iter()
+			.map(|(nominator, denominator)| iR64 { nominator: *nominator, denominator: *denominator })
+			.collect();
+
+		return Self::new_with_rational_data(
+			tag, 
+			ExifTagFormat::RATIONAL64S, 
+			values.len(), 
+			&rationals.to_u8_vec(&Endian::Little)
+		);
+	}
+
+	/// Validates the format and number of values for `new_rational` and 
+	/// `new_srational` and creates the tag from the little endian raw data
+	fn
+	new_with_rational_data
+	(
+		tag:      &ExifTag,
+		format:   ExifTagFormat,
+		count:    usize,
+		raw_data: &Vec<u8>
+	)
+	-> Result<ExifTag, String>
+	{
+		if tag.format() != format
+		{
+			return Err(format!("{} is not a {:?} tag!", tag.name(), format));
+		}
+
+		// Unknown tags may use the hex value of a known tag, so the expected
+		// count is determined using the known tag
+		if let Some(expected_count) = ExifTag::from_u16(tag.as_u16(), &tag.get_group())
+			.ok()
+			.and_then(|known_tag| known_tag.expected_count())
+		{
+			if expected_count as usize != count
+			{
+				return Err(format!("{} expects {} values but got {}!", tag.name(), expected_count, count));
+			}
+		}
+
+		return Self::from_u16_with_data(
+			tag.as_u16(), 
+			&format, 
+			raw_data, 
+			&Endian::Little, 
+			&tag.get_group()
+		);
+	}
+}
@@ -0,0 +1,281 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::convert::TryFrom;
+
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::exif_tag_format::*;
+use crate::ifd::ExifTagGroup;
+use crate::rational::iR64;
+use crate::rational::uR64;
+use crate::u8conversion::U8conversion;
+
+/// The typed value of a tag. Strings are stored decoded, rationals as pairs
+/// of nominator and denominator.
+/// Single floats are kept as `f32` when serializing so that they are not
+/// written with the additional (meaningless) digits of an `f64`. When
+/// deserializing, all floating point values are read as `f64`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum
+TagValue
+{
+	Text(String),
+	Integers(Vec<i64>),
+	Floats(Vec<f64>),
+	#[serde(skip_deserializing)]
+	SingleFloats(Vec<f32>),
+	Rationals(Vec<(i64, i64)>),
+}
+
+/// The serialized form of an `ExifTag`. The name is only used to tell known
+/// and unknown tags apart, as a known tag may be stored as an unknown one if
+/// its value could not be decoded using the expected format.
+#[derive(Serialize, Deserialize)]
+struct
+SerializedTag
+{
+	name:      String,
+	hex:       u16,
+	group:     ExifTagGroup,
+	format:    ExifTagFormat,
+	value:     TagValue,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	strips:    Option<Vec<Vec<u8>>>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	thumbnail: Option<Vec<u8>>,
+}
+
+fn
+to_integers<T: Copy + Into<i64>>
+(
+	values: Vec<T>
+)
+-> TagValue
+{
+	return TagValue::Integers(values.into_iter().map(Into::into).collect());
+}
+
+fn
+from_integers<T: TryFrom<i64>>
+(
+	values: &[i64]
+)
+-> Result<Vec<T>, String>
+{
+	return values.iter()
+		.map(|value| T::try_from(*value).map_err(|_| format!("Value {} out of range", value)))
+		.collect();
+}
+
+/// Converts the value of the tag into its typed form, going through the bytes
+/// (as done by the `Display` implementation) to avoid listing all tags
+fn
+get_tag_value
+(
+	tag: &ExifTag
+)
+-> TagValue
+{
+	match tag
+	{
+		ExifTag::StripOffsets(   offsets, _) => return to_integers(offsets.clone()),
+		ExifTag::ThumbnailOffset(offsets, _) => return to_integers(offsets.clone()),
+		_                                    => (),
+	}
+
+	let bytes = tag.value_as_u8_vec(&Endian::Little);
+
+	match tag.format()
+	{
+		ExifTagFormat::STRING      => TagValue::Text(<STRING as U8conversion<STRING>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT8U       => to_integers(<INT8U  as U8conversion<INT8U >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::UNDEF       => to_integers(<UNDEF  as U8conversion<UNDEF >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT16U      => to_integers(<INT16U as U8conversion<INT16U>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT32U      => to_integers(<INT32U as U8conversion<INT32U>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT8S       => to_integers(<INT8S  as U8conversion<INT8S >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT16S      => to_integers(<INT16S as U8conversion<INT16S>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT32S      => to_integers(<INT32S as U8conversion<INT32S>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::FLOAT       => TagValue::SingleFloats(<FLOAT  as U8conversion<FLOAT >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::DOUBLE      => TagValue::Floats(      <DOUBLE as U8conversion<DOUBLE>>::from_u8_vec(&bytes, &Endian::Little)),
+
+		ExifTagFormat::RATIONAL64U => TagValue::Rationals(
+			<RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(&bytes, &Endian::Little)
+				.into_iter()
+				.map(|rational| (rational.nominator as i64, rational.denominator as i64))
+				.collect()
+		),
+
+		ExifTagFormat::RATIONAL64S => TagValue::Rationals(
+			<RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(&bytes, &Endian::Little)
+				.into_iter()
+				.map(|rational| (rational.nominator as i64, rational.denominator as i64))
+				.collect()
+		),
+	}
+}
+
+/// Converts the typed value back into bytes (little endian) for the given
+/// format. Integers are accepted for floating point formats as well, and an
+/// empty list is accepted for any format except `STRING`.
+fn
+tag_value_to_u8_vec
+(
+	value:  &TagValue,
+	format: &ExifTagFormat
+)
+-> Result<Vec<u8>, String>
+{
+	let endian = &Endian::Little;
+
+	match (format, value)
+	{
+		(ExifTagFormat::STRING, TagValue::Text(text))
+			=> return Ok(text.to_u8_vec(endian)),
+
+		(ExifTagFormat::INT8U,  TagValue::Integers(values)) => return Ok(from_integers::<u8 >(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::UNDEF,  TagValue::Integers(values)) => return Ok(from_integers::<u8 >(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::INT16U, TagValue::Integers(values)) => return Ok(from_integers::<u16>(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::INT32U, TagValue::Integers(values)) => return Ok(from_integers::<u32>(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::INT8S,  TagValue::Integers(values)) => return Ok(from_integers::<i8 >(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::INT16S, TagValue::Integers(values)) => return Ok(from_integers::<i16>(values)?.to_u8_vec(endian)),
+		(ExifTagFormat::INT32S, TagValue::Integers(values)) => return Ok(from_integers::<i32>(values)?.to_u8_vec(endian)),
+
+		(ExifTagFormat::FLOAT,  TagValue::Integers(values)) => return Ok(values.iter().map(|value| *value as f32).collect::<FLOAT >().to_u8_vec(endian)),
+		(ExifTagFormat::FLOAT,  TagValue::Floats(values))   => return Ok(values.iter().map(|value| *value as f32).collect::<FLOAT >().to_u8_vec(endian)),
+		(ExifTagFormat::DOUBLE, TagValue::Integers(values)) => return Ok(values.iter().map(|value| *value as f64).collect::<DOUBLE>().to_u8_vec(endian)),
+		(ExifTagFormat::DOUBLE, TagValue::Floats(values))   => return Ok(values.to_u8_vec(endian)),
+
+		(ExifTagFormat::RATIONAL64U, TagValue::Rationals(values)) => {
+			let mut rationals = RATIONAL64U::new();
+			for (nominator, denominator) in values
+			{
+				rationals.push(uR64 {
+					nominator:   from_integers::<u32>(&[*nominator  ])?[0],
+					denominator: from_integers::<u32>(&[*denominator])?[0],
+				});
+			}
+			return Ok(rationals.to_u8_vec(endian));
+		},
+
+		(ExifTagFormat::RATIONAL64S, TagValue::Rationals(values)) => {
+			let mut rationals = RATIONAL64S::new();
+			for (nominator, denominator) in values
+			{
+				rationals.push(iR64 {
+					nominator:   from_integers::<i32>(&[*nominator  ])?[0],
+					denominator: from_integers::<i32>(&[*denominator])?[0],
+				});
+			}
+			return Ok(rationals.to_u8_vec(endian));
+		},
+
+		// An empty list can't be told apart from other empty lists
+		(format, TagValue::Integers(values)) if values.is_empty() && *format != ExifTagFormat::STRING
+			=> return Ok(Vec::new()),
+
+		_ => return Err(format!("Value does not match format {:?}", format)),
+	}
+}
+
+/// Serializes the tag as its name, hex value, group, format and typed value.
+/// The image data of the `StripOffsets` and `ThumbnailOffset` tags is stored
+/// in the additional `strips` and `thumbnail` fields.
+impl
+Serialize
+for
+ExifTag
+{
+	fn
+	serialize<S: Serializer>
+	(
+		&self,
+		serializer: S
+	)
+	-> Result<S::Ok, S::Error>
+	{
+		let (strips, thumbnail) = match self
+		{
+			ExifTag::StripOffsets(   _, strip_data    ) => (Some(strip_data.clone()), None),
+			ExifTag::ThumbnailOffset(_, thumbnail_data) => (None, Some(thumbnail_data.clone())),
+			_                                           => (None, None),
+		};
+
+		return SerializedTag {
+			name:   self.name().to_string(),
+			hex:    self.as_u16(),
+			group:  self.get_group(),
+			format: self.format(),
+			value:  get_tag_value(self),
+			strips,
+			thumbnail,
+		}.serialize(serializer);
+	}
+}
+
+impl<'de>
+Deserialize<'de>
+for
+ExifTag
+{
+	fn
+	deserialize<D: Deserializer<'de>>
+	(
+		deserializer: D
+	)
+	-> Result<Self, D::Error>
+	{
+		let serialized = SerializedTag::deserialize(deserializer)?;
+		let raw_data   = tag_value_to_u8_vec(&serialized.value, &serialized.format).map_err(D::Error::custom)?;
+
+		if serialized.name == "Unknown"
+		{
+			return Ok(ExifTag::unknown_from_u8_vec(
+				serialized.hex,
+				&serialized.format,
+				&raw_data,
+				&Endian::Little,
+				&serialized.group
+			));
+		}
+
+		let tag = ExifTag::from_u16_with_data(
+			serialized.hex,
+			&serialized.format,
+			&raw_data,
+			&Endian::Little,
+			&serialized.group
+		).map_err(D::Error::custom)?;
+
+		if tag.name() != serialized.name || tag.format() != serialized.format
+		{
+			return Err(D::Error::custom(format!(
+				"Tag {:#06x} ({}, {:?}) does not match {} ({:?})",
+				serialized.hex,
+				serialized.name,
+				serialized.format,
+				tag.name(),
+				tag.format()
+			)));
+		}
+
+		match (tag, serialized.strips, serialized.thumbnail)
+		{
+			(ExifTag::StripOffsets(offsets, _), Some(strip_data), _)
+				=> return Ok(ExifTag::StripOffsets(offsets, strip_data)),
+			(ExifTag::ThumbnailOffset(offsets, _), _, Some(thumbnail_data))
+				=> return Ok(ExifTag::ThumbnailOffset(offsets, thumbnail_data)),
+			(tag, _, _)
+				=> return Ok(tag),
+		}
+	}
+}
@@ -17,6 +17,7 @@ pub type FLOAT          = Vec<f32>;
 pub type DOUBLE         = Vec<f64>;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum
 ExifTagFormat
 {
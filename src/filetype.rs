@@ -6,6 +6,8 @@ use std::path::Path;
 
 use crate::general_file_io::*;
 
+pub use crate::general_file_io::detect_file_type;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[allow(non_snake_case)]
 pub enum
@@ -13,7 +15,10 @@ FileExtension
 {
 	PNG  {as_zTXt_chunk: bool},
 	JPEG,
+	GIF,
+	HEIF,
 	JXL,
+	PSD,
 	TIFF,
 	WEBP
 }
@@ -34,10 +39,18 @@ FileExtension
 	{
 		match input 
 		{
+			"avif"  => Ok(FileExtension::HEIF),
+			"dng"   => Ok(FileExtension::TIFF),
+			"gif"   => Ok(FileExtension::GIF),
+			"heic"  => Ok(FileExtension::HEIF),
+			"heif"  => Ok(FileExtension::HEIF),
+			"jpe"   => Ok(FileExtension::JPEG),
 			"jpg"   => Ok(FileExtension::JPEG),
 			"jpeg"  => Ok(FileExtension::JPEG),
 			"jxl"   => Ok(FileExtension::JXL),
 			"png"   => Ok(FileExtension::PNG{ as_zTXt_chunk: true}),
+			"psb"   => Ok(FileExtension::PSD),
+			"psd"   => Ok(FileExtension::PSD),
 			"tif"   => Ok(FileExtension::TIFF),
 			"tiff"  => Ok(FileExtension::TIFF),
 			"webp"  => Ok(FileExtension::WEBP),
@@ -47,6 +60,92 @@ FileExtension
 }
 
 
+impl
+FileExtension
+{
+	/// Gets the file type for a user supplied extension, e.g. "JPEG" or
+	/// ".tif". In contrast to `from_str`, this ignores the case as well as a
+	/// leading dot.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::filetype::FileExtension;
+	///
+	/// assert_eq!(FileExtension::from_extension_str(".JPE").unwrap(), FileExtension::JPEG);
+	/// assert_eq!(FileExtension::from_extension_str("tif").unwrap(),  FileExtension::TIFF);
+	/// ```
+	pub fn
+	from_extension_str
+	(
+		extension: &str
+	)
+	-> Result<FileExtension, std::io::Error>
+	{
+		let extension = extension.trim().trim_start_matches('.').to_lowercase();
+		return FileExtension::from_str(extension.as_str());
+	}
+
+	/// Gets the file type for a MIME type, e.g. taken from a HTTP
+	/// `Content-Type` header. Parameters like "; charset=..." are ignored.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::filetype::FileExtension;
+	///
+	/// assert_eq!(FileExtension::from_mime("image/webp").unwrap(), FileExtension::WEBP);
+	/// assert!(FileExtension::from_mime("text/plain").is_err());
+	/// ```
+	pub fn
+	from_mime
+	(
+		mime_type: &str
+	)
+	-> Result<FileExtension, std::io::Error>
+	{
+		let essence = mime_type.split(';').next().unwrap_or("").trim().to_lowercase();
+
+		match essence.as_str()
+		{
+			"image/avif"        => Ok(FileExtension::HEIF),
+			"image/gif"         => Ok(FileExtension::GIF),
+			"image/heic"        => Ok(FileExtension::HEIF),
+			"image/heif"        => Ok(FileExtension::HEIF),
+			"image/jpeg"        => Ok(FileExtension::JPEG),
+			"image/jpg"         => Ok(FileExtension::JPEG),
+			"image/pjpeg"       => Ok(FileExtension::JPEG),
+			"image/jxl"         => Ok(FileExtension::JXL),
+			"image/png"         => Ok(FileExtension::PNG{ as_zTXt_chunk: true}),
+			"image/vnd.adobe.photoshop" => Ok(FileExtension::PSD),
+			"image/x-photoshop" => Ok(FileExtension::PSD),
+			"image/tiff"        => Ok(FileExtension::TIFF),
+			"image/x-adobe-dng" => Ok(FileExtension::TIFF),
+			"image/webp"        => Ok(FileExtension::WEBP),
+			_                   => io_error!(Unsupported, format!("Unknown MIME type: {}", mime_type))
+		}
+	}
+
+	/// Gets the MIME type of the file type. As AVIF and HEIC files are both
+	/// handled as HEIF, the generic "image/heif" is returned for them.
+	pub fn
+	mime_type
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			FileExtension::PNG{ .. } => "image/png",
+			FileExtension::JPEG      => "image/jpeg",
+			FileExtension::GIF       => "image/gif",
+			FileExtension::HEIF      => "image/heif",
+			FileExtension::JXL       => "image/jxl",
+			FileExtension::PSD       => "image/vnd.adobe.photoshop",
+			FileExtension::TIFF      => "image/tiff",
+			FileExtension::WEBP      => "image/webp",
+		}
+	}
+}
 
 pub fn
 get_file_type
@@ -31,10 +31,39 @@ macro_rules! io_error {
 	};
 }
 
+/// Same as `io_error`, but wraps one of the variants of `LittleExifError`, 
+/// which gets restored when converting the error into a `LittleExifError`
+macro_rules! little_exif_error {
+	($variant:ident, $message:expr)
+	=>
+	{
+		Err(std::io::Error::from(
+			crate::error::LittleExifError::$variant($message.to_string())
+		))
+	};
+}
+
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::path::Path;
 
+use crate::filetype::FileExtension;
+use crate::gif::GIF87A_SIGNATURE;
+use crate::gif::GIF89A_SIGNATURE;
+use crate::heif::FTYP_BOX_TYPE;
+use crate::heif::HEIF_BRANDS;
+use crate::jpg::JPG_SIGNATURE;
+use crate::jxl::ISO_BMFF_JXL_SIGNATURE;
+use crate::jxl::JXL_SIGNATURE;
+use crate::png::PNG_SIGNATURE;
+use crate::psd::PSD_SIGNATURE;
+use crate::tiff::BIG_TIFF_SIGNATURE_BIG_ENDIAN;
+use crate::tiff::BIG_TIFF_SIGNATURE_LITTLE_ENDIAN;
+use crate::tiff::TIFF_SIGNATURE_BIG_ENDIAN;
+use crate::tiff::TIFF_SIGNATURE_LITTLE_ENDIAN;
+use crate::webp::RIFF_SIGNATURE;
+use crate::webp::WEBP_SIGNATURE;
+
 pub(crate) fn
 open_read_file
 (
@@ -71,5 +100,90 @@ open_write_file
 		.open(path)
 }
 
+/// Determines the type of an image by looking at the magic bytes at its
+/// start instead of relying on a file extension.
+/// Returns `None` if the type is unknown or not supported by little_exif.
+/// PNGs are reported with `as_zTXt_chunk` set to true, which is the same
+/// default as for determining the type from the file extension.
+///
+/// # Examples
+/// ```
+/// use little_exif::filetype::detect_file_type;
+/// use little_exif::filetype::FileExtension;
+///
+/// assert_eq!(detect_file_type(b"GIF89a\x01\x00\x01\x00"), Some(FileExtension::GIF));
+/// assert_eq!(detect_file_type(b"RIFF\x24\x00\x00\x00AVI LIST"), None);
+/// ```
+pub fn
+detect_file_type
+(
+	buffer: &[u8]
+)
+-> Option<FileExtension>
+{
+	if buffer.starts_with(&PNG_SIGNATURE)
+	{
+		return Some(FileExtension::PNG { as_zTXt_chunk: true });
+	}
+
+	if buffer.starts_with(&JPG_SIGNATURE)
+	{
+		return Some(FileExtension::JPEG);
+	}
+
+	if buffer.starts_with(&JXL_SIGNATURE) || buffer.starts_with(&ISO_BMFF_JXL_SIGNATURE)
+	{
+		return Some(FileExtension::JXL);
+	}
+
+	if buffer.starts_with(&TIFF_SIGNATURE_LITTLE_ENDIAN)     || buffer.starts_with(&TIFF_SIGNATURE_BIG_ENDIAN)
+	|| buffer.starts_with(&BIG_TIFF_SIGNATURE_LITTLE_ENDIAN) || buffer.starts_with(&BIG_TIFF_SIGNATURE_BIG_ENDIAN)
+	{
+		return Some(FileExtension::TIFF);
+	}
+
+	// A raw EXIF block with the header of a JPEG APP1 segment is TIFF data
+	if let Some(tiff_data) = buffer.strip_prefix(&EXIF_HEADER)
+	{
+		if tiff_data.starts_with(&TIFF_SIGNATURE_LITTLE_ENDIAN) || tiff_data.starts_with(&TIFF_SIGNATURE_BIG_ENDIAN)
+		{
+			return Some(FileExtension::TIFF);
+		}
+	}
+
+	if buffer.starts_with(&GIF87A_SIGNATURE) || buffer.starts_with(&GIF89A_SIGNATURE)
+	{
+		return Some(FileExtension::GIF);
+	}
+
+	if buffer.starts_with(&PSD_SIGNATURE)
+	{
+		return Some(FileExtension::PSD);
+	}
+
+	// The RIFF container is used by other formats as well (e.g. AVI, WAV), so
+	// the form type following the file size needs to be checked, too
+	if buffer.starts_with(&RIFF_SIGNATURE) && buffer.get(8..12) == Some(&WEBP_SIGNATURE[..])
+	{
+		return Some(FileExtension::WEBP);
+	}
+
+	// Other ISO BMFF based formats start with an ftyp box, whose major brand
+	// tells whether this is a HEIF based format (e.g. HEIC or AVIF)
+	if buffer.get(4..8) == Some(&FTYP_BOX_TYPE[..])
+	{
+		if let Some(brand) = buffer.get(8..12)
+		{
+			if HEIF_BRANDS.iter().any(|heif_brand| heif_brand == brand)
+			{
+				return Some(FileExtension::HEIF);
+			}
+		}
+	}
+
+	return None;
+}
+
 pub(crate) use perform_file_action;
-pub(crate) use io_error;
\ No newline at end of file
+pub(crate) use io_error;
+pub(crate) use little_exif_error;
\ No newline at end of file
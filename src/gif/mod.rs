@@ -0,0 +1,392 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! GIF files are not able to store EXIF data. However, GIF89a allows for
+//! XMP metadata stored in an Application Extension block with the identifier
+//! "XMP Data" and authentication code "XMP". The XMP packet is *not* split
+//! into sub-blocks but written as is, followed by a "magic trailer" that
+//! ensures that decoders unaware of XMP skip the data when interpreting it as
+//! sequence of sub-blocks.
+
+use crate::general_file_io::io_error;
+use crate::general_file_io::little_exif_error;
+use crate::metadata::removal_plan::RemovalPlan;
+use crate::util::insert_multiple_at;
+use crate::util::range_remove;
+
+pub(crate) const GIF87A_SIGNATURE: [u8; 6] = [0x47, 0x49, 0x46, 0x38, 0x37, 0x61]; // "GIF87a"
+pub(crate) const GIF89A_SIGNATURE: [u8; 6] = [0x47, 0x49, 0x46, 0x38, 0x39, 0x61]; // "GIF89a"
+
+const EXTENSION_INTRODUCER:       u8 = 0x21;
+const IMAGE_DESCRIPTOR:           u8 = 0x2c;
+const TRAILER:                    u8 = 0x3b;
+const APPLICATION_EXTENSION:      u8 = 0xff;
+const BLOCK_TERMINATOR:           u8 = 0x00;
+
+// Header (6 bytes) + Logical Screen Descriptor (7 bytes)
+const HEADER_AND_LSD_LENGTH:      usize = 13;
+
+// Image Descriptor without the introducer 0x2C
+const IMAGE_DESCRIPTOR_LENGTH:    usize = 9;
+
+// Identifier "XMP Data" and authentication code "XMP"
+const XMP_APPLICATION_IDENTIFIER: [u8; 11] = [
+	0x58, 0x4d, 0x50, 0x20, 0x44, 0x61, 0x74, 0x61, // "XMP Data"
+	0x58, 0x4d, 0x50                                // "XMP"
+];
+
+// Length of the magic trailer without the final block terminator
+const XMP_MAGIC_TRAILER_LENGTH:   usize = 257;
+
+/// The blocks of a GIF file that are relevant for handling the metadata
+#[derive(Debug, PartialEq)]
+enum
+GifBlockType
+{
+	XmpApplicationExtension,
+	OtherExtension,
+	Image,
+}
+
+/// Describes a block of a GIF file, starting with its introducer byte at
+/// `start` and ending (exclusively) at `end`, i.e. after its terminator.
+#[derive(Debug)]
+struct
+GifBlock
+{
+	block_type: GifBlockType,
+	start:      usize,
+	end:        usize,
+}
+
+/// Builds the 257 bytes of the magic trailer that follow the XMP packet:
+/// 0x01, 0xFF, 0xFE, ..., 0x01, 0x00
+fn
+get_xmp_magic_trailer()
+-> Vec<u8>
+{
+	let mut trailer = vec![0x01];
+	trailer.extend((0x00..=0xffu8).rev());
+	return trailer;
+}
+
+fn
+check_signature
+(
+	file_buffer: &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	if file_buffer.len() < HEADER_AND_LSD_LENGTH
+	{
+		return io_error!(InvalidData, "Can't open GIF file - Too short!");
+	}
+
+	if file_buffer[0..6] != GIF87A_SIGNATURE && file_buffer[0..6] != GIF89A_SIGNATURE
+	{
+		return little_exif_error!(InvalidSignature, "Can't open GIF file - Wrong signature!");
+	}
+
+	return Ok(());
+}
+
+/// Computes the size of a color table as given by the lower three bits of the
+/// packed fields of the Logical Screen Descriptor or an Image Descriptor,
+/// assuming that the color table flag (highest bit) is set.
+fn
+get_color_table_length
+(
+	packed_fields: u8
+)
+-> usize
+{
+	if packed_fields & 0x80 == 0
+	{
+		return 0;
+	}
+	return 3 * (1 << ((packed_fields & 0x07) + 1));
+}
+
+/// Skips a sequence of data sub-blocks, each starting with a length byte,
+/// that is terminated by a block terminator (a sub-block of length 0).
+/// Returns the position right after the terminator.
+fn
+skip_sub_blocks
+(
+	file_buffer: &[u8],
+	mut position: usize
+)
+-> Result<usize, std::io::Error>
+{
+	loop
+	{
+		let sub_block_length = match file_buffer.get(position)
+		{
+			Some(length) => *length as usize,
+			None         => return io_error!(UnexpectedEof, "GIF sub-block exceeds the end of the file!"),
+		};
+
+		position += 1;
+
+		if sub_block_length == BLOCK_TERMINATOR as usize
+		{
+			return Ok(position);
+		}
+
+		position += sub_block_length;
+	}
+}
+
+/// Returns the position right after the Logical Screen Descriptor and the
+/// optional Global Color Table, i.e. the position of the first block
+fn
+get_first_block_position
+(
+	file_buffer: &[u8]
+)
+-> usize
+{
+	return HEADER_AND_LSD_LENGTH + get_color_table_length(file_buffer[10]);
+}
+
+/// Walks the GIF block stream and returns the extension and image blocks that
+/// are located between the Global Color Table and the Trailer.
+fn
+parse_gif
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<GifBlock>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut blocks   = Vec::new();
+	let mut position = get_first_block_position(file_buffer);
+
+	loop
+	{
+		let start = position;
+
+		match file_buffer.get(position)
+		{
+			Some(&EXTENSION_INTRODUCER) => {
+				let label = match file_buffer.get(position + 1)
+				{
+					Some(label) => *label,
+					None        => return io_error!(UnexpectedEof, "GIF extension label missing!"),
+				};
+
+				let is_xmp = label == APPLICATION_EXTENSION
+					&& file_buffer.get(position + 2) == Some(&(XMP_APPLICATION_IDENTIFIER.len() as u8))
+					&& file_buffer.get(position + 3 .. position + 3 + XMP_APPLICATION_IDENTIFIER.len()) == Some(&XMP_APPLICATION_IDENTIFIER[..]);
+
+				position = skip_sub_blocks(file_buffer, position + 2)?;
+
+				blocks.push(GifBlock {
+					block_type: if is_xmp { GifBlockType::XmpApplicationExtension } else { GifBlockType::OtherExtension },
+					start,
+					end:        position
+				});
+			},
+
+			Some(&IMAGE_DESCRIPTOR) => {
+				let packed_fields = match file_buffer.get(position + IMAGE_DESCRIPTOR_LENGTH)
+				{
+					Some(packed_fields) => *packed_fields,
+					None                => return io_error!(UnexpectedEof, "GIF image descriptor truncated!"),
+				};
+
+				// Skip the introducer, descriptor, local color table and the
+				// LZW minimum code size
+				position += 1 + IMAGE_DESCRIPTOR_LENGTH + get_color_table_length(packed_fields) + 1;
+				position = skip_sub_blocks(file_buffer, position)?;
+
+				blocks.push(GifBlock {
+					block_type: GifBlockType::Image,
+					start,
+					end:        position
+				});
+			},
+
+			Some(&TRAILER) => return Ok(blocks),
+
+			Some(other) => return io_error!(
+				InvalidData,
+				format!("Unknown GIF block introducer {:#04x} at position {}!", other, position)
+			),
+
+			None => return io_error!(UnexpectedEof, "GIF trailer missing!"),
+		}
+	}
+}
+
+/// Reads the XMP packet stored in the Application Extension of the GIF file
+pub(crate) fn
+read_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<String, std::io::Error>
+{
+	for block in parse_gif(file_buffer)?
+	{
+		if block.block_type != GifBlockType::XmpApplicationExtension
+		{
+			continue;
+		}
+
+		// Skip introducer, label, block size and identifier and strip the
+		// magic trailer and the block terminator at the end
+		let data_start = block.start + 3 + XMP_APPLICATION_IDENTIFIER.len();
+		if block.end < data_start + XMP_MAGIC_TRAILER_LENGTH + 1
+		{
+			return io_error!(InvalidData, "GIF XMP application extension too short!");
+		}
+
+		let data_end = block.end - 1 - XMP_MAGIC_TRAILER_LENGTH;
+		if file_buffer[data_end..block.end - 1] != get_xmp_magic_trailer()[..]
+		{
+			return io_error!(InvalidData, "GIF XMP application extension has no valid magic trailer!");
+		}
+
+		return match String::from_utf8(file_buffer[data_start..data_end].to_vec())
+		{
+			Ok(xmp) => Ok(xmp),
+			Err(_)  => io_error!(InvalidData, "GIF XMP data is not valid UTF-8!"),
+		};
+	}
+
+	return little_exif_error!(NoMetadata, "No XMP data found!");
+}
+
+/// Removes all XMP Application Extensions from the GIF file. Other extensions
+/// like comments or the NETSCAPE looping extension are kept.
+pub(crate) fn
+clear_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	// Remove in reverse order so that the positions of the remaining blocks
+	// stay valid
+	for block in parse_gif(file_buffer)?.iter().rev()
+	{
+		if block.block_type == GifBlockType::XmpApplicationExtension
+		{
+			range_remove(file_buffer, block.start, block.end);
+		}
+	}
+
+	return Ok(());
+}
+
+/// Determines the XMP Application Extensions that `clear_metadata` would
+/// remove, without modifying the file
+pub(crate) fn
+plan_clear_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<RemovalPlan>, std::io::Error>
+{
+	return Ok(parse_gif(file_buffer)?
+		.iter()
+		.filter(|block| block.block_type == GifBlockType::XmpApplicationExtension)
+		.map(|block| RemovalPlan::new("XMP Application Extension", block.start..block.end))
+		.collect());
+}
+
+/// Writes the XMP packet into an Application Extension that gets inserted
+/// right after the Global Color Table, i.e. before any other block. Existing
+/// XMP data gets replaced. As extensions are only available since GIF89a,
+/// the version in the header gets updated if necessary.
+pub(crate) fn
+write_metadata
+(
+	file_buffer: &mut Vec<u8>,
+	xmp:         &str
+)
+-> Result<(), std::io::Error>
+{
+	// A zero byte in the packet would be interpreted as block terminator
+	if xmp.as_bytes().contains(&BLOCK_TERMINATOR)
+	{
+		return io_error!(InvalidInput, "XMP data for GIF must not contain NUL bytes!");
+	}
+
+	clear_metadata(file_buffer)?;
+
+	let mut xmp_extension = vec![
+		EXTENSION_INTRODUCER,
+		APPLICATION_EXTENSION,
+		XMP_APPLICATION_IDENTIFIER.len() as u8
+	];
+	xmp_extension.extend(XMP_APPLICATION_IDENTIFIER.iter());
+	xmp_extension.extend(xmp.as_bytes().iter());
+	xmp_extension.extend(get_xmp_magic_trailer().iter());
+	xmp_extension.push(BLOCK_TERMINATOR);
+
+	let insert_position = get_first_block_position(file_buffer);
+	if insert_multiple_at(file_buffer, insert_position, &mut xmp_extension) != 0
+	{
+		return io_error!(UnexpectedEof, "GIF global color table exceeds the end of the file!");
+	}
+
+	file_buffer[0..6].copy_from_slice(&GIF89A_SIGNATURE);
+
+	return Ok(());
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// A 1x1 pixel GIF89a with a global color table of two colors, a comment
+	/// extension and a single image
+	fn
+	get_test_gif()
+	-> Vec<u8>
+	{
+		let mut gif = GIF89A_SIGNATURE.to_vec();
+		gif.extend([0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00]);
+		gif.extend([0x00, 0x00, 0x00, 0xff, 0xff, 0xff]);
+		gif.extend([0x21, 0xfe, 0x02, 0x48, 0x69, 0x00]);
+		gif.extend([0x2c, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00]);
+		gif.extend([0x02, 0x02, 0x44, 0x01, 0x00]);
+		gif.push(TRAILER);
+		return gif;
+	}
+
+	#[test]
+	fn
+	write_read_clear_test()
+	-> Result<(), std::io::Error>
+	{
+		let original = get_test_gif();
+		let mut gif  = original.clone();
+		let xmp      = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>";
+
+		assert!(read_metadata(&gif).is_err());
+
+		write_metadata(&mut gif, xmp)?;
+		write_metadata(&mut gif, xmp)?;
+		assert_eq!(read_metadata(&gif)?, xmp);
+
+		// Inserted after the global color table, the other blocks survive
+		let blocks = parse_gif(&gif)?;
+		assert_eq!(blocks.len(), 3);
+		assert_eq!(blocks[0].block_type, GifBlockType::XmpApplicationExtension);
+		assert_eq!(blocks[0].start, 19);
+		assert_eq!(
+			plan_clear_metadata(&gif)?,
+			vec![RemovalPlan::new("XMP Application Extension", 19..gif.len() - original.len() + 19)]
+		);
+
+		clear_metadata(&mut gif)?;
+		assert_eq!(gif, original);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,459 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! HEIF (and therefore also HEIC and AVIF) files are based on the ISO base
+//! media file format (ISO BMFF). In contrast to the JXL container, the EXIF
+//! data is not stored in a dedicated top level box but as an *item* of the
+//! `meta` box:
+//! - The `iinf` box lists all items, where the `infe` entry of the EXIF item
+//!   has the item type "Exif"
+//! - The `iloc` box gives the location of each item, either as offset into
+//!   the file (usually pointing into the `mdat` box) or into the `idat` box
+//!   of the `meta` box
+//!
+//! The EXIF item itself starts with a 4 byte offset to the TIFF header,
+//! which is usually preceded by the "Exif\0\0" header.
+//! Currently, only reading the metadata is supported.
+
+use std::path::Path;
+
+use crate::general_file_io::io_error;
+use crate::general_file_io::little_exif_error;
+
+pub(crate) const FTYP_BOX_TYPE: [u8; 4] = [0x66, 0x74, 0x79, 0x70]; // "ftyp"
+
+// Major brands of HEIF based formats, see ISO/IEC 23008-12 and the AV1 Image
+// File Format specification
+pub(crate) const HEIF_BRANDS: [[u8; 4]; 10] = [
+	*b"heic", *b"heix", *b"hevc", *b"hevx", *b"heim",
+	*b"heis", *b"mif1", *b"msf1", *b"avif", *b"avis"
+];
+
+#[non_exhaustive]
+struct HeifBoxType;
+
+impl HeifBoxType {
+	pub const META: [u8; 4] = [0x6d, 0x65, 0x74, 0x61]; // "meta"
+	pub const IINF: [u8; 4] = [0x69, 0x69, 0x6e, 0x66]; // "iinf"
+	pub const INFE: [u8; 4] = [0x69, 0x6e, 0x66, 0x65]; // "infe"
+	pub const ILOC: [u8; 4] = [0x69, 0x6c, 0x6f, 0x63]; // "iloc"
+	pub const IDAT: [u8; 4] = [0x69, 0x64, 0x61, 0x74]; // "idat"
+}
+
+const EXIF_ITEM_TYPE: [u8; 4] = [0x45, 0x78, 0x69, 0x66]; // "Exif"
+
+/// Describes a box of the ISO BMFF structure. The data of the box starts
+/// (after its header) at `data_start` and ends (exclusively) at `end`.
+#[derive(Debug)]
+struct
+IsoBmffBox
+{
+	box_type:   [u8; 4],
+	data_start: usize,
+	end:        usize,
+}
+
+/// A single extent of an item, given by the `iloc` box
+#[derive(Debug)]
+struct
+ItemExtent
+{
+	offset: u64,
+	length: u64,
+}
+
+/// The location of an item, given by the `iloc` box
+#[derive(Debug)]
+struct
+ItemLocation
+{
+	item_id:             u32,
+	construction_method: u16,
+	base_offset:         u64,
+	extents:             Vec<ItemExtent>,
+}
+
+/// Reads a big endian unsigned integer consisting of `size` bytes (where
+/// `size` may be 0, 1, 2, 4 or 8) and advances the position accordingly
+fn
+read_uint
+(
+	buffer:   &[u8],
+	position: &mut usize,
+	size:     usize
+)
+-> Result<u64, std::io::Error>
+{
+	if size > 8
+	{
+		return io_error!(InvalidData, format!("Invalid integer size in HEIF data: {}", size));
+	}
+
+	let bytes = match buffer.get(*position..*position + size)
+	{
+		Some(bytes) => bytes,
+		None        => return io_error!(UnexpectedEof, "Unexpected end of HEIF data!"),
+	};
+
+	*position += size;
+	return Ok(bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64));
+}
+
+/// Parses the boxes located in the given range of the buffer, without
+/// descending into their children
+fn
+parse_boxes
+(
+	buffer: &[u8],
+	start:  usize,
+	end:    usize
+)
+-> Result<Vec<IsoBmffBox>, std::io::Error>
+{
+	let mut boxes    = Vec::new();
+	let mut position = start;
+
+	while position + 8 <= end
+	{
+		let box_start = position;
+		let mut size  = read_uint(buffer, &mut position, 4)?;
+
+		let mut box_type = [0u8; 4];
+		box_type.copy_from_slice(&buffer[position..position+4]);
+		position += 4;
+
+		// A size of 1 indicates that the actual size follows as 64 bit value
+		// A size of 0 indicates that the box extends to the end of the data
+		if size == 1
+		{
+			size = read_uint(buffer, &mut position, 8)?;
+		}
+		else if size == 0
+		{
+			size = (end - box_start) as u64;
+		}
+
+		let box_end = box_start as u64 + size;
+		if box_end > end as u64 || box_end < position as u64
+		{
+			return io_error!(InvalidData, format!("Invalid size of HEIF box {:?}", String::from_utf8_lossy(&box_type)));
+		}
+
+		boxes.push(IsoBmffBox { box_type, data_start: position, end: box_end as usize });
+		position = box_end as usize;
+	}
+
+	return Ok(boxes);
+}
+
+/// Checks that the data starts with an `ftyp` box with a HEIF brand
+fn
+check_signature
+(
+	file_buffer: &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	if file_buffer.get(4..8) != Some(&FTYP_BOX_TYPE[..])
+	{
+		return io_error!(InvalidData, "Can't open HEIF file - Missing ftyp box!");
+	}
+
+	match file_buffer.get(8..12)
+	{
+		Some(brand) if HEIF_BRANDS.iter().any(|heif_brand| heif_brand == brand)
+			=> Ok(()),
+		_
+			=> io_error!(InvalidData, "Can't open HEIF file - Unknown brand!"),
+	}
+}
+
+/// Finds the ID of the item with the type "Exif" in the `iinf` box
+fn
+find_exif_item_id
+(
+	buffer:   &[u8],
+	iinf_box: &IsoBmffBox
+)
+-> Result<Option<u32>, std::io::Error>
+{
+	let mut position = iinf_box.data_start;
+	let version      = read_uint(buffer, &mut position, 4)? >> 24;
+	let entry_count_size = if version == 0 { 2 } else { 4 };
+	read_uint(buffer, &mut position, entry_count_size)?;
+
+	for infe_box in parse_boxes(buffer, position, iinf_box.end)?
+	{
+		if infe_box.box_type != HeifBoxType::INFE
+		{
+			continue;
+		}
+
+		// Only versions 2 and 3 of the infe box contain the item type
+		let mut position = infe_box.data_start;
+		let version      = read_uint(buffer, &mut position, 4)? >> 24;
+		if version < 2
+		{
+			continue;
+		}
+
+		let item_id = read_uint(buffer, &mut position, if version == 2 { 2 } else { 4 })? as u32;
+
+		// Skip the item protection index
+		read_uint(buffer, &mut position, 2)?;
+
+		if buffer.get(position..position+4) == Some(&EXIF_ITEM_TYPE[..])
+		{
+			return Ok(Some(item_id));
+		}
+	}
+
+	return Ok(None);
+}
+
+/// Parses the item locations stored in the `iloc` box
+fn
+parse_item_locations
+(
+	buffer:   &[u8],
+	iloc_box: &IsoBmffBox
+)
+-> Result<Vec<ItemLocation>, std::io::Error>
+{
+	let mut position = iloc_box.data_start;
+	let version      = read_uint(buffer, &mut position, 4)? >> 24;
+
+	let sizes            = read_uint(buffer, &mut position, 2)?;
+	let offset_size      = ((sizes >> 12) & 0x0f) as usize;
+	let length_size      = ((sizes >>  8) & 0x0f) as usize;
+	let base_offset_size = ((sizes >>  4) & 0x0f) as usize;
+	let index_size       = if version == 1 || version == 2 { (sizes & 0x0f) as usize } else { 0 };
+
+	let item_count = read_uint(buffer, &mut position, if version < 2 { 2 } else { 4 })?;
+
+	let mut locations = Vec::new();
+	for _ in 0..item_count
+	{
+		let item_id = read_uint(buffer, &mut position, if version < 2 { 2 } else { 4 })? as u32;
+
+		// The construction method is stored in the lower 4 bits
+		let construction_method = if version == 1 || version == 2
+		{
+			(read_uint(buffer, &mut position, 2)? & 0x0f) as u16
+		}
+		else
+		{
+			0
+		};
+
+		// Skip the data reference index
+		read_uint(buffer, &mut position, 2)?;
+
+		let base_offset  = read_uint(buffer, &mut position, base_offset_size)?;
+		let extent_count = read_uint(buffer, &mut position, 2)?;
+
+		let mut extents = Vec::new();
+		for _ in 0..extent_count
+		{
+			read_uint(buffer, &mut position, index_size)?;
+			let offset = read_uint(buffer, &mut position, offset_size)?;
+			let length = read_uint(buffer, &mut position, length_size)?;
+			extents.push(ItemExtent { offset, length });
+		}
+
+		locations.push(ItemLocation { item_id, construction_method, base_offset, extents });
+	}
+
+	return Ok(locations);
+}
+
+/// Reads the raw EXIF data from the HEIF file buffer. The returned data
+/// starts with the TIFF header, i.e. the 4 byte offset prefix of the EXIF
+/// item as well as the "Exif\0\0" header are skipped.
+pub(crate) fn
+read_metadata
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let top_level_boxes = parse_boxes(file_buffer, 0, file_buffer.len())?;
+	let meta_box = match top_level_boxes.iter().find(|top_level_box| top_level_box.box_type == HeifBoxType::META)
+	{
+		Some(meta_box) => meta_box,
+		None           => return little_exif_error!(NoMetadata, "No meta box found in HEIF file!"),
+	};
+
+	// The meta box is a "full box", so skip its version and flags
+	let meta_children = parse_boxes(file_buffer, meta_box.data_start + 4, meta_box.end)?;
+	let find_child    = |box_type: [u8; 4]| meta_children.iter().find(|child| child.box_type == box_type);
+
+	let exif_item_id = match find_child(HeifBoxType::IINF)
+	{
+		Some(iinf_box) => find_exif_item_id(file_buffer, iinf_box)?,
+		None           => None,
+	};
+
+	let exif_item_id = match exif_item_id
+	{
+		Some(exif_item_id) => exif_item_id,
+		None               => return little_exif_error!(NoMetadata, "No EXIF item found in HEIF file!"),
+	};
+
+	let locations = match find_child(HeifBoxType::ILOC)
+	{
+		Some(iloc_box) => parse_item_locations(file_buffer, iloc_box)?,
+		None           => return io_error!(Other, "No iloc box found in HEIF file!"),
+	};
+
+	let location = match locations.iter().find(|location| location.item_id == exif_item_id)
+	{
+		Some(location) => location,
+		None           => return io_error!(Other, "No location found for EXIF item in HEIF file!"),
+	};
+
+	// Determine the data the offsets of the extents refer to: Either the
+	// entire file (construction method 0) or the data of the idat box (1)
+	let reference_data = match location.construction_method
+	{
+		0 => file_buffer,
+		1 => match find_child(HeifBoxType::IDAT)
+		{
+			Some(idat_box) => &file_buffer[idat_box.data_start..idat_box.end],
+			None           => return io_error!(Other, "No idat box found in HEIF file!"),
+		},
+		_ => return io_error!(
+			Unsupported,
+			format!("Construction method {} of HEIF EXIF item not supported!", location.construction_method)
+		),
+	};
+
+	// Assemble the item from its extents
+	let mut exif_item = Vec::new();
+	for extent in &location.extents
+	{
+		let start = location.base_offset.checked_add(extent.offset);
+		let end   = start.and_then(|start| start.checked_add(extent.length));
+
+		match (start, end)
+		{
+			(Some(start), Some(end)) if end <= reference_data.len() as u64
+				=> exif_item.extend(&reference_data[start as usize..end as usize]),
+			_
+				=> return io_error!(InvalidData, "EXIF item extends beyond the HEIF data!"),
+		}
+	}
+
+	// Skip the offset to the TIFF header as well as the bytes before it
+	let mut position      = 0;
+	let tiff_header_start = read_uint(&exif_item, &mut position, 4)?.saturating_add(4);
+	if tiff_header_start > exif_item.len() as u64
+	{
+		return io_error!(InvalidData, "Invalid TIFF header offset in HEIF EXIF item!");
+	}
+
+	return Ok(exif_item[tiff_header_start as usize..].to_vec());
+}
+
+/// Reads the raw EXIF data from the HEIF file at the given path
+pub(crate) fn
+file_read_metadata
+(
+	path: &Path
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return read_metadata(&std::fs::read(path)?);
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn
+	make_box
+	(
+		box_type: &[u8; 4],
+		data:     &[u8]
+	)
+	-> Vec<u8>
+	{
+		let mut result = ((data.len() + 8) as u32).to_be_bytes().to_vec();
+		result.extend(box_type);
+		result.extend(data);
+		return result;
+	}
+
+	/// Builds a minimal HEIF file with an image item (ID 1) and an EXIF item
+	/// (ID 2) that is either stored in the idat box or in the mdat box
+	fn
+	get_test_heif
+	(
+		tiff_data:        &[u8],
+		use_idat:         bool
+	)
+	-> Vec<u8>
+	{
+		let mut exif_item = 6u32.to_be_bytes().to_vec();
+		exif_item.extend(b"Exif\0\0");
+		exif_item.extend(tiff_data);
+
+		let ftyp = make_box(b"ftyp", b"heic\0\0\0\0mif1heic");
+
+		let mut iinf_data = vec![0, 0, 0, 0, 0, 2];
+		iinf_data.extend(make_box(b"infe", b"\x02\0\0\0\0\x01\0\0hvc1\0"));
+		iinf_data.extend(make_box(b"infe", b"\x02\0\0\0\0\x02\0\0Exif\0"));
+		let iinf = make_box(b"iinf", &iinf_data);
+
+		// Version 1 of the iloc box with 4 byte offsets and lengths
+		let build_iloc = |exif_offset: u32| {
+			let mut iloc_data = vec![1, 0, 0, 0, 0x44, 0x00, 0, 2];
+			iloc_data.extend([0, 1, 0, 0, 0, 0, 0, 1]);
+			iloc_data.extend([0u8, 0, 0, 0, 0, 0, 0, 0]);
+			iloc_data.extend([0, 2, 0, if use_idat { 1 } else { 0 }, 0, 0, 0, 1]);
+			iloc_data.extend(exif_offset.to_be_bytes());
+			iloc_data.extend((exif_item.len() as u32).to_be_bytes());
+			make_box(b"iloc", &iloc_data)
+		};
+
+		let idat = if use_idat { make_box(b"idat", &exif_item) } else { Vec::new() };
+
+		// The offset into the mdat box depends on the size of the meta box
+		let meta_length = 8 + 4 + iinf.len() + build_iloc(0).len() + idat.len();
+		let exif_offset = if use_idat { 0 } else { (ftyp.len() + meta_length + 8) as u32 };
+
+		let mut meta_data = vec![0, 0, 0, 0];
+		meta_data.extend(iinf);
+		meta_data.extend(build_iloc(exif_offset));
+		meta_data.extend(idat);
+
+		let mut heif = ftyp;
+		heif.extend(make_box(b"meta", &meta_data));
+		if !use_idat
+		{
+			heif.extend(make_box(b"mdat", &exif_item));
+		}
+		return heif;
+	}
+
+	#[test]
+	fn
+	read_exif_item_test()
+	-> Result<(), std::io::Error>
+	{
+		let tiff_data = b"II\x2a\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+
+		for use_idat in [false, true]
+		{
+			let heif = get_test_heif(tiff_data, use_idat);
+			assert_eq!(read_metadata(&heif)?, tiff_data.to_vec());
+		}
+
+		assert!(read_metadata(&make_box(b"ftyp", b"isom\0\0\0\0")).is_err());
+
+		Ok(())
+	}
+}
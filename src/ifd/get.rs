@@ -20,6 +20,39 @@ ImageFileDirectory
 		return &self.tags;
 	}
 
+	/// Gets a mutable reference to the tag with the given hex value, e.g. for
+	/// editing its value in place instead of removing and adding it again.
+	/// Returns `None` if there is no such tag in this IFD.
+	/// Note that the tag must not be replaced by a tag with a different hex
+	/// value, as this would break the sorting of the tags - use `set_tag` and
+	/// `remove_tag` for this instead.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// use little_exif::ifd::ImageFileDirectory;
+	///
+	/// let mut ifd = ImageFileDirectory::new_with_tags(Vec::new(), ExifTagGroup::GENERIC, 0);
+	/// ifd.set_tag(ExifTag::Model("Camera".to_string())).unwrap();
+	///
+	/// if let Some(ExifTag::Model(model)) = ifd.get_tag_mut(0x0110)
+	/// {
+	///     model.push_str(" Mk II");
+	/// }
+	/// assert_eq!(ifd.get_tags()[0], ExifTag::Model("Camera Mk II".to_string()));
+	/// ```
+	pub fn
+	get_tag_mut
+	(
+		&mut self,
+		hex: u16
+	)
+	-> Option<&mut ExifTag>
+	{
+		return self.tags.iter_mut().find(|tag| tag.as_u16() == hex);
+	}
+
 	pub fn
 	get_generic_ifd_nr
 	(
@@ -40,6 +73,18 @@ ImageFileDirectory
 		return self.ifd_type;
 	}
 
+	/// Gets the IFDs referenced via the `SubIFDs` tag (0x014a) of this IFD,
+	/// e.g. the full resolution image data of a DNG file
+	pub fn
+	get_sub_ifds
+	(
+		&self
+	)
+	-> &Vec<ImageFileDirectory>
+	{
+		return &self.sub_ifds;
+	}
+
 	pub fn
 	get_offset_tag_for_parent_ifd
 	(
@@ -5,6 +5,7 @@ pub mod get;
 pub mod set;
 
 use core::panic;
+use std::fmt;
 use std::io::Cursor;
 use std::io::Read;
 use std::io::Seek;
@@ -15,8 +16,10 @@ use crate::exif_tag::decode::decode_tag_with_format_exceptions;
 use crate::exif_tag::ExifTag;
 use crate::exif_tag::TagType;
 use crate::exif_tag_format::ExifTagFormat;
+use crate::exif_tag_format::INT32U;
 use crate::general_file_io::io_error;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
 use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::to_u8_vec_macro;
 use crate::u8conversion::U8conversion;
@@ -30,6 +33,31 @@ use crate::u8conversion::U8conversion;
 const IFD_ENTRY_LENGTH: u32     = 12;
 const IFD_END_NO_LINK:  [u8; 4] = [0x00, 0x00, 0x00, 0x00];
 
+/// The BigTIFF counterparts: An entry consists of the tags hex value (2 byte),
+/// the format (2 byte), the number of components (8 byte) and the value/offset
+/// section (8 byte). The number of entries and the link to the next IFD use
+/// 8 bytes as well.
+const BIG_TIFF_IFD_ENTRY_LENGTH: u32 = 20;
+const BIG_TIFF_IFD_END_NO_LINK:  [u8; 8] = [0x00; 8];
+
+/// BigTIFF specific formats for 8 byte (un)signed integers and IFD offsets,
+/// which get converted to their 4 byte counterparts while decoding
+const BIG_TIFF_FORMAT_LONG8:  u16 = 16;
+const BIG_TIFF_FORMAT_SLONG8: u16 = 17;
+const BIG_TIFF_FORMAT_IFD8:   u16 = 18;
+
+/// The maximum nesting depth of IFDs (e.g. IFD0 -> EXIF -> Interop) that
+/// gets decoded, protecting against stack overflows caused by malicious data
+const MAX_IFD_DEPTH: usize = 16;
+
+/// Hex value of the `MakerNote` tag, which requires special treatment as its
+/// data may contain offsets
+pub(crate) const MAKERNOTE_HEX: u16 = 0x927c;
+
+/// Hex value of the `SubIFDs` tag (e.g. used by DNG files), whose values are
+/// offsets to additional IFDs that are decoded into `sub_ifds`
+pub(crate) const SUBIFDS_HEX: u16 = 0x014a;
+
 /// The different types of Image File Directories (IFD). A generic IFD is one
 /// without further specialization, like e.g. IFD0. The generic IFDs start
 /// with IFD0, which is located via the offset at the start of the TIFF data. 
@@ -39,6 +67,7 @@ const IFD_END_NO_LINK:  [u8; 4] = [0x00, 0x00, 0x00, 0x00];
 /// the ExifIFD offset: 0x8769) that are located in the respective generic IFD 
 /// (most of them in IFD0).
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_snake_case, non_camel_case_types)]
 pub enum
 ExifTagGroup
@@ -50,16 +79,128 @@ ExifTagGroup
 	GPS,
 }
 
+impl
+ExifTagGroup
+{
+	/// Returns all groups modeled by little_exif, in the order used when 
+	/// sorting IFDs. Maker notes are not modeled as a separate group (yet).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// let names = ExifTagGroup::all().iter().map(|group| group.as_str()).collect::<Vec<_>>();
+	/// assert_eq!(names, vec!["IFD0", "ExifIFD", "Interop", "GPS"]);
+	/// ```
+	pub fn
+	all
+	()
+	-> &'static [ExifTagGroup]
+	{
+		return &[
+			ExifTagGroup::GENERIC,
+			ExifTagGroup::EXIF,
+			ExifTagGroup::INTEROP,
+			ExifTagGroup::GPS,
+		];
+	}
+
+	/// Returns the name of the group as used by e.g. exiftool
+	/// Note that the generic IFDs following IFD0 (e.g. IFD1) belong to the
+	/// `GENERIC` group as well, which is nonetheless named "IFD0".
+	pub fn
+	as_str
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			ExifTagGroup::GENERIC => "IFD0",
+			ExifTagGroup::EXIF    => "ExifIFD",
+			ExifTagGroup::INTEROP => "Interop",
+			ExifTagGroup::GPS     => "GPS",
+		}
+	}
+}
+
+impl
+fmt::Display
+for
+ExifTagGroup
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		write!(f, "{}", self.as_str())
+	}
+}
+
 /// The value of `belongs_to_generic_ifd_nr` tells us what generic IFD this
 /// specific IFD belongs to, e.g. `0` would indicate that it belongs (or is)
 /// IFD0. 
+/// The IFDs referenced via the `SubIFDs` tag (0x014a) of a generic IFD are
+/// stored in `sub_ifds` of that IFD, as they are not part of the chain of
+/// generic IFDs.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct
 ImageFileDirectory
 {
 	tags:                      Vec<ExifTag>,
 	ifd_type:                  ExifTagGroup,
 	belongs_to_generic_ifd_nr: u32,
+	sub_ifds:                  Vec<ImageFileDirectory>,
+}
+
+/// Two IFDs are considered equal if they have the same group and generic IFD
+/// number, contain the same tags with the same values (regardless of their
+/// order) and have the same SubIFDs.
+impl
+PartialEq
+for
+ImageFileDirectory
+{
+	fn
+	eq
+	(
+		&self,
+		other: &Self
+	)
+	-> bool
+	{
+		if self.ifd_type                  != other.ifd_type
+		|| self.belongs_to_generic_ifd_nr != other.belongs_to_generic_ifd_nr
+		|| self.tags.len()                != other.tags.len()
+		|| self.sub_ifds                  != other.sub_ifds
+		{
+			return false;
+		}
+
+		// Match each tag with a not yet matched equal tag of the other IFD,
+		// so that duplicate tags are accounted for as well
+		let mut matched = vec![false; other.tags.len()];
+		for tag in &self.tags
+		{
+			let matching_index = other.tags.iter().enumerate().position(|(index, other_tag)| 
+				!matched[index] && other_tag == tag
+			);
+
+			match matching_index
+			{
+				Some(index) => matched[index] = true,
+				None        => return false,
+			}
+		}
+
+		return true;
+	}
 }
 
 impl
@@ -75,7 +216,7 @@ ImageFileDirectory
 	)
 	-> Self
 	{
-		ImageFileDirectory { tags: tags, ifd_type: group, belongs_to_generic_ifd_nr: nr }
+		ImageFileDirectory { tags: tags, ifd_type: group, belongs_to_generic_ifd_nr: nr, sub_ifds: Vec::new() }
 	}
 
 	/// Sorts the tags according to their hex value
@@ -91,19 +232,114 @@ ImageFileDirectory
 			|a, b|
 			a.as_u16().cmp(&b.as_u16())
 		);
+
+		for sub_ifd in self.sub_ifds.iter_mut()
+		{
+			sub_ifd.sort_tags();
+		}
+	}
+
+	/// Checks that the given number of bytes starting at the given offset
+	/// (relative to the start of the TIFF data) are within the data, so that
+	/// corrupted offsets and counts result in an error instead of e.g. huge
+	/// allocations
+	fn
+	check_data_range
+	(
+		data_cursor:         &Cursor<&Vec<u8>>,
+		data_begin_position: u64,
+		offset:              u32,
+		byte_count:          u32
+	)
+	-> Result<(), std::io::Error>
+	{
+		let end = data_begin_position + offset as u64 + byte_count as u64;
+		if end > data_cursor.get_ref().len() as u64
+		{
+			return io_error!(
+				InvalidData, 
+				format!(
+					"Offset {} and byte count {} exceed the available data of {} bytes!", 
+					offset, 
+					byte_count, 
+					data_cursor.get_ref().len() as u64 - data_begin_position.min(data_cursor.get_ref().len() as u64)
+				)
+			);
+		}
+		return Ok(());
+	}
+
+	/// Converts an offset or component count read from a BigTIFF file to a 
+	/// u32, as larger values are not supported by the rest of little_exif
+	fn
+	big_tiff_value_to_u32
+	(
+		raw:    &[u8],
+		endian: &Endian
+	)
+	-> Result<u32, std::io::Error>
+	{
+		let value = from_u8_vec_macro!(u64, &raw.to_vec(), endian);
+		if value > u32::MAX as u64
+		{
+			return io_error!(Unsupported, format!("BigTIFF value {} exceeds the supported maximum of {}!", value, u32::MAX));
+		}
+		return Ok(value as u32);
+	}
+
+	/// Converts the data of the BigTIFF specific 8 byte formats to their 
+	/// 4 byte counterparts, e.g. LONG8 to INT32U, so that the tags can be
+	/// processed like in a regular TIFF file
+	fn
+	convert_big_tiff_data
+	(
+		hex_format: u16,
+		raw_data:   &[u8],
+		endian:     &Endian
+	)
+	-> Result<(ExifTagFormat, Vec<u8>), std::io::Error>
+	{
+		let mut converted = Vec::new();
+		for chunk in raw_data.chunks_exact(8)
+		{
+			if hex_format == BIG_TIFF_FORMAT_SLONG8
+			{
+				let value = from_u8_vec_macro!(i64, &chunk.to_vec(), endian);
+				if value < i32::MIN as i64 || value > i32::MAX as i64
+				{
+					return io_error!(Unsupported, format!("BigTIFF value {} exceeds the supported range of INT32S!", value));
+				}
+				converted.extend(to_u8_vec_macro!(i32, &(value as i32), endian));
+			}
+			else
+			{
+				let value = Self::big_tiff_value_to_u32(chunk, endian)?;
+				converted.extend(to_u8_vec_macro!(u32, &value, endian));
+			}
+		}
+
+		let format = if hex_format == BIG_TIFF_FORMAT_SLONG8 { ExifTagFormat::INT32S } else { ExifTagFormat::INT32U };
+		return Ok((format, converted));
 	}
 
 	/// If everything goes Ok and there is enough data to unpack, this returns
 	/// the offset to the next generic IFD that needs to be processed.
+	/// If `big_tiff` is set, the IFD gets decoded using the BigTIFF layout
+	/// with 8 byte counts, offsets and links.
 	pub(crate) fn
 	decode_ifd
 	(
 		data_cursor:         &mut Cursor<&Vec<u8>>,
 		data_begin_position:      u64,                                          // Stays the same for all calls to this function while decoding
 		endian:              &    Endian,
+		big_tiff:                 bool,                                         // Stays the same for all calls to this function while decoding
 		group:               &    ExifTagGroup,
 		generic_ifd_nr:           u32,                                          // Reuse value for recursive calls; only gets incremented by caller
 		insert_into:         &mut Vec<ImageFileDirectory>,                      // Stays the same for all calls to this function while decoding
+		makernote_offset:    &mut Option<u32>,                                  // Stays the same for all calls to this function while decoding
+		warnings:            &mut Vec<MetadataWarning>,                         // Stays the same for all calls to this function while decoding
+		visited_offsets:     &mut Vec<u32>,                                     // Stays the same for all calls to this function while decoding
+		depth:                    usize,                                        // Gets incremented for recursive calls
 	)
 	-> Result<Option<u32>, std::io::Error>
 	{
@@ -113,22 +349,50 @@ ImageFileDirectory
 		// Backup the entry position where this IFD started
 		let data_cursor_entry_position = data_cursor.position();
 
+		// An IFD that links back to an already decoded one (or to itself) 
+		// would otherwise result in an endless recursion or loop
+		let ifd_offset = data_cursor_entry_position.saturating_sub(data_begin_position) as u32;
+		if visited_offsets.contains(&ifd_offset)
+		{
+			return io_error!(InvalidData, "Circular IFD reference");
+		}
+		visited_offsets.push(ifd_offset);
+
+		if depth > MAX_IFD_DEPTH
+		{
+			return io_error!(InvalidData, format!("IFDs are nested deeper than {} levels!", MAX_IFD_DEPTH));
+		}
+
+		// The sizes of the different parts of the IFD depend on whether this
+		// is a regular TIFF or a BigTIFF
+		// Note: The value/offset section has the same length as the link
+		let (count_length, entry_length, link_length) = match big_tiff
+		{
+			false => (2usize, IFD_ENTRY_LENGTH,          IFD_END_NO_LINK.len()),
+			true  => (8usize, BIG_TIFF_IFD_ENTRY_LENGTH, BIG_TIFF_IFD_END_NO_LINK.len()),
+		};
+
 		// Check if there is enough data to decode an IFD
-		if (data_cursor.get_ref().len() as i64 - data_cursor_entry_position as i64) < 6i64
+		if (data_cursor.get_ref().len() as i64 - data_cursor_entry_position as i64) < (count_length + IFD_END_NO_LINK.len()) as i64
 		{
 			return Ok(None);
 		}
 
-		// The first two bytes give us the number of entries in this IFD
-		let mut number_of_entries_buffer = vec![0u8; 2];
+		// The first two (BigTIFF: eight) bytes give us the number of entries
+		// in this IFD
+		let mut number_of_entries_buffer = vec![0u8; count_length];
 		data_cursor.read_exact(&mut number_of_entries_buffer)?;
-		let number_of_entries = from_u8_vec_macro!(u16, &number_of_entries_buffer.to_vec(), endian);
+		let number_of_entries = match big_tiff
+		{
+			false => from_u8_vec_macro!(u16, &number_of_entries_buffer.to_vec(), endian) as u32,
+			true  => Self::big_tiff_value_to_u32(&number_of_entries_buffer, endian)?,
+		};
 
 		// Check that there is enough data to unpack
 		let required = 0
-			+ 2
-			+ IFD_ENTRY_LENGTH as usize * number_of_entries as usize 
-			+ IFD_END_NO_LINK.len();
+			+ count_length
+			+ entry_length as usize * number_of_entries as usize 
+			+ link_length;
 		let available = (0
 			+ data_cursor.get_ref().len() as i64 
 			- data_cursor_entry_position  as i64) as usize;
@@ -151,26 +415,47 @@ ImageFileDirectory
 		// TAG-DECODING
 
 		// Storing all tags while decoding
-		let mut tags = Vec::new();
+		let mut tags     = Vec::new();
+		let mut sub_ifds = Vec::new();
 
 		// loop through the entries - assumes that the value stored in
 		// `number_of_entries` is correct
 		for _ in 0..number_of_entries
 		{
 			// Read the entry into a buffer
-			let mut entry_buffer = vec![0u8; IFD_ENTRY_LENGTH as usize];
+			let mut entry_buffer = vec![0u8; entry_length as usize];
 			data_cursor.read_exact(&mut entry_buffer)?;
 
-			// Decode the first 8 bytes with the tag, format and component number
+			// Decode the first 8 (BigTIFF: 12) bytes with the tag, format and
+			// component number
 			let hex_tag              = from_u8_vec_macro!(u16, &entry_buffer[0..2].to_vec(), endian);
 			let hex_format           = from_u8_vec_macro!(u16, &entry_buffer[2..4].to_vec(), endian);
-			let hex_component_number = from_u8_vec_macro!(u32, &entry_buffer[4..8].to_vec(), endian);
+			let hex_component_number = match big_tiff
+			{
+				false => from_u8_vec_macro!(u32, &entry_buffer[4..8].to_vec(), endian),
+				true  => Self::big_tiff_value_to_u32(&entry_buffer[4..12], endian)?,
+			};
+			let value_section        = &entry_buffer[(entry_length as usize - link_length)..];
+
+			// BigTIFF introduces formats for 8 byte values, which are read as
+			// such and converted later on
+			let is_big_tiff_format = big_tiff && [
+				BIG_TIFF_FORMAT_LONG8, 
+				BIG_TIFF_FORMAT_SLONG8, 
+				BIG_TIFF_FORMAT_IFD8
+			].contains(&hex_format);
 
 			// Decode the format
 			// TODO: What to do in case these two differ but the given format
 			// can be casted into the expected one, e.g. R64U to R64S?
-			let format;
-			if let Some(decoded_format) = ExifTagFormat::from_u16(hex_format)
+			// Note: The format of the BigTIFF specific ones gets determined 
+			// after their conversion
+			let mut format;
+			if is_big_tiff_format
+			{
+				format = ExifTagFormat::INT32U;
+			}
+			else if let Some(decoded_format) = ExifTagFormat::from_u16(hex_format)
 			{
 				format = decoded_format;
 			}
@@ -186,13 +471,33 @@ ImageFileDirectory
 			// data even if the given format in the image file is not the
 			// right/default one for the currently processed tag according to 
 			// the exif specification. 
-			let byte_count = format.bytes_per_component() * hex_component_number;
+			let bytes_per_component = if is_big_tiff_format { 8 } else { format.bytes_per_component() };
+			let byte_count = match bytes_per_component.checked_mul(hex_component_number)
+			{
+				Some(byte_count) => byte_count,
+				None             => return io_error!(InvalidData, format!("Number of components {} of tag {:#06x} is too large!", hex_component_number, hex_tag)),
+			};
 
-			let raw_data;
-			if byte_count > 4
+			let mut raw_data;
+			if byte_count as usize > link_length
 			{
 				// Compute the offset
-				let hex_offset = from_u8_vec_macro!(u32, &entry_buffer[8..12].to_vec(), endian);
+				let hex_offset = match big_tiff
+				{
+					false => from_u8_vec_macro!(u32, &value_section.to_vec(), endian),
+					true  => Self::big_tiff_value_to_u32(value_section, endian)?,
+				};
+
+				// Remember where the MakerNote data is located, as it may
+				// contain offsets that depend on this position
+				if hex_tag == MAKERNOTE_HEX && *group == ExifTagGroup::EXIF
+				{
+					*makernote_offset = Some(hex_offset);
+				}
+
+				// Make sure that the data is located within the buffer before
+				// allocating memory for it
+				Self::check_data_range(data_cursor, data_begin_position, hex_offset, byte_count)?;
 
 				// Backup current position & go to offset position
 				let backup_position = data_cursor.position();
@@ -209,9 +514,62 @@ ImageFileDirectory
 			}
 			else
 			{
-				// The 4 bytes are the actual data
+				// The 4 (BigTIFF: 8) bytes are the actual data
 				// Note: This may actually be *less* than 4 bytes! 
-				raw_data = entry_buffer[8..(8+byte_count as usize)].to_vec();
+				raw_data = value_section[0..byte_count as usize].to_vec();
+			}
+
+			if is_big_tiff_format
+			{
+				(format, raw_data) = Self::convert_big_tiff_data(hex_format, &raw_data, endian)?;
+			}
+
+			// The SubIFDs tag contains offsets to further IFDs that get
+			// decoded and stored with this IFD instead of the tag itself
+			if hex_tag == SUBIFDS_HEX && *group == ExifTagGroup::GENERIC && format == ExifTagFormat::INT32U
+			{
+				let offsets         = <INT32U as U8conversion<INT32U>>::from_u8_vec(&raw_data, endian);
+				let backup_position = data_cursor.position();
+
+				for offset in offsets
+				{
+					if data_begin_position as usize + offset as usize >= data_cursor.get_ref().len()
+					{
+						warnings.push(MetadataWarning::SkippedIfd { group: *group, offset: offset });
+						continue;
+					}
+
+					data_cursor.set_position(data_begin_position);
+					data_cursor.seek(std::io::SeekFrom::Current(offset as i64))?;
+
+					// The SubIFD gets pushed last, after any IFDs it links to
+					// itself (which are not supported and thus dropped)
+					let mut sub_ifd_vec = Vec::new();
+					if let Err(error) = Self::decode_ifd(
+						data_cursor,
+						data_begin_position,
+						endian,
+						big_tiff,
+						group,
+						generic_ifd_nr,
+						&mut sub_ifd_vec,
+						makernote_offset,
+						warnings,
+						visited_offsets,
+						depth + 1,
+					)
+					{
+						return Err(std::io::Error::new(
+							error.kind(),
+							format!("Could not decode SubIFD at offset {}:\n  {}", offset, error)
+						));
+					}
+
+					sub_ifds.extend(sub_ifd_vec.pop());
+				}
+
+				data_cursor.set_position(backup_position);
+				continue;
 			}
 
 			// Try to get the tag via its hex value
@@ -220,6 +578,8 @@ ImageFileDirectory
 			// Start of by checking if this is an unknown tag
 			if tag_result.is_err()
 			{
+				warnings.push(MetadataWarning::UnknownTag { hex: hex_tag, group: *group });
+
 				// Note: `from_u16_with_data` can NOT be called initially due
 				// to some possible conversion of data needed, e.g. INT16U to
 				// INT32U, which is not accounted for yet at this stage
@@ -240,9 +600,20 @@ ImageFileDirectory
 			if let TagType::IFD_OFFSET(subifd_group) = tag.get_tag_type()
 			{
 				// Compute the offset to the SubIFD and save the current position
+				if raw_data.len() != 4
+				{
+					return io_error!(InvalidData, format!("Offset tag {:#06x} has {} bytes of data instead of 4!", hex_tag, raw_data.len()));
+				}
 				let offset          = from_u8_vec_macro!(u32, &raw_data, endian) as usize;
 				let backup_position = data_cursor.position();
 
+				// Skip SubIFDs that are located past the end of the data
+				if data_begin_position as usize + offset >= data_cursor.get_ref().len()
+				{
+					warnings.push(MetadataWarning::SkippedIfd { group: subifd_group, offset: offset as u32 });
+					continue;
+				}
+
 				// Go to the SubIFD offset and decode that
 				data_cursor.set_position(data_begin_position);
 				data_cursor.seek(std::io::SeekFrom::Current(offset as i64))?;
@@ -251,9 +622,14 @@ ImageFileDirectory
 					data_cursor,
 					data_begin_position,
 					endian,
+					big_tiff,
 					&subifd_group,
 					generic_ifd_nr,
 					insert_into,
+					makernote_offset,
+					warnings,
+					visited_offsets,
+					depth + 1,
 				);
 
 				// Check that this actually worked
@@ -280,20 +656,47 @@ ImageFileDirectory
 				}
 				else
 				{
-					return io_error!(Other, format!("Could not decode SubIFD {:?}:\n  {}", subifd_group, subifd_decode_result.err().unwrap()));
+					let error = subifd_decode_result.err().unwrap();
+					return Err(std::io::Error::new(
+						error.kind(),
+						format!("Could not decode SubIFD {:?}:\n  {}", subifd_group, error)
+					));
 				}
 			}
 
 			// At this point we check if the format is actually what we expect
 			// it to be and convert it if possible
-			tag = decode_tag_with_format_exceptions(
+			if format != tag.format()
+			{
+				warnings.push(MetadataWarning::UnexpectedFormat { 
+					hex:      hex_tag, 
+					group:    *group, 
+					expected: tag.format(), 
+					found:    format.clone()
+				});
+			}
+
+			tag = match decode_tag_with_format_exceptions(
 				&tag,
-				format,
+				format.clone(),
 				&raw_data,
 				endian,
 				hex_tag,
 				group
-			)?;
+			)
+			{
+				Ok(decoded_tag) => decoded_tag,
+
+				// Value tags whose format can't be converted are preserved 
+				// as unknown tags so that they don't get lost when writing 
+				// the metadata back. Data offset tags can't be preserved that
+				// way, as their offsets become invalid.
+				Err(_) if tag.get_tag_type() == TagType::VALUE => {
+					tags.push(ExifTag::unknown_from_u8_vec(hex_tag, &format, &raw_data, endian, group));
+					continue;
+				},
+				Err(error) => return Err(error),
+			};
 
 			// Now we have at least confirmed that the format is ok (or has
 			// been corrected). Next, we need to differ between the two other
@@ -350,11 +753,25 @@ ImageFileDirectory
 			{
 				let backup_position = data_cursor.position();
 
+				// The strips are only read if all of them are located within 
+				// the data. Otherwise (e.g. for image data of a DNG that is 
+				// not part of the data at hand) the offsets are kept as they
+				// are, without dereferencing them
+				let strips_readable = offsets.len() == byte_counts.len()
+					&& offsets.iter().zip(byte_counts.iter()).all(|(offset, byte_count)| 
+						Self::check_data_range(data_cursor, data_begin_position, *offset, *byte_count).is_ok()
+					);
+
 				let mut strip_data = Vec::new();
 
 				// Gather the data from the offsets
 				for (offset, byte_count) in offsets.iter().zip(byte_counts.iter())
 				{
+					if !strips_readable
+					{
+						break;
+					}
+
 					data_cursor.set_position(data_begin_position);
 					data_cursor.seek(std::io::SeekFrom::Current(*offset as i64))?;
 
@@ -364,7 +781,15 @@ ImageFileDirectory
 				}
 
 				// Push StripOffset tag to tags vector
-				tags.push(ExifTag::StripOffsets(Vec::new(), strip_data));
+				if strips_readable
+				{
+					tags.push(ExifTag::StripOffsets(Vec::new(), strip_data));
+				}
+				else
+				{
+					warnings.push(MetadataWarning::UnreadableImageData { hex: 0x0111, group: *group });
+					tags.push(ExifTag::StripOffsets(offsets, Vec::new()));
+				}
 
 				// Push StripByteCounts tag to tags vector
 				tags.push(ExifTag::StripByteCounts(byte_counts));
@@ -392,6 +817,11 @@ ImageFileDirectory
 				let backup_position = data_cursor.position();
 
 				if offset.len() == 1 && length.len() == 1
+				&& Self::check_data_range(data_cursor, data_begin_position, offset[0], length[0]).is_err()
+				{
+					warnings.push(MetadataWarning::UnreadableImageData { hex: 0x0201, group: *group });
+				}
+				else if offset.len() == 1 && length.len() == 1
 				{
 					let mut thumbnail_data = vec![0u8; length[0] as usize];
 
@@ -425,11 +855,12 @@ ImageFileDirectory
 		insert_into.push(ImageFileDirectory { 
 			tags: tags, 
 			ifd_type: *group, 
-			belongs_to_generic_ifd_nr: generic_ifd_nr
+			belongs_to_generic_ifd_nr: generic_ifd_nr,
+			sub_ifds: sub_ifds,
 		});
 
 		// Read in the link to the next IFD and check if its zero
-		let mut next_ifd_link_buffer = vec![0u8; 4];
+		let mut next_ifd_link_buffer = vec![0u8; link_length];
 		if data_cursor.read_exact(&mut next_ifd_link_buffer).is_err()
 		{
 			// Covers the case that this IFD is stored at the very end of the
@@ -438,14 +869,17 @@ ImageFileDirectory
 		}
 
 		let link_is_zero = next_ifd_link_buffer.iter()
-			.zip(IFD_END_NO_LINK.iter())
-			.filter(|&(read, constant)| read == constant)
-			.count() == IFD_END_NO_LINK.len();
+			.all(|&read| read == 0x00);
 
 		if link_is_zero
 		{
 			return Ok(None);
 		}
+
+		if big_tiff
+		{
+			return Ok(Some(Self::big_tiff_value_to_u32(&next_ifd_link_buffer, endian)?));
+		}
 		return Ok(Some(from_u8_vec_macro!(u32, &next_ifd_link_buffer, endian)));
 	}
 
@@ -462,21 +896,42 @@ ImageFileDirectory
 		data:                       &Metadata,
 		ifds_with_offset_info_only: &mut Vec<ImageFileDirectory>,
 		encode_vec:                 &mut Vec<u8>,
-		current_offset:             &mut u32
+		current_offset:             &mut u32,
+		warnings:                   &mut Vec<MetadataWarning>
 	)
 	-> Result<(u64, Vec<u8>), std::io::Error>
 	{
 
 		// Store all relevant tags (IFD tags + offset tags) in a temporary 
 		// location and sort them there
-		let all_relevant_tags = self.tags.iter().chain(ifds_with_offset_info_only
+		let mut all_relevant_tags = self.tags.iter().chain(ifds_with_offset_info_only
 			.iter()
 			.filter(|ifd| 
 				ifd.get_generic_ifd_nr() == self.get_generic_ifd_nr() &&
 				ifd.get_ifd_type()       == self.get_ifd_type()
 			)
-			.next().unwrap().get_tags()
-			.iter()).cloned().collect::<Vec<ExifTag>>();
+			.flat_map(|ifd| ifd.get_tags().iter())
+		).cloned().collect::<Vec<ExifTag>>();
+
+		// The offset tags come after the tags of the IFD itself, and the tags
+		// of a decoded IFD might not be sorted in the first place, so sort
+		// them as required by the TIFF specification (see `sort_tags`)
+		all_relevant_tags.sort_by_key(|tag| tag.as_u16());
+
+		// Insert a placeholder for the SubIFDs tag whose offsets are only 
+		// known once the SubIFDs are encoded
+		if !self.sub_ifds.is_empty()
+		{
+			all_relevant_tags.retain(|tag| tag.as_u16() != SUBIFDS_HEX);
+			let position = all_relevant_tags.iter()
+				.position(|tag| tag.as_u16() > SUBIFDS_HEX)
+				.unwrap_or(all_relevant_tags.len());
+			all_relevant_tags.insert(position, ExifTag::UnknownINT32U(
+				vec![0; self.sub_ifds.len()], 
+				SUBIFDS_HEX, 
+				ExifTagGroup::GENERIC
+			));
+		}
 
 		// Start writing this IFD by adding the number of entries
 		let count_entries = all_relevant_tags.iter().filter(
@@ -523,6 +978,22 @@ ImageFileDirectory
 			// StripOffsets that are stored in a local vec
 			let value = match tag.get_tag_type()
 			{
+				TagType::VALUE if tag.as_u16() == SUBIFDS_HEX && !self.sub_ifds.is_empty() => {
+					let mut value = Vec::new();
+					for sub_ifd in &self.sub_ifds
+					{
+						let (_, sub_ifd_offset) = sub_ifd.encode_ifd(
+							data, 
+							&mut Vec::new(), 
+							&mut ifd_offset_area, 
+							current_offset,
+							warnings
+						)?;
+						value.extend(sub_ifd_offset);
+					}
+					value
+				},
+
 				TagType::VALUE => {
 					tag.value_as_u8_vec(&data.get_endian())
 				},
@@ -530,6 +1001,12 @@ ImageFileDirectory
 				TagType::DATA_OFFSET(_) => {
 					match tag
 					{
+						// Strips that could not be read while decoding keep
+						// their original offsets
+						ExifTag::StripOffsets(offsets, strip_data) if strip_data.is_empty() => {
+							offsets.to_u8_vec(&data.get_endian())
+						},
+
 						ExifTag::StripOffsets(_, strip_data) => {
 							let mut value = Vec::new();
 							for strip in strip_data
@@ -572,7 +1049,8 @@ ImageFileDirectory
 								data, 
 								ifds_with_offset_info_only, 
 								&mut ifd_offset_area, 
-								current_offset
+								current_offset,
+								warnings
 							)
 						{
 							subifd_offset
@@ -597,10 +1075,10 @@ ImageFileDirectory
 			
 			// Add Tag & Data Format /                                          2 + 2 bytes
 			encode_vec.extend(to_u8_vec_macro!(u16, &tag.as_u16(),          &data.get_endian()).iter());
-			encode_vec.extend(to_u8_vec_macro!(u16, &tag.format().as_u16(), &data.get_endian()).iter());
+			encode_vec.extend(to_u8_vec_macro!(u16, &tag.type_code(),       &data.get_endian()).iter());
 
 			// Add number of components /                                       4 bytes
-			let number_of_components: u32 = tag.number_of_components();
+			let number_of_components: u32 = tag.count();
 			encode_vec.extend(to_u8_vec_macro!(u32, &number_of_components, &data.get_endian()).iter());
 
 			// Optional string padding (i.e. string is shorter than it should be)
@@ -619,6 +1097,17 @@ ImageFileDirectory
 			let byte_count: u32 = number_of_components * tag.format().bytes_per_component();
 			if byte_count > 4
 			{
+				// The MakerNote data may contain offsets that need to be 
+				// adjusted to the new position of the data
+				let value = if tag.as_u16() == MAKERNOTE_HEX && self.ifd_type == ExifTagGroup::EXIF
+				{
+					data.relocate_makernote(value, *current_offset, warnings)
+				}
+				else
+				{
+					value
+				};
+
 				encode_vec.extend(to_u8_vec_macro!(u32, current_offset, &data.get_endian()).iter());
 				ifd_offset_area.extend(value.iter());
 				ifd_offset_area.extend(string_padding.iter());
@@ -2,6 +2,8 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::metadata::dedup::DedupStrategy;
 
 use super::ImageFileDirectory;
 
@@ -13,12 +15,98 @@ ImageFileDirectory
 	/// otherwise. 
 	/// If the tag already exists in the IFD, it is replaced by the given tag.
 	/// All tags in the IFD are sorted after the insert. 
+	/// Returns an error if the tag is an `Unknown...` tag whose hex value 
+	/// belongs to a known tag that requires a different format, e.g. an
+	/// `INT16U` value for a tag that must be `RATIONAL64U`. Use 
+	/// `set_tag_unchecked` for intentionally writing non-standard formats.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// use little_exif::ifd::ImageFileDirectory;
+	///
+	/// let mut ifd = ImageFileDirectory::new_with_tags(Vec::new(), ExifTagGroup::EXIF, 0);
+	///
+	/// // ExposureTime (0x829a) must be stored as RATIONAL64U
+	/// assert!(ifd.set_tag(ExifTag::UnknownINT16U(vec![1], 0x829a, ExifTagGroup::EXIF)).is_err());
+	/// assert!(ifd.set_tag(ExifTag::ExposureTime(vec![0.01.into()])).is_ok());
+	/// ```
 	pub fn
 	set_tag
 	(
 		&mut self,
 		input_tag: ExifTag,	
 	)
+	-> Result<(), std::io::Error>
+	{
+		if input_tag.is_unknown()
+		{
+			if let Ok(known_tag) = ExifTag::from_u16(input_tag.as_u16(), &input_tag.get_group())
+			{
+				if known_tag.format() != input_tag.format()
+				{
+					return io_error!(
+						InvalidInput, 
+						format!(
+							"Tag {} ({:#06x}) requires format {:?}, but {:?} was provided",
+							known_tag.name(),
+							input_tag.as_u16(),
+							known_tag.format(),
+							input_tag.format()
+						)
+					);
+				}
+			}
+		}
+
+		self.set_tag_unchecked(input_tag);
+		return Ok(());
+	}
+
+	/// Adds a tag to the image file directory. Unlike `set_tag`, this does 
+	/// not replace an existing tag with the same hex value but returns an
+	/// error in this case, so that no duplicates can be introduced by 
+	/// accident. All tags in the IFD are sorted after the insert. 
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// use little_exif::ifd::ImageFileDirectory;
+	///
+	/// let mut ifd = ImageFileDirectory::new_with_tags(Vec::new(), ExifTagGroup::GENERIC, 0);
+	///
+	/// assert!(ifd.add_tag(ExifTag::Model("Camera".to_string())).is_ok());
+	/// assert!(ifd.add_tag(ExifTag::Model("Other camera".to_string())).is_err());
+	/// ```
+	pub fn
+	add_tag
+	(
+		&mut self,
+		input_tag: ExifTag,
+	)
+	-> Result<(), std::io::Error>
+	{
+		if self.tags.iter().any(|tag| tag.as_u16() == input_tag.as_u16())
+		{
+			return io_error!(
+				AlreadyExists, 
+				format!("Tag {:#06x} already exists in the {:?} IFD", input_tag.as_u16(), self.ifd_type)
+			);
+		}
+
+		return self.set_tag(input_tag);
+	}
+
+	/// Same as `set_tag`, but does not check the format of the tag. Intended
+	/// for advanced users who intentionally write non-standard formats.
+	pub fn
+	set_tag_unchecked
+	(
+		&mut self,
+		input_tag: ExifTag,	
+	)
 	{
 		if input_tag.get_group() != self.ifd_type
 		{
@@ -29,6 +117,18 @@ ImageFileDirectory
 		self.sort_tags();
 	}
 
+	/// Replaces the IFDs referenced via the `SubIFDs` tag (0x014a) of this
+	/// IFD, see `get_sub_ifds`
+	pub(crate) fn
+	set_sub_ifds
+	(
+		&mut self,
+		sub_ifds: Vec<ImageFileDirectory>
+	)
+	{
+		self.sub_ifds = sub_ifds;
+	}
+
 	/// Removes a tag with a given hex value from the image file directory.
 	/// If the tag is removed successfully, nothing happens.
 	/// If no such tag exists, nothing happens.
@@ -42,4 +142,46 @@ ImageFileDirectory
 		self.tags.retain(|tag| tag.as_u16() != input_tag.as_u16());
 		self.sort_tags();
 	}
-}
\ No newline at end of file
+
+	/// Reduces tags with the same hex value to a single one according to the
+	/// given strategy, see `Metadata::dedup_tags`. Also applies to SubIFDs.
+	/// Returns the number of tags that were removed.
+	pub(crate) fn
+	dedup_tags
+	(
+		&mut self,
+		strategy: DedupStrategy
+	)
+	-> usize
+	{
+		let number_of_tags = self.tags.len();
+
+		let mut kept_tags: Vec<ExifTag> = Vec::new();
+		for tag in self.tags.drain(..)
+		{
+			if let Some(kept_tag) = kept_tags.iter_mut().find(|kept_tag| kept_tag.as_u16() == tag.as_u16())
+			{
+				let replace = match strategy
+				{
+					DedupStrategy::KeepFirst        => false,
+					DedupStrategy::KeepLast         => true,
+					DedupStrategy::KeepLargestCount => tag.number_of_components() > kept_tag.number_of_components(),
+				};
+
+				if replace
+				{
+					*kept_tag = tag;
+				}
+			}
+			else
+			{
+				kept_tags.push(tag);
+			}
+		}
+		self.tags = kept_tags;
+
+		return number_of_tags - self.tags.len() + self.sub_ifds.iter_mut()
+			.map(|sub_ifd| sub_ifd.dedup_tags(strategy))
+			.sum::<usize>();
+	}
+}
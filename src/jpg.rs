@@ -12,13 +12,30 @@ use std::path::Path;
 
 use crate::endian::Endian;
 use crate::metadata::Metadata;
+use crate::metadata::removal_plan::RemovalPlan;
+use crate::metadata::warning::MetadataWarning;
 use crate::u8conversion::*;
 use crate::general_file_io::*;
 
 pub(crate) const JPG_SIGNATURE: [u8; 2] = [0xff, 0xd8];
 
 const JPG_MARKER_PREFIX: u8  = 0xff;
+const JPG_APP0_MARKER:   u8  = 0xe0;
 const JPG_APP1_MARKER:   u16 = 0xffe1;
+const JPG_COM_MARKER:    u8  = 0xfe;
+
+// The length of a segment is stored using 2 bytes and includes these 2 bytes
+const JPG_MAX_SEGMENT_LENGTH: usize = 0xffff;
+
+// The maximum length of the encoded EXIF data that fits into an APP1 segment
+// next to the length field and the "Exif" header
+const JPG_MAX_EXIF_LENGTH: usize = JPG_MAX_SEGMENT_LENGTH - 2 - EXIF_HEADER.len();
+
+// APP2 segments storing (a part of) an ICC profile start with "ICC_PROFILE"
+// and a NUL byte, followed by the sequence number and the total count
+const ICC_PROFILE_IDENTIFIER: [u8; 12] = [
+	0x49, 0x43, 0x43, 0x5f, 0x50, 0x52, 0x4f, 0x46, 0x49, 0x4c, 0x45, 0x00
+];
 
 
 
@@ -27,14 +44,28 @@ encode_metadata_jpg
 (
 	exif_vec: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
 	// vector storing the data that will be returned
 	let mut jpg_exif: Vec<u8> = Vec::new();
 
 	// Compute the length of the exif data (includes the two bytes of the
 	// actual length field)
-	let length = 2u16 + (EXIF_HEADER.len() as u16) + (exif_vec.len() as u16);
+	// The EXIF data can't be split across multiple APP1 segments, so it has to
+	// fit into a single one
+	let segment_length = 2 + EXIF_HEADER.len() + exif_vec.len();
+	if segment_length > JPG_MAX_SEGMENT_LENGTH
+	{
+		return io_error!(
+			InvalidInput,
+			format!(
+				"EXIF data too large for JPEG APP1 segment: {} bytes (limit: {} bytes)",
+				EXIF_HEADER.len() + exif_vec.len(),
+				JPG_MAX_SEGMENT_LENGTH - 2
+			)
+		);
+	}
+	let length = segment_length as u16;
 
 	// Start with the APP1 marker and the length of the data
 	// Then copy the previously encoded EXIF data 
@@ -43,7 +74,7 @@ encode_metadata_jpg
 	jpg_exif.extend(EXIF_HEADER.iter());
 	jpg_exif.extend(exif_vec.iter());
 
-	return jpg_exif;
+	return Ok(jpg_exif);
 }
 
 
@@ -63,7 +94,7 @@ check_signature
 
 	if !signature_is_valid
 	{
-		return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+		return little_exif_error!(InvalidSignature, "Can't open JPG file - Wrong signature!");
 	}
 
 	// Signature is valid - can proceed using as JPG file
@@ -89,6 +120,8 @@ file_check_signature
 }
 
 
+/// Removes the APP1 segments containing EXIF data. Other APP1 segments, e.g.
+/// containing XMP data, are kept.
 pub(crate) fn
 clear_metadata
 (
@@ -96,10 +129,27 @@ clear_metadata
 )
 -> Result<(), std::io::Error>
 {
-	return clear_segment(file_buffer, 0xe1);
+	return clear_segment_with_identifier(file_buffer, 0xe1, Some(&EXIF_HEADER));
 }
 
 
+/// Determines the APP1 segments containing EXIF data that `clear_metadata`
+/// would remove, without modifying the file
+pub(crate) fn
+plan_clear_metadata
+(
+	file_buffer: &Vec<u8>,
+)
+-> Result<Vec<RemovalPlan>, std::io::Error>
+{
+	let segments = find_segments_with_identifier(file_buffer, 0xe1, Some(&EXIF_HEADER), false)?;
+
+	return Ok(segments.iter()
+		.map(|segment| RemovalPlan::new("APP1", segment.start as usize..segment.end() as usize))
+		.collect()
+	);
+}
+
 pub(crate) fn
 clear_segment
 (
@@ -108,83 +158,108 @@ clear_segment
 )
 -> Result<(), std::io::Error>
 {
-	check_signature(&file_buffer)?;
+	return clear_segment_with_identifier(file_buffer, segment_marker, None);
+}
+
+/// Removes all segments with the given marker. If an identifier is given,
+/// only those segments whose data starts with the identifier get removed.
+fn
+clear_segment_with_identifier
+(
+	file_buffer:    &mut Vec<u8>,
+	segment_marker: u8,
+	identifier:     Option<&[u8]>,
+)
+-> Result<(), std::io::Error>
+{
+	let segments = find_segments_with_identifier(file_buffer, segment_marker, identifier, false)?;
+
+	// Remove the segments starting with the last one, so that the positions
+	// of the others stay valid
+	for segment in segments.iter().rev()
+	{
+		crate::util::range_remove(file_buffer, segment.start as usize, segment.end() as usize);
+	}
+
+	return Ok(());
+}
+
+/// Gets all segments with the given marker whose data starts with the given
+/// identifier (or all segments with that marker if there is no identifier).
+/// If `stop_at_sos` is set, only the segments before the image data (i.e.
+/// the first SOS segment) are considered.
+fn
+find_segments_with_identifier
+(
+	file_buffer:    &Vec<u8>,
+	segment_marker: u8,
+	identifier:     Option<&[u8]>,
+	stop_at_sos:    bool,
+)
+-> Result<Vec<JpgSegment>, std::io::Error>
+{
+	check_signature(file_buffer)?;
 
-	// Setup of variables necessary for going through the file
-	let mut byte_buffer = [0u8; 1];                                             // A buffer for reading in a byte of data from the file
-	let mut previous_byte_was_marker_prefix = false;                            // A boolean for remembering if the previous byte was a marker prefix (0xFF)
 	let mut cursor = Cursor::new(file_buffer);
 
 	// Skip 0xFFD8 at the start
-	cursor.seek(SeekFrom::Current(2))?;
+	cursor.set_position(2);
 
-	loop
+	let mut found_segments = Vec::new();
+	for segment in JpgSegmentIterator::new(cursor)?
 	{
-		// Read next byte into buffer
-		cursor.read_exact(&mut byte_buffer)?;
+		let segment = segment?;
 
-		if previous_byte_was_marker_prefix
+		if stop_at_sos && segment.marker == 0xda
 		{
-			// Check if this is the end of the file. In that case, the length
-			// data can't be read and we need to return prematurely. 
-			if byte_buffer[0] == 0xd9                                           // EOI marker
-			{
-				// No more data to read in
-				return Ok(());
-			}
-
-			// Read in the length of the segment
-			// (which follows immediately after the marker)
-			let mut length_buffer = [0u8; 2];
-			cursor.read_exact(&mut length_buffer)?;
-
-			// Decode the length to determine how much more data there is
-			let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
-			let remaining_length = (length - 2) as usize;
+			break;
+		}
 
-			if byte_buffer[0] == segment_marker                                 // Given marker, e.g. for APP1
-			{
-				// Backup current position, account for the 4 bytes already read
-				let backup_position = cursor.position() - 4;
+		if segment.end() > file_buffer.len() as u64
+		{
+			return io_error!(UnexpectedEof, "JPEG segment extends beyond the end of the data!");
+		}
 
-				// Skip the segment
-				cursor.seek(SeekFrom::Current(remaining_length as i64))?;
+		let data = &file_buffer[segment.data_start as usize..segment.end() as usize];
+		if segment.marker == segment_marker
+		&& data.starts_with(identifier.unwrap_or(&[]))
+		{
+			found_segments.push(segment);
+		}
+	}
 
-				// Copy data from there onwards into a buffer
-				let mut temp_buffer = Vec::new();
-				cursor.read_to_end(&mut temp_buffer)?;
+	return Ok(found_segments);
+}
 
-				// Overwrite segment
-				cursor.set_position(backup_position);
-				cursor.write_all(&temp_buffer)?;
+/// Checks if the data of the segment the cursor is currently positioned at
+/// starts with the given identifier (or if there is no identifier at all).
+/// The position of the cursor stays the same.
+fn
+segment_starts_with
+<T: Seek + Read>
+(
+	cursor:           &mut T,
+	remaining_length: usize,
+	identifier:       Option<&[u8]>,
+)
+-> Result<bool, std::io::Error>
+{
+	let identifier = match identifier
+	{
+		Some(identifier) => identifier,
+		None             => return Ok(true),
+	};
 
-				// Cut off right-most bytes that are now duplicates due 
-				// to the previous shift-to-left operation
-				let cutoff_index = backup_position as usize + temp_buffer.len();
-				cursor.get_mut().truncate(cutoff_index);
+	if remaining_length < identifier.len()
+	{
+		return Ok(false);
+	}
 
-				// Seek to start of next segment
-				cursor.set_position(backup_position);
-			}
-			else if byte_buffer[0] == 0xda
-			{
-				// See `generic_read_metadata`
-				cursor.seek(SeekFrom::Current(remaining_length as i64))?;
-				skip_ecs(&mut cursor)?;
-			}
-			else
-			{
-				// Skip this segment
-				cursor.seek(SeekFrom::Current(remaining_length as i64))?;
-			}
+	let mut identifier_buffer = vec![0u8; identifier.len()];
+	cursor.read_exact(&mut identifier_buffer)?;
+	cursor.seek(SeekFrom::Current(-(identifier.len() as i64)))?;
 
-			previous_byte_was_marker_prefix = false;
-		}
-		else
-		{
-			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
-		}
-	}
+	return Ok(identifier_buffer == identifier);
 }
 
 pub(crate) fn
@@ -218,7 +293,15 @@ file_clear_metadata
 )
 -> Result<(), std::io::Error>
 {
-	return file_clear_segment(path, 0xe1);
+	let mut file_buffer: Vec<u8> = std::fs::read(path)?;
+
+	// Only clear the APP1 segments with EXIF data, see `clear_metadata`
+	clear_metadata(&mut file_buffer)?;
+
+	let mut file = std::fs::OpenOptions::new().write(true).truncate(true).open(path)?;
+	perform_file_action!(file.write_all(&file_buffer));
+
+	return Ok(());
 }
 
 
@@ -229,7 +312,7 @@ as_u8_vec
 (
 	general_encoded_metadata: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
 	encode_metadata_jpg(general_encoded_metadata)
 }
@@ -240,17 +323,26 @@ pub(crate) fn
 write_metadata
 (
 	file_buffer: &mut Vec<u8>,
-	metadata:    &Metadata
+	metadata:    &Metadata,
+	warnings:    &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
+	// Encode the data specifically for JPG
+	// This is done first so that the image stays untouched if the data can't
+	// be encoded, e.g. due to being too large (after possibly dropping the
+	// thumbnail, depending on the thumbnail policy)
+	let mut encoded_metadata = encode_metadata_jpg(
+		&metadata.encode_with_size_limit(JPG_MAX_EXIF_LENGTH, warnings)?
+	)?;
+
 	// Remove old metadata
 	clear_metadata(file_buffer)?;
 
-	// Encode the data specifically for JPG
-	let mut encoded_metadata = encode_metadata_jpg(&metadata.encode()?);
-
 	// Insert the metadata right after the signature
+	// All other segments are kept as they are, including their order. This
+	// is important e.g. for the Adobe APP14 segment that specifies the color
+	// transform of YCCK/CMYK images
 	crate::util::insert_multiple_at(file_buffer, 2, &mut encoded_metadata);
 
 	return Ok(());
@@ -264,7 +356,8 @@ pub(crate) fn
 file_write_metadata
 (
 	path:     &Path,
-	metadata: &Metadata
+	metadata: &Metadata,
+	warnings: &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
@@ -277,7 +370,7 @@ file_write_metadata
 	// Writes the metadata to the file_buffer vec
 	// The called function handles the removal of old metadata and the JPG
 	// specific encoding, so we pass only the generally encoded metadata here
-	write_metadata(&mut file_buffer, metadata)?;
+	write_metadata(&mut file_buffer, metadata, warnings)?;
 
 	// Seek back to start & write the file
 	perform_file_action!(file.seek(SeekFrom::Start(0)));
@@ -303,6 +396,199 @@ read_metadata
 	return generic_read_metadata(&mut cursor);
 }
 
+/// Reads the ICC profile stored in one or more APP2 segments. The parts are
+/// concatenated according to their sequence numbers.
+pub(crate) fn
+read_icc_profile
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let mut parts = read_segments_with_identifier(file_buffer, 0xe2, &ICC_PROFILE_IDENTIFIER)?;
+
+	// Each part starts with its sequence number (starting at 1) and the total
+	// number of parts
+	if parts.is_empty() || parts.iter().any(|part| part.len() < 2)
+	{
+		return little_exif_error!(NoMetadata, "No ICC profile found!");
+	}
+
+	let total_count = parts[0][1] as usize;
+	if parts.len() != total_count
+	{
+		return io_error!(InvalidData, format!("Expected {} ICC profile segments but found {}!", total_count, parts.len()));
+	}
+
+	parts.sort_by_key(|part| part[0]);
+
+	let mut icc_profile = Vec::new();
+	for (index, part) in parts.iter().enumerate()
+	{
+		if part[0] as usize != index + 1
+		{
+			return io_error!(InvalidData, "ICC profile segments have invalid sequence numbers!");
+		}
+		icc_profile.extend(&part[2..]);
+	}
+
+	return Ok(icc_profile);
+}
+
+/// Reads the data of the first COM segment, if there is any
+pub(crate) fn
+read_comment
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Option<Vec<u8>>, std::io::Error>
+{
+	let mut comments = read_segments_with_identifier(file_buffer, JPG_COM_MARKER, &[])?;
+	if comments.is_empty()
+	{
+		return Ok(None);
+	}
+	return Ok(Some(comments.swap_remove(0)));
+}
+
+/// Replaces any existing COM segments with a single one containing the given
+/// comment. The segment is inserted right after the signature and the APP0
+/// segment(s), if there are any.
+pub(crate) fn
+write_comment
+(
+	file_buffer: &mut Vec<u8>,
+	comment:     &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	// The length of the segment includes the two bytes of the length field
+	let segment_length = 2 + comment.len();
+	if segment_length > JPG_MAX_SEGMENT_LENGTH
+	{
+		return io_error!(
+			InvalidInput,
+			format!(
+				"Comment too large for JPEG COM segment: {} bytes (limit: {} bytes)",
+				comment.len(),
+				JPG_MAX_SEGMENT_LENGTH - 2
+			)
+		);
+	}
+
+	clear_segment(file_buffer, JPG_COM_MARKER)?;
+
+	// Skip the signature and any APP0 (e.g. JFIF) segments
+	let mut insert_position = JPG_SIGNATURE.len();
+	while file_buffer.len() >= insert_position + 4
+	&&    file_buffer[insert_position]     == JPG_MARKER_PREFIX
+	&&    file_buffer[insert_position + 1] == JPG_APP0_MARKER
+	{
+		let length = from_u8_vec_macro!(u16, &file_buffer[insert_position+2..insert_position+4].to_vec(), &Endian::Big);
+		insert_position += 2 + length as usize;
+	}
+
+	if insert_position > file_buffer.len()
+	{
+		return io_error!(InvalidData, "Invalid JPEG APP0 segment length!");
+	}
+
+	let mut comment_segment = vec![JPG_MARKER_PREFIX, JPG_COM_MARKER];
+	comment_segment.extend(to_u8_vec_macro!(u16, &(segment_length as u16), &Endian::Big));
+	comment_segment.extend(comment);
+
+	crate::util::insert_multiple_at(file_buffer, insert_position, &mut comment_segment);
+
+	return Ok(());
+}
+
+/// Gets the data (without the identifier) of all segments with the given 
+/// marker whose data starts with the given identifier
+fn
+read_segments_with_identifier
+(
+	file_buffer:    &Vec<u8>,
+	segment_marker: u8,
+	identifier:     &[u8],
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	// Segments of interest are located before the image data
+	let segments = find_segments_with_identifier(file_buffer, segment_marker, Some(identifier), true)?;
+
+	return Ok(segments.iter()
+		.map(|segment| file_buffer[segment.data_start as usize + identifier.len()..segment.end() as usize].to_vec())
+		.collect()
+	);
+}
+
+/// Gets the width and height of the image as declared in the first start of
+/// frame segment (SOF0-SOF15, except for DHT, JPG and DAC which share this
+/// marker range). 
+pub(crate) fn
+read_dimensions
+(
+	file_buffer: &[u8]
+)
+-> Result<(u32, u32), std::io::Error>
+{
+	if !file_buffer.starts_with(&JPG_SIGNATURE)
+	{
+		return little_exif_error!(InvalidSignature, "Can't open JPG file - Wrong signature!");
+	}
+
+	let mut position = JPG_SIGNATURE.len();
+
+	while position + 4 <= file_buffer.len()
+	{
+		if file_buffer[position] != JPG_MARKER_PREFIX
+		{
+			return io_error!(InvalidData, format!("Expected JPEG marker at position {}!", position));
+		}
+
+		let marker = file_buffer[position + 1];
+
+		// Skip fill bytes and standalone markers without a segment length
+		if marker == JPG_MARKER_PREFIX
+		{
+			position += 1;
+			continue;
+		}
+		if is_standalone_marker(marker)
+		{
+			position += 2;
+			continue;
+		}
+
+		// The image data follows, so there is no frame header to be found
+		if marker == 0xda || marker == 0xd9
+		{
+			break;
+		}
+
+		let length = from_u8_vec_macro!(u16, &file_buffer[position+2..position+4].to_vec(), &Endian::Big) as usize;
+
+		if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker)
+		{
+			// The frame header consists of the sample precision (1 byte), 
+			// followed by the height and width (2 bytes each)
+			if length < 7 || position + 9 > file_buffer.len()
+			{
+				return io_error!(InvalidData, "Invalid JPEG start of frame segment!");
+			}
+
+			let height = from_u8_vec_macro!(u16, &file_buffer[position+5..position+7].to_vec(), &Endian::Big);
+			let width  = from_u8_vec_macro!(u16, &file_buffer[position+7..position+9].to_vec(), &Endian::Big);
+
+			return Ok((width as u32, height as u32));
+		}
+
+		position += 2 + length;
+	}
+
+	return io_error!(InvalidData, "Could not find start of frame segment in JPEG data!");
+}
+
 pub(crate) fn
 file_read_metadata
 (
@@ -315,6 +601,187 @@ file_read_metadata
 	return generic_read_metadata(&mut buffered_file);
 }
 
+/// Checks if the given marker is a standalone marker, i.e. a marker that is
+/// not followed by a segment length (TEM, RST0-RST7 and SOI)
+fn
+is_standalone_marker
+(
+	marker: u8
+)
+-> bool
+{
+	return marker == 0x01 || marker == 0xd8 || (0xd0..=0xd7).contains(&marker);
+}
+
+/// A segment of the JPEG data as found by `JpgSegmentIterator`
+#[derive(Clone, Copy, Debug)]
+struct
+JpgSegment
+{
+	marker:      u8,
+	start:       u64,                                                           // Position of the marker prefix (0xFF)
+	data_start:  u64,                                                           // Position after the marker and the length field
+	data_length: usize,                                                         // Length of the data without the length field
+}
+
+impl
+JpgSegment
+{
+	/// The position directly after the segment
+	fn
+	end
+	(
+		&self
+	)
+	-> u64
+	{
+		return self.data_start + self.data_length as u64;
+	}
+}
+
+/// Iterates over the segments of the JPEG data the cursor is positioned in,
+/// starting at the current position (i.e. after the signature) and ending
+/// at the EOI marker. Any number of 0xFF fill bytes preceding a marker as 
+/// well as standalone markers (see `is_standalone_marker`) are skipped.
+/// When `next` returns a segment, the cursor is positioned at the start of
+/// its data, so that it can be read before continuing the iteration.
+struct
+JpgSegmentIterator
+<T: Seek + Read>
+{
+	cursor:        T,
+	next_position: u64,                                                         // Where to continue scanning for the next marker
+	after_sos:     bool,                                                        // Whether the previous segment was a SOS segment
+	finished:      bool,
+}
+
+impl
+<T: Seek + Read>
+JpgSegmentIterator<T>
+{
+	fn
+	new
+	(
+		mut cursor: T
+	)
+	-> Result<Self, std::io::Error>
+	{
+		let next_position = cursor.stream_position()?;
+		return Ok(JpgSegmentIterator { cursor, next_position, after_sos: false, finished: false });
+	}
+
+	/// Reads the next segment, or `None` if the EOI marker is reached
+	fn
+	read_next_segment
+	(
+		&mut self
+	)
+	-> Result<Option<JpgSegment>, std::io::Error>
+	{
+		self.cursor.seek(SeekFrom::Start(self.next_position))?;
+
+		if self.after_sos
+		{
+			// The start of scan (SOS) segment is followed by a blob of image
+			// data, the entropy-coded segment (ECS), which has no information
+			// regarding its length (as it may easily be bigger than the max
+			// segment length of 64kb), so it has to be scanned
+			skip_ecs(&mut self.cursor)?;
+		}
+
+		// Setup of variables necessary for going through the data
+		let mut byte_buffer = [0u8; 1];                                         // A buffer for reading in a byte of data from the file
+		let mut previous_byte_was_marker_prefix = false;                        // A boolean for remembering if the previous byte was a marker prefix (0xFF)
+
+		loop
+		{
+			// Read next byte into buffer
+			self.cursor.read_exact(&mut byte_buffer)?;
+
+			if !previous_byte_was_marker_prefix
+			{
+				previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+				continue;
+			}
+
+			let marker = byte_buffer[0];
+
+			// Any number of 0xFF fill bytes may precede a marker, so the
+			// marker prefix state is kept for the next byte
+			if marker == JPG_MARKER_PREFIX
+			{
+				continue;
+			}
+
+			// Standalone markers (TEM, RST0-RST7, SOI) are not followed by
+			// any length information, so there is nothing to skip
+			if is_standalone_marker(marker)
+			{
+				previous_byte_was_marker_prefix = false;
+				continue;
+			}
+
+			// Check if this is the end of the file. In that case, the length
+			// data can't be read
+			if marker == 0xd9                                                   // EOI marker
+			{
+				return Ok(None);
+			}
+
+			let start = self.cursor.stream_position()? - 2;
+
+			// Read in the length of the segment (which follows immediately 
+			// after the marker and includes the two length bytes themselves)
+			let mut length_buffer = [0u8; 2];
+			self.cursor.read_exact(&mut length_buffer)?;
+
+			let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+			if length < 2
+			{
+				return io_error!(InvalidData, format!("Invalid JPEG segment length {} for marker 0x{:02x}", length, marker));
+			}
+
+			let segment = JpgSegment {
+				marker,
+				start,
+				data_start:  start + 4,
+				data_length: (length - 2) as usize,
+			};
+
+			self.next_position = segment.end();
+			self.after_sos     = marker == 0xda;
+
+			return Ok(Some(segment));
+		}
+	}
+}
+
+impl
+<T: Seek + Read>
+Iterator
+for
+JpgSegmentIterator<T>
+{
+	type Item = Result<JpgSegment, std::io::Error>;
+
+	fn
+	next
+	(
+		&mut self
+	)
+	-> Option<Self::Item>
+	{
+		if self.finished
+		{
+			return None;
+		}
+
+		let result = self.read_next_segment();
+		self.finished = !matches!(result, Ok(Some(_)));
+		return result.transpose();
+	}
+}
+
 /// Skips the entropy-coded segment (ECS) that is followed by a start of scan
 /// segment (SOS) and positions the cursor at the start of the next segment,
 /// i.e. a 0xFF byte that is followed by a marker that is NOT 0xD0-0xD7 or 0x00.
@@ -377,74 +844,24 @@ generic_read_metadata
 )
 -> Result<Vec<u8>, std::io::Error>
 {
-	// Setup of variables necessary for going through the data
-	let mut byte_buffer = [0u8; 1];                                             // A buffer for reading in a byte of data from the file
-	let mut previous_byte_was_marker_prefix = false;                            // A boolean for remembering if the previous byte was a marker prefix (0xFF)
+	let mut segments = JpgSegmentIterator::new(cursor)?;
 
-	loop
+	while let Some(segment) = segments.next()
 	{
-		// Read next byte into buffer
-		cursor.read_exact(&mut byte_buffer)?;
+		let segment = segment?;
 
-		if previous_byte_was_marker_prefix
+		// APP1 segments are not only used for EXIF but e.g. also for XMP
+		// data, so check the identifier first
+		if segment.marker == 0xe1
+		&& segment_starts_with(&mut segments.cursor, segment.data_length, Some(&EXIF_HEADER))?
 		{
-			// Check if this is the end of the file. In that case, the length
-			// data can't be read and we need to return prematurely. 
-			// This is why this case can't be included in the match afterwards.
-			if byte_buffer[0] == 0xd9                                           // EOI marker
-			{
-				// No more data to read in
-				return io_error!(Other, "No EXIF data found!");
-			}
-
-			// Read in the length of the segment
-			// (which follows immediately after the marker)
-			let mut length_buffer = [0u8; 2];
-			cursor.read_exact(&mut length_buffer)?;
+			// Read in & return the data
+			let mut app1_buffer = vec![0u8; segment.data_length];
+			segments.cursor.read_exact(&mut app1_buffer)?;
 
-			// Decode the length to determine how much more data there is
-			let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
-			let remaining_length = (length - 2) as usize;
-
-			match byte_buffer[0]
-			{
-				0xe1 => {                                                       // APP1 marker
-					// Read in & return the remaining data
-					let mut app1_buffer = vec![0u8; remaining_length];
-					cursor.read_exact(&mut app1_buffer)?;
-
-					return Ok(app1_buffer);
-				},
-
-				0xda => {                                                       // SOS marker
-					// The start of scan (SOS) segment is followed by a blob of
-					// image data, the entropy-coded segment (ECS), which has no
-					// information regarding its length (as it may easily be 
-					// bigger than the max segment length of 64kb)
-
-					// So, we have to scan byte-for-byte at this point until
-					// a marker prefix comes up that is NOT
-					// - followed by a restart marker (D0 - D7) or 
-					// - a data FF (followed by 00)
-
-					// So, start by skipping the SOS segment
-					cursor.seek(SeekFrom::Current(remaining_length as i64))?;
-
-					// And skip the ECS
-					skip_ecs(cursor)?;
-				}
-
-				_ => {                                                          // Every other marker
-					// Skip this segment
-					cursor.seek(SeekFrom::Current(remaining_length as i64))?;
-				},
-			}
-
-			previous_byte_was_marker_prefix = false;
-		}
-		else
-		{
-			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+			return Ok(app1_buffer);
 		}
 	}
-}
\ No newline at end of file
+
+	return little_exif_error!(NoMetadata, "No EXIF data found!");
+}
@@ -11,6 +11,8 @@ use std::path::Path;
 
 use crate::endian::Endian;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
+use crate::metadata::removal_plan::RemovalPlan;
 use crate::u8conversion::*;
 use crate::general_file_io::*;
 use crate::util::insert_multiple_at;
@@ -93,7 +95,7 @@ check_signature
 {
 	if starts_with_jxl_signature(file_buffer)
 	{
-		return io_error!(Other, "Simple JXL codestream file - No metadata!");
+		return little_exif_error!(NoMetadata, "Simple JXL codestream file - No metadata!");
 	}
 
 	if !starts_with_iso_bmff_signature(file_buffer)
@@ -161,6 +163,44 @@ clear_metadata
 	}
 }
 
+/// Determines the EXIF boxes that `clear_metadata` would remove, without
+/// modifying the file
+pub(crate) fn
+plan_clear_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<RemovalPlan>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut plans    = Vec::new();
+	let mut position = 0;
+
+	while position < file_buffer.len()
+	{
+		if position + 8 > file_buffer.len()
+		{
+			return io_error!(UnexpectedEof, "JXL box header exceeds the end of the file!");
+		}
+
+		let length = from_u8_vec_macro!(u32, &file_buffer[position..position+4].to_vec(), &Endian::Big) as usize;
+		if length < 8
+		{
+			return io_error!(InvalidData, "Invalid JXL box length!");
+		}
+
+		if file_buffer[position+4..position+8] == EXIF
+		{
+			plans.push(RemovalPlan::new("Exif", position..position+length));
+		}
+
+		position += length;
+	}
+
+	return Ok(plans);
+}
+
 pub(crate) fn
 file_clear_metadata
 (
@@ -377,7 +417,8 @@ pub(crate) fn
 write_metadata
 (
 	file_buffer: &mut Vec<u8>,
-	metadata:    &Metadata
+	metadata:    &Metadata,
+	warnings:    &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error> 
 {
@@ -403,7 +444,7 @@ write_metadata
 		*file_buffer = new_file_buffer;
 	}
 
-	let mut encoded_metadata = encode_metadata_jxl(&metadata.encode()?);
+	let mut encoded_metadata = encode_metadata_jxl(&metadata.encode_with_warnings(warnings)?);
 	let     insert_position  = find_insert_position(file_buffer)?;
 	insert_multiple_at(file_buffer, insert_position, &mut encoded_metadata);
 
@@ -414,7 +455,8 @@ pub(crate) fn
 file_write_metadata
 (
 	path:     &Path,
-	metadata: &Metadata
+	metadata: &Metadata,
+	warnings: &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
@@ -427,7 +469,7 @@ file_write_metadata
 	// Writes the metadata to the file_buffer vec
 	// The called function handles the removal of old metadata and the JPG
 	// specific encoding, so we pass only the generally encoded metadata here
-	write_metadata(&mut file_buffer, metadata)?;
+	write_metadata(&mut file_buffer, metadata, warnings)?;
 
 	// Seek back to start & write the file
 	perform_file_action!(file.seek(SeekFrom::Start(0)));
@@ -7,7 +7,12 @@
 //! - .jp(e)g 
 //! - .jxl
 //! - .webp
+//! - .tif(f)
+//! - .heif, .heic and .avif (only reading)
+//! - .psd and .psb (only reading)
 //! files and a few dozen tags in IFD0 and ExifIFD. 
+//! For .gif files, which can't store EXIF data, reading and writing XMP
+//! data is supported.
 //! 
 //! Interaction is done via the [`Metadata`](metadata/struct.Metadata.html) struct and the [`ExifTag`](exif_tag/enum.ExifTag.html) enum.
 //!
@@ -20,9 +25,25 @@
 //! let mut metadata = Metadata::new();
 //! metadata.set_tag(
 //!     ExifTag::ImageDescription("Hello World!".to_string())
-//! );
+//! ).unwrap();
 //! metadata.write_to_file(std::path::Path::new("image.png"));
 //! ```
+//!
+//! # Features
+//! - `async`: Adds `Metadata::new_from_path_async` and 
+//!   `Metadata::write_to_file_async` using `tokio::fs`.
+//! - `serde`: Implements `Serialize` and `Deserialize` for `Metadata` and the
+//!   types it consists of, e.g. for storing the metadata as JSON.
+//!
+//! little_exif is not a `no_std` crate: The in-memory decoding relies on
+//! `std::io::Cursor` and reports errors via `std::io::Error`.
+//!
+//! # Errors
+//! The functions of `Metadata` for reading, writing and clearing metadata
+//! return a [`LittleExifError`](error/enum.LittleExifError.html), which
+//! allows distinguishing e.g. a wrong signature from missing metadata. It
+//! converts from and into `std::io::Error`, which is still used by the
+//! remaining functions.
 
 #![forbid(unsafe_code)]
 #![crate_type = "lib"]
@@ -31,7 +52,10 @@
 mod general_file_io;
 pub mod ifd;
 
-mod png;
+mod gif;
+mod heif;
+pub mod png;
+mod psd;
 mod jpg;
 mod jxl;
 mod tiff;
@@ -39,9 +63,12 @@ mod webp;
 mod util;
 
 pub mod endian;
+pub mod error;
 pub mod rational;
 pub mod u8conversion;
 pub mod exif_tag;
 pub mod exif_tag_format;
+pub mod exif_datetime;
 pub mod filetype;
+pub mod limits;
 pub mod metadata;
\ No newline at end of file
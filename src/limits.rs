@@ -0,0 +1,54 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+use miniz_oxide::inflate::TINFLStatus;
+
+use crate::general_file_io::io_error;
+
+/// The default maximum size (64 MiB) of decompressed metadata, e.g. of the
+/// EXIF data stored in a zTXt chunk of a PNG image. This protects against
+/// "decompression bombs", i.e. tiny chunks inflating to gigabytes. For PNG
+/// images, the limit can be changed per read using
+/// `PngReadOptions::max_decompressed_size`.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Inflates the given zlib compressed data, but fails if the result would
+/// exceed the given maximum size. To be used for all compressed metadata.
+pub(crate) fn
+decompress_zlib
+(
+	data:     &[u8],
+	max_size: usize
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	match decompress_to_vec_zlib_with_limit(data, max_size)
+	{
+		Ok(decompressed_data) => Ok(decompressed_data),
+		Err(error) if error.status == TINFLStatus::HasMoreOutput
+			=> io_error!(InvalidData, format!("Decompressed data exceeds the limit of {} bytes!", max_size)),
+		Err(error)
+			=> io_error!(InvalidData, format!("Could not inflate compressed data: {}", error)),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use miniz_oxide::deflate::compress_to_vec_zlib;
+
+	#[test]
+	fn
+	decompression_bomb()
+	{
+		// 16 MiB of zeros compress to only a few KiB
+		let bomb = compress_to_vec_zlib(&vec![0u8; 16 * 1024 * 1024], 10);
+		assert!(bomb.len() < 64 * 1024);
+
+		let error = super::decompress_zlib(&bomb, 1024 * 1024).unwrap_err();
+		assert!(error.to_string().contains("1048576"));
+
+		assert_eq!(super::decompress_zlib(&bomb, 16 * 1024 * 1024).unwrap().len(), 16 * 1024 * 1024);
+	}
+}
@@ -0,0 +1,138 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+
+use super::Metadata;
+
+/// Allows constructing a `Metadata` struct from scratch by chaining calls.
+/// Each tag gets inserted into the IFD of its group that is associated with
+/// IFD0, analogous to `Metadata::set_tag`.
+///
+/// # Examples
+/// ```
+/// use little_exif::endian::Endian;
+/// use little_exif::exif_tag::ExifTag;
+/// use little_exif::metadata::builder::MetadataBuilder;
+///
+/// let metadata = MetadataBuilder::new()
+///     .endian(Endian::Big)
+///     .tag(ExifTag::Make("Canon".to_string()))
+///     .tag(ExifTag::ISO(vec![100]))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(metadata.get_endian(), Endian::Big);
+/// ```
+#[derive(Clone, Debug)]
+pub struct
+MetadataBuilder
+{
+	endian:             Endian,
+	tags:               Vec<ExifTag>,
+	replace_duplicates: bool,
+}
+
+impl
+Default
+for
+MetadataBuilder
+{
+	fn
+	default
+	()
+	-> Self
+	{
+		return MetadataBuilder {
+			endian:             Endian::Little,
+			tags:               Vec::new(),
+			replace_duplicates: false,
+		};
+	}
+}
+
+impl
+MetadataBuilder
+{
+	/// Constructs a new builder for a `Metadata` struct that uses little
+	/// endian notation and contains no tags.
+	pub fn
+	new
+	()
+	-> Self
+	{
+		return Self::default();
+	}
+
+	/// Sets the endianness that is used when encoding the metadata
+	pub fn
+	endian
+	(
+		mut self,
+		endian: Endian
+	)
+	-> Self
+	{
+		self.endian = endian;
+		return self;
+	}
+
+	/// Adds a tag to the metadata
+	pub fn
+	tag
+	(
+		mut self,
+		tag: ExifTag
+	)
+	-> Self
+	{
+		self.tags.push(tag);
+		return self;
+	}
+
+	/// Sets whether a tag that is added again to the same IFD replaces the
+	/// previous one. Otherwise (default), `build` returns an error.
+	pub fn
+	replace_duplicates
+	(
+		mut self,
+		replace_duplicates: bool
+	)
+	-> Self
+	{
+		self.replace_duplicates = replace_duplicates;
+		return self;
+	}
+
+	/// Constructs the `Metadata` struct. Returns an error if a tag has been
+	/// added more than once (unless `replace_duplicates` is set) or if the
+	/// format of a tag does not match its hex value (see `set_tag`).
+	pub fn
+	build
+	(
+		self
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(self.endian);
+
+		for tag in self.tags
+		{
+			if !self.replace_duplicates
+			&& metadata.get_tag_by_hex(tag.as_u16(), Some(tag.get_group())).next().is_some()
+			{
+				return io_error!(
+					AlreadyExists,
+					format!("Tag {:#06x} has been added more than once to the {:?} IFD", tag.as_u16(), tag.get_group())
+				);
+			}
+
+			metadata.set_tag(tag)?;
+		}
+
+		return Ok(metadata);
+	}
+}
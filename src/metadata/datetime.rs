@@ -0,0 +1,127 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_datetime::ExifDateTime;
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+impl
+Metadata
+{
+	/// Combines the given datetime tag with the associated sub second and
+	/// offset tags (if present) into an `ExifDateTime`
+	fn
+	get_datetime_generic
+	(
+		&self,
+		datetime_tag: ExifTag,
+		subsec_tag:   ExifTag,
+		offset_tag:   ExifTag,
+	)
+	-> Option<ExifDateTime>
+	{
+		let mut datetime = ExifDateTime::from_exif_string(
+			&self.get_tag_value_string(&datetime_tag)?
+		)?;
+
+		datetime.subsec_nanos = self.get_tag_value_string(&subsec_tag)
+			.and_then(|subsec| ExifDateTime::parse_subsec(&subsec));
+
+		datetime.offset_minutes = self.get_tag_value_string(&offset_tag)
+			.and_then(|offset| ExifDateTime::parse_offset(&offset));
+
+		return Some(datetime);
+	}
+
+	/// Gets the date and time of when the image file was last changed, using
+	/// the `ModifyDate` (DateTime), `SubSecTime` and `OffsetTime` tags.
+	/// Returns `None` if the tag is absent or malformed.
+	pub fn
+	get_datetime
+	(
+		&self
+	)
+	-> Option<ExifDateTime>
+	{
+		return self.get_datetime_generic(
+			ExifTag::ModifyDate(String::new()),
+			ExifTag::SubSecTime(String::new()),
+			ExifTag::OffsetTime(String::new()),
+		);
+	}
+
+	/// Gets the date and time of when the original image was taken, using
+	/// the `DateTimeOriginal`, `SubSecTimeOriginal` and `OffsetTimeOriginal`
+	/// tags. Returns `None` if the tag is absent or malformed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(datetime) = metadata.get_datetime_original()
+	/// {
+	///     println!("Taken in {}", datetime.year);
+	/// }
+	/// ```
+	pub fn
+	get_datetime_original
+	(
+		&self
+	)
+	-> Option<ExifDateTime>
+	{
+		return self.get_datetime_generic(
+			ExifTag::DateTimeOriginal(String::new()),
+			ExifTag::SubSecTimeOriginal(String::new()),
+			ExifTag::OffsetTimeOriginal(String::new()),
+		);
+	}
+
+	/// Gets the local date and time of when the original image was taken
+	/// together with its offset to UTC in minutes (e.g. -330 for "-05:30"),
+	/// see `get_datetime_original`. The UTC time is the local time minus the
+	/// offset.
+	/// Returns `None` if the `OffsetTimeOriginal` tag is absent or malformed,
+	/// instead of assuming that the local time is UTC.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((datetime, offset_minutes)) = metadata.get_datetime_original_utc()
+	/// {
+	///     println!("Taken at {}:{} (UTC offset: {} minutes)", datetime.hour, datetime.minute, offset_minutes);
+	/// }
+	/// ```
+	pub fn
+	get_datetime_original_utc
+	(
+		&self
+	)
+	-> Option<(ExifDateTime, i32)>
+	{
+		let datetime = self.get_datetime_original()?;
+		return Some((datetime, datetime.offset_minutes?));
+	}
+
+	/// Gets the date and time of when the image was stored as digital data,
+	/// using the `CreateDate` (DateTimeDigitized), `SubSecTimeDigitized` and
+	/// `OffsetTimeDigitized` tags. Returns `None` if the tag is absent or
+	/// malformed.
+	pub fn
+	get_datetime_digitized
+	(
+		&self
+	)
+	-> Option<ExifDateTime>
+	{
+		return self.get_datetime_generic(
+			ExifTag::CreateDate(String::new()),
+			ExifTag::SubSecTimeDigitized(String::new()),
+			ExifTag::OffsetTimeDigitized(String::new()),
+		);
+	}
+}
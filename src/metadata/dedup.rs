@@ -0,0 +1,55 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use super::Metadata;
+
+/// Determines which tag `Metadata::dedup_tags` keeps if an IFD contains 
+/// multiple tags with the same hex value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+DedupStrategy
+{
+	/// Keep the tag that comes first in the IFD
+	KeepFirst,
+
+	/// Keep the tag that comes last in the IFD
+	KeepLast,
+
+	/// Keep the tag with the largest number of components (e.g. the longest
+	/// string), or the first one of those in case of a tie
+	KeepLargestCount,
+}
+
+impl
+Metadata
+{
+	/// Removes duplicate tags, i.e. tags with the same hex value within the
+	/// same IFD, which some files (illegally) contain. 
+	/// When decoding, little_exif keeps all of these tags in the order they
+	/// are stored in the file, so that e.g. `get_tag` yields all of them and
+	/// `encode` writes all of them. This function reduces them to a single
+	/// tag according to the given strategy, keeping its position.
+	/// Returns the number of tags that were removed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::dedup::DedupStrategy;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let removed = metadata.dedup_tags(DedupStrategy::KeepFirst);
+	/// println!("Removed {} duplicate tags", removed);
+	/// ```
+	pub fn
+	dedup_tags
+	(
+		&mut self,
+		strategy: DedupStrategy
+	)
+	-> usize
+	{
+		return self.image_file_directories.iter_mut()
+			.map(|ifd| ifd.dedup_tags(strategy))
+			.sum();
+	}
+}
@@ -0,0 +1,212 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+
+use crate::exif_tag::ExifTag;
+use crate::exif_tag::TagType;
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+
+/// A single difference between two `Metadata` structs, see `Metadata::diff`.
+/// IFDs are identified by their group and generic IFD number.
+#[derive(Clone, Debug, PartialEq)]
+pub enum
+TagDiff
+{
+	/// The tag only exists in the other struct
+	Added
+	{
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32,
+		tag:            ExifTag,
+	},
+
+	/// The tag only exists in this struct
+	Removed
+	{
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32,
+		tag:            ExifTag,
+	},
+
+	/// The tag exists in the same IFD of both structs, but with different 
+	/// values
+	Changed
+	{
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32,
+		old:            ExifTag,
+		new:            ExifTag,
+	},
+
+	/// The tag exists with the same value in both structs, but in different
+	/// IFDs
+	Moved
+	{
+		from:           (ExifTagGroup, u32),
+		to:             (ExifTagGroup, u32),
+		tag:            ExifTag,
+	},
+}
+
+impl
+TagDiff
+{
+	/// Gets the IFD the difference is sorted by, i.e. the IFD the tag is
+	/// located in after the change
+	fn
+	ifd_key
+	(
+		&self
+	)
+	-> (u32, ExifTagGroup)
+	{
+		match self
+		{
+			TagDiff::Added   { group, generic_ifd_nr, .. } |
+			TagDiff::Removed { group, generic_ifd_nr, .. } |
+			TagDiff::Changed { group, generic_ifd_nr, .. } => (*generic_ifd_nr, *group),
+			TagDiff::Moved   { to, .. }                    => (to.1, to.0),
+		}
+	}
+}
+
+/// The differences between two `Metadata` structs, grouped by IFD
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct
+MetadataDiff
+{
+	pub changes: Vec<TagDiff>,
+}
+
+impl
+MetadataDiff
+{
+	/// Checks if there are no differences at all
+	pub fn
+	is_empty
+	(
+		&self
+	)
+	-> bool
+	{
+		return self.changes.is_empty();
+	}
+}
+
+impl
+fmt::Display
+for
+MetadataDiff
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		for change in &self.changes
+		{
+			match change
+			{
+				TagDiff::Added { group, generic_ifd_nr, tag }
+					=> writeln!(f, "+ {} {}: {:?}", group, generic_ifd_nr, tag)?,
+				TagDiff::Removed { group, generic_ifd_nr, tag }
+					=> writeln!(f, "- {} {}: {:?}", group, generic_ifd_nr, tag)?,
+				TagDiff::Changed { group, generic_ifd_nr, old, new }
+					=> writeln!(f, "~ {} {}: {:?} -> {:?}", group, generic_ifd_nr, old, new)?,
+				TagDiff::Moved { from, to, tag }
+					=> writeln!(f, "> {} {} -> {} {}: {:?}", from.0, from.1, to.0, to.1, tag)?,
+			}
+		}
+		return Ok(());
+	}
+}
+
+impl
+Metadata
+{
+	/// Determines the differences between this and another `Metadata` struct,
+	/// i.e. what changed when going from this struct to the other one. 
+	/// Tags are matched by their hex value within the same IFD, so their
+	/// order does not matter. A tag that got removed from one IFD and added
+	/// to another with the same value is reported as moved.
+	/// Like for `merge`, offset tags are not considered.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let original = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let mut edited = original.clone();
+	/// edited.set_tag(ExifTag::ImageDescription("Hello World!".to_string())).unwrap();
+	///
+	/// print!("{}", original.diff(&edited));
+	/// ```
+	pub fn
+	diff
+	(
+		&self,
+		other: &Metadata
+	)
+	-> MetadataDiff
+	{
+		// Collect the tags of both structs along with their IFD
+		let collect_tags = |metadata: &Metadata| metadata.get_all_tags()
+			.filter(|(_, tag)| !matches!(tag.get_tag_type(), TagType::IFD_OFFSET(_)))
+			.map(|(ifd, tag)| ((ifd.get_ifd_type(), ifd.get_generic_ifd_nr()), tag.clone()))
+			.collect::<Vec<_>>();
+
+		let mut removed = collect_tags(self);
+		let mut added   = collect_tags(other);
+		let mut changes = Vec::new();
+
+		// Tags with the same hex value in the same IFD are either unchanged
+		// or changed
+		let mut index = 0;
+		while index < removed.len()
+		{
+			let (ifd, tag) = &removed[index];
+			if let Some(other_index) = added.iter().position(|(other_ifd, other_tag)| 
+				other_ifd == ifd && other_tag.as_u16() == tag.as_u16()
+			)
+			{
+				let (ifd, old) = removed.remove(index);
+				let (_,   new) = added.remove(other_index);
+				if old != new
+				{
+					changes.push(TagDiff::Changed { group: ifd.0, generic_ifd_nr: ifd.1, old, new });
+				}
+				continue;
+			}
+			index += 1;
+		}
+
+		// Tags with the same value in different IFDs got moved
+		let mut index = 0;
+		while index < removed.len()
+		{
+			if let Some(other_index) = added.iter().position(|(_, other_tag)| *other_tag == removed[index].1)
+			{
+				let (from, tag) = removed.remove(index);
+				let (to,   _  ) = added.remove(other_index);
+				changes.push(TagDiff::Moved { from, to, tag });
+				continue;
+			}
+			index += 1;
+		}
+
+		changes.extend(removed.into_iter().map(|(ifd, tag)| TagDiff::Removed { group: ifd.0, generic_ifd_nr: ifd.1, tag }));
+		changes.extend(added.into_iter().map(  |(ifd, tag)| TagDiff::Added {   group: ifd.0, generic_ifd_nr: ifd.1, tag }));
+
+		// Group the changes by IFD (stable, so the order within an IFD stays)
+		changes.sort_by(|a, b| a.ifd_key().partial_cmp(&b.ifd_key()).unwrap());
+
+		return MetadataDiff { changes };
+	}
+}
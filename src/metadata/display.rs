@@ -0,0 +1,178 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::exif_tag_format::*;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+use crate::u8conversion::U8conversion;
+
+use super::Metadata;
+
+// Binary values longer than this are not printed byte by byte
+const MAX_PRINTED_BINARY_LENGTH: usize = 64;
+
+/// Formats the bytes as space separated hex values, e.g. "01 ab ff"
+fn
+format_hex
+(
+	bytes: &[u8]
+)
+-> String
+{
+	return bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<String>>().join(" ");
+}
+
+/// Joins the values using spaces, like exiftool does for multiple values
+fn
+join_values<T: ToString>
+(
+	values: &[T]
+)
+-> String
+{
+	return values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(" ");
+}
+
+/// Formats the value of the tag in a human readable way. Rationals are
+/// printed as fractions and the image data is only summarized.
+fn
+format_tag_value
+(
+	tag: &ExifTag
+)
+-> String
+{
+	match tag
+	{
+		ExifTag::StripOffsets(offsets, strip_data) if strip_data.is_empty()
+			=> return join_values(offsets),
+		ExifTag::StripOffsets(_, strip_data)
+			=> return format!(
+				"(Binary data {} bytes in {} strips)",
+				strip_data.iter().map(Vec::len).sum::<usize>(),
+				strip_data.len()
+			),
+		ExifTag::ThumbnailOffset(_, thumbnail_data)
+			=> return format!("(Binary data {} bytes)", thumbnail_data.len()),
+		_
+			=> (),
+	}
+
+	// Going through the bytes avoids having to list all tags
+	let bytes = tag.value_as_u8_vec(&Endian::Little);
+
+	// Unknown and UNDEF tags may contain large binary data (e.g. XMP or ICC
+	// profiles), which is only summarized
+	if (tag.is_unknown() || tag.format() == ExifTagFormat::UNDEF) && bytes.len() > MAX_PRINTED_BINARY_LENGTH
+	{
+		return format!("(Binary data {} bytes)", bytes.len());
+	}
+
+	if tag.is_unknown()
+	{
+		return format_hex(&bytes);
+	}
+
+	match tag.format()
+	{
+		ExifTagFormat::STRING      => String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string(),
+		ExifTagFormat::INT8U       => join_values(&<INT8U  as U8conversion<INT8U >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT16U      => join_values(&<INT16U as U8conversion<INT16U>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT32U      => join_values(&<INT32U as U8conversion<INT32U>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT8S       => join_values(&<INT8S  as U8conversion<INT8S >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT16S      => join_values(&<INT16S as U8conversion<INT16S>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::INT32S      => join_values(&<INT32S as U8conversion<INT32S>>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::FLOAT       => join_values(&<FLOAT  as U8conversion<FLOAT >>::from_u8_vec(&bytes, &Endian::Little)),
+		ExifTagFormat::DOUBLE      => join_values(&<DOUBLE as U8conversion<DOUBLE>>::from_u8_vec(&bytes, &Endian::Little)),
+
+		ExifTagFormat::RATIONAL64U => <RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(&bytes, &Endian::Little)
+			.iter()
+			.map(|rational| format!("{}/{}", rational.nominator, rational.denominator))
+			.collect::<Vec<String>>()
+			.join(" "),
+
+		ExifTagFormat::RATIONAL64S => <RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(&bytes, &Endian::Little)
+			.iter()
+			.map(|rational| format!("{}/{}", rational.nominator, rational.denominator))
+			.collect::<Vec<String>>()
+			.join(" "),
+
+		// Some UNDEF tags (e.g. ExifVersion) contain ASCII characters
+		ExifTagFormat::UNDEF if !bytes.is_empty() && bytes.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+			=> String::from_utf8_lossy(&bytes).to_string(),
+		ExifTagFormat::UNDEF
+			=> format_hex(&bytes),
+	}
+}
+
+/// Writes the tags of the IFD, one `Name: value` line per tag. Unknown tags
+/// are named by their hex value.
+fn
+write_ifd_tags
+(
+	f:   &mut fmt::Formatter,
+	ifd: &ImageFileDirectory
+)
+-> fmt::Result
+{
+	for tag in ifd.get_tags()
+	{
+		let name = match tag.is_unknown()
+		{
+			true  => format!("{:#06x}", tag.as_u16()),
+			false => tag.name().to_string(),
+		};
+		writeln!(f, "{:<32}: {}", name, format_tag_value(tag))?;
+	}
+	return Ok(());
+}
+
+/// Prints the metadata similar to exiftool: Each IFD starts with a header
+/// line (e.g. "---- ExifIFD ----"), followed by one `Name: value` line per
+/// tag. For IFDs not associated with IFD0, the generic IFD number is added
+/// to the header.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::metadata::Metadata;
+///
+/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+/// print!("{}", metadata);
+/// ```
+impl
+fmt::Display
+for
+Metadata
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		for ifd in self.get_ifds()
+		{
+			let generic_ifd_nr = ifd.get_generic_ifd_nr();
+			match (ifd.get_ifd_type(), generic_ifd_nr)
+			{
+				(ExifTagGroup::GENERIC, _) => writeln!(f, "---- IFD{} ----", generic_ifd_nr)?,
+				(group,                 0) => writeln!(f, "---- {} ----", group)?,
+				(group,                 _) => writeln!(f, "---- {} (IFD{}) ----", group, generic_ifd_nr)?,
+			}
+			write_ifd_tags(f, ifd)?;
+
+			for (index, sub_ifd) in ifd.get_sub_ifds().iter().enumerate()
+			{
+				writeln!(f, "---- SubIFD{} (IFD{}) ----", index, generic_ifd_nr)?;
+				write_ifd_tags(f, sub_ifd)?;
+			}
+		}
+		return Ok(());
+	}
+}
@@ -3,12 +3,178 @@
 
 use crate::exif_tag::ExifTag;
 use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
 
 use super::Metadata;
 
 impl
 Metadata
 {
+	/// Removes the given tag from the metadata struct. Analogous to `set_tag`,
+	/// the tag gets removed from the IFD of the tag's group that is associated
+	/// with IFD0. See `remove_tag_by_hex` for further details.
+	pub fn
+	remove_tag
+	(
+		&mut self,
+		tag:            &ExifTag,
+		keep_empty_ifd: bool
+	)
+	-> Option<ExifTag>
+	{
+		return self.remove_tag_by_hex(tag.as_u16(), tag.get_group(), 0, keep_empty_ifd);
+	}
+
+	/// Removes the tag with the given hex value from the IFD of the specified
+	/// group that is associated with the specified generic IFD number and 
+	/// returns the removed tag. If no such tag exists, `None` is returned.
+	/// If the IFD is empty afterwards, it gets removed as well, unless 
+	/// `keep_empty_ifd` is set (e.g. because new tags are about to be added)
+	/// or another IFD (e.g. the Interop IFD for the ExifIFD) depends on it.
+	pub fn
+	remove_tag_by_hex
+	(
+		&mut self,
+		hex:            u16,
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32,
+		keep_empty_ifd: bool
+	)
+	-> Option<ExifTag>
+	{
+		let ifd_index = self.image_file_directories.iter().position(|ifd| 
+			ifd.get_generic_ifd_nr() == generic_ifd_nr &&
+			ifd.get_ifd_type()       == group
+		)?;
+
+		let ifd       = &mut self.image_file_directories[ifd_index];
+		let removed   = ifd.get_tags().iter().find(|tag| tag.as_u16() == hex)?.clone();
+		ifd.remove_tag(removed.clone());
+
+		if keep_empty_ifd || !ifd.get_tags().is_empty()
+		{
+			return Some(removed);
+		}
+
+		// Check that no other IFD requires this one as its parent
+		let has_child_ifd = self.image_file_directories.iter().any(|other_ifd|
+			other_ifd.get_generic_ifd_nr() == generic_ifd_nr &&
+			matches!(
+				other_ifd.get_offset_tag_for_parent_ifd(), 
+				Some((parent_group, _)) if parent_group == group
+			)
+		);
+
+		if !has_child_ifd
+		{
+			self.image_file_directories.remove(ifd_index);
+		}
+
+		return Some(removed);
+	}
+
+	/// Removes all IFDs and thereby all tags from the metadata struct, e.g. 
+	/// for selectively adding tags again afterwards. Returns the number of 
+	/// tags that were removed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let removed = metadata.clear_all();
+	/// println!("Removed {} tags", removed);
+	/// ```
+	pub fn
+	clear_all
+	(
+		&mut self
+	)
+	-> usize
+	{
+		let removed = self.image_file_directories.iter().map(|ifd| ifd.get_tags().len()).sum();
+		self.image_file_directories.clear();
+		return removed;
+	}
+
+	/// Removes all IFDs of the given group (e.g. `GPS` for privacy reasons)
+	/// from the metadata struct. IFDs that depend on a removed IFD (e.g. the
+	/// Interop IFD for the ExifIFD) are removed as well, as are manually set
+	/// offset tags (e.g. `GPSInfo`) pointing to the removed IFDs.
+	/// Returns the number of tags that were removed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// 
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let removed = metadata.clear_group(ExifTagGroup::GPS);
+	/// println!("Removed {} GPS related tags", removed);
+	/// ```
+	pub fn
+	clear_group
+	(
+		&mut self,
+		group: ExifTagGroup
+	)
+	-> usize
+	{
+		// Determine the groups to remove, including the ones depending on 
+		// the given group
+		let mut groups_to_remove = vec![group];
+		loop
+		{
+			let dependent_group = self.image_file_directories.iter().find(|ifd|
+				!groups_to_remove.contains(&ifd.get_ifd_type()) &&
+				matches!(
+					ifd.get_offset_tag_for_parent_ifd(),
+					Some((parent_group, _)) if groups_to_remove.contains(&parent_group)
+				)
+			);
+
+			match dependent_group
+			{
+				Some(ifd) => groups_to_remove.push(ifd.get_ifd_type()),
+				None      => break,
+			}
+		}
+
+		let mut removed = 0;
+
+		self.image_file_directories.retain(|ifd|
+		{
+			if groups_to_remove.contains(&ifd.get_ifd_type())
+			{
+				removed += ifd.get_tags().len();
+				return false;
+			}
+			return true;
+		});
+
+		// Remove any offset tags pointing to the removed IFDs as encoding
+		// these would fail otherwise
+		for ifd in self.image_file_directories.iter_mut()
+		{
+			let offset_tags: Vec<ExifTag> = ifd.get_tags().iter()
+				.filter(|tag| matches!(
+					ImageFileDirectory::get_ifd_type_for_offset_tag(tag),
+					Some(offset_group) if groups_to_remove.contains(&offset_group)
+				))
+				.cloned()
+				.collect();
+
+			removed += offset_tags.len();
+
+			for tag in offset_tags
+			{
+				ifd.remove_tag(tag);
+			}
+		}
+
+		return removed;
+	}
+
 	/// Reduces the `Metadata` struct to the absolute minimum required for 
 	/// TIFF compliance without losing important data (see table in exif_tag.rs,
 	/// strip and thumbnail data) which is all assumed to be in GENERIC IFDs.
@@ -31,6 +31,38 @@ Metadata
 		self.endian.clone()
 	}
 
+	/// Gets the TIFF/EXIF data (starting with the byte order marker, without
+	/// the "Exif\0\0" header) as it was extracted from the file when the 
+	/// struct got created, e.g. for handing it to another library verbatim. 
+	/// This is the data *before* any edits: Changes made to the tags are not 
+	/// reflected here, use `encode` for that. As the offsets of e.g. the 
+	/// MakerNote and the thumbnail refer to this data, it also allows doing
+	/// custom parsing. 
+	/// Note that for TIFF files, this is the entire file (BigTIFF files are
+	/// converted to regular TIFF data first).
+	/// Returns `None` if the struct was not read from a file or the metadata
+	/// could not be decoded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(raw_exif) = metadata.raw_exif()
+	/// {
+	///     std::fs::write("exif.tif", raw_exif).unwrap();
+	/// }
+	/// ```
+	pub fn
+	raw_exif
+	(
+		&self
+	)
+	-> Option<&[u8]>
+	{
+		return self.raw_exif.as_deref();
+	}
+
 	/// Gets the image file directories stored in the struct
 	pub fn
 	get_ifds
@@ -42,6 +74,32 @@ Metadata
 		&self.image_file_directories
 	}
 
+	/// Gets a compact overview of the image file directories stored in the
+	/// struct, consisting of the group, the generic IFD number and the number
+	/// of tags of each IFD. 
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let has_gps = metadata.ifd_summary().iter().any(|(group, _, _)| *group == ExifTagGroup::GPS);
+	/// ```
+	pub fn
+	ifd_summary
+	(
+		&self
+	)
+	-> Vec<(ExifTagGroup, u32, usize)>
+	{
+		self.image_file_directories.iter().map(|ifd| (
+			ifd.get_ifd_type(),
+			ifd.get_generic_ifd_nr(),
+			ifd.get_tags().len()
+		)).collect()
+	}
+
 	/// Gets an image file directory that is of a specific group an is
 	/// associated with a certain generic IFD number
 	pub fn 
@@ -60,6 +118,7 @@ Metadata
 	}
 
 	/// Gets the maximum generic ifd number that any of the struct's IFDs has
+	/// or 0 if there are no generic IFDs at all
 	pub fn
 	get_max_generic_ifd_number
 	(
@@ -69,9 +128,9 @@ Metadata
 	{
 		self.image_file_directories.iter()
 			.filter(|ifd| ifd.get_ifd_type() == ExifTagGroup::GENERIC)
-			.max_by(|ifd1, ifd2| ifd1.get_generic_ifd_nr().cmp(&ifd2.get_generic_ifd_nr()))
-			.unwrap()
-			.get_generic_ifd_nr()
+			.map(|ifd| ifd.get_generic_ifd_nr())
+			.max()
+			.unwrap_or(0)
 	}
 
 	/// Gets an image file directory that is of a specific group an is
@@ -131,6 +190,17 @@ impl Metadata
 	/// Note: While it is not necessary to provide the group, it may be needed
 	/// in some cases as there are tags that have the same tag number, e.g. 
 	/// the `InteroperabilityVersion` and the `GPSLatitude` tags.
+	/// If the group is `None`, tags from all groups with this hex number are
+	/// returned, otherwise the search is restricted to IFDs of this group.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let gps_latitude = metadata.get_tag_by_hex(0x0002, Some(ExifTagGroup::GPS)).next();
+	/// ```
 	pub fn
 	get_tag_by_hex
 	(
@@ -149,6 +219,131 @@ impl Metadata
 			group:             group,
 		}
 	}
+
+	/// Gets an iterator over all tags in all IFDs of the struct. Each tag is
+	/// accompanied by the IFD it is stored in.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// for (ifd, tag) in metadata.get_all_tags()
+	/// {
+	///     println!("{:?} {}: {:?}", ifd.get_ifd_type(), ifd.get_generic_ifd_nr(), tag);
+	/// }
+	/// ```
+	pub fn
+	get_all_tags
+	(
+		&self
+	)
+	-> impl Iterator<Item = (&ImageFileDirectory, &ExifTag)>
+	{
+		return self.image_file_directories.iter().flat_map(|ifd| 
+			ifd.get_tags().iter().map(move |tag| (ifd, tag))
+		);
+	}
+
+	/// Checks if the metadata contains the given tag (in any IFD of the tag's
+	/// group), regardless of its value. See `get_tag` for details.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	/// 
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::ISO(vec![100])).unwrap();
+	/// assert!( metadata.has_tag(&ExifTag::ISO(Vec::new())));
+	/// assert!(!metadata.has_tag(&ExifTag::Model(String::new())));
+	/// ```
+	pub fn
+	has_tag
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> bool
+	{
+		return self.get_tag(tag).next().is_some();
+	}
+
+	/// Checks if the metadata contains a tag with the given hex number in 
+	/// any group. See `get_tag_by_hex` for details.
+	pub fn
+	has_tag_by_hex
+	(
+		&self,
+		hex: u16
+	)
+	-> bool
+	{
+		return self.get_tag_by_hex(hex, None).next().is_some();
+	}
+
+	/// Checks if the metadata contains at least one tag in an IFD of the 
+	/// given group, e.g. for finding out if there is any GPS information.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// 
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::GPSAltitudeRef(vec![0])).unwrap();
+	/// assert!( metadata.has_group(ExifTagGroup::GPS));
+	/// assert!(!metadata.has_group(ExifTagGroup::INTEROP));
+	/// ```
+	pub fn
+	has_group
+	(
+		&self,
+		group: ExifTagGroup
+	)
+	-> bool
+	{
+		return self.image_file_directories.iter().any(|ifd| 
+			ifd.get_ifd_type() == group && !ifd.get_tags().is_empty()
+		);
+	}
+
+	/// Gets the value of a string tag (e.g. `ImageDescription` or `Make`) as
+	/// `String` without the NUL terminator. Returns `None` if the tag can't
+	/// be found or if its format is not `STRING`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// let description = metadata.get_tag_value_string(&ExifTag::ImageDescription(String::new()));
+	/// ```
+	pub fn
+	get_tag_value_string
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> Option<String>
+	{
+		let found_tag = self.get_tag(tag).next()?;
+
+		if !found_tag.is_string()
+		{
+			return None;
+		}
+
+		let mut value = found_tag.value_as_u8_vec(&self.endian);
+		while value.last() == Some(&0x00)
+		{
+			value.pop();
+		}
+
+		return Some(String::from_utf8_lossy(&value).to_string());
+	}
 }
 
 pub struct
@@ -0,0 +1,149 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::rational::uR64;
+
+use super::Metadata;
+
+// Seconds are stored with a precision of 1/10000 of an arc second
+const SECONDS_DENOMINATOR: u64 = 10000;
+
+/// Converts the degrees, minutes and seconds stored as rationals into decimal
+/// degrees. Some writers store less than three components (e.g. only degrees
+/// and decimal minutes), which is handled as well.
+fn
+dms_to_decimal
+(
+	dms: &Vec<uR64>
+)
+-> Option<f64>
+{
+	if dms.is_empty() || dms.len() > 3
+	{
+		return None;
+	}
+
+	let mut decimal = 0.0;
+	for (index, value) in dms.iter().enumerate()
+	{
+		if value.denominator == 0
+		{
+			return None;
+		}
+		decimal += value.nominator as f64 / value.denominator as f64 / 60f64.powi(index as i32);
+	}
+
+	return Some(decimal);
+}
+
+/// Converts decimal degrees into three rationals for degrees, minutes and
+/// seconds. Rounding is done on the seconds so that e.g. 59.99999 seconds
+/// properly carry over to the minutes instead of resulting in 60 seconds.
+fn
+decimal_to_dms
+(
+	decimal: f64
+)
+-> Vec<uR64>
+{
+	let total_seconds = (decimal.abs() * 3600.0 * SECONDS_DENOMINATOR as f64).round() as u64;
+
+	let degrees = total_seconds / (3600 * SECONDS_DENOMINATOR);
+	let minutes = total_seconds / (60   * SECONDS_DENOMINATOR) % 60;
+	let seconds = total_seconds % (60   * SECONDS_DENOMINATOR);
+
+	return vec![
+		uR64 { nominator: degrees as u32, denominator: 1                          },
+		uR64 { nominator: minutes as u32, denominator: 1                          },
+		uR64 { nominator: seconds as u32, denominator: SECONDS_DENOMINATOR as u32 },
+	];
+}
+
+impl
+Metadata
+{
+	/// Gets the GPS position as signed decimal latitude and longitude, e.g.
+	/// `(48.2082, 16.3738)`. South and West are represented by negative values
+	/// based on the `GPSLatitudeRef` and `GPSLongitudeRef` tags. If one of
+	/// these reference tags is missing, a positive value is assumed.
+	/// Returns `None` if latitude or longitude are missing or malformed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((latitude, longitude)) = metadata.get_gps_position()
+	/// {
+	///     println!("{} {}", latitude, longitude);
+	/// }
+	/// ```
+	pub fn
+	get_gps_position
+	(
+		&self
+	)
+	-> Option<(f64, f64)>
+	{
+		let latitude = match self.get_tag(&ExifTag::GPSLatitude(Vec::new())).next()?
+		{
+			ExifTag::GPSLatitude(dms) => dms_to_decimal(dms)?,
+			_                         => return None,
+		};
+
+		let longitude = match self.get_tag(&ExifTag::GPSLongitude(Vec::new())).next()?
+		{
+			ExifTag::GPSLongitude(dms) => dms_to_decimal(dms)?,
+			_                          => return None,
+		};
+
+		let latitude_ref  = self.get_tag_value_string(&ExifTag::GPSLatitudeRef( String::new()));
+		let longitude_ref = self.get_tag_value_string(&ExifTag::GPSLongitudeRef(String::new()));
+
+		let latitude_sign  = if latitude_ref.as_deref()  == Some("S") { -1.0 } else { 1.0 };
+		let longitude_sign = if longitude_ref.as_deref() == Some("W") { -1.0 } else { 1.0 };
+
+		// Adding 0.0 turns a possible negative zero into a positive zero
+		return Some((
+			latitude_sign  * latitude  + 0.0,
+			longitude_sign * longitude + 0.0
+		));
+	}
+
+	/// Sets the GPS position using signed decimal latitude and longitude.
+	/// This writes the `GPSLatitude`, `GPSLatitudeRef`, `GPSLongitude` and
+	/// `GPSLongitudeRef` tags into the GPS IFD associated with IFD0.
+	/// Negative values are written as South/West, zero (including negative
+	/// zero) as North/East. If no `GPSVersionID` is present yet, it gets set
+	/// to 2.3.0.0 as this tag is required by the specification.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_gps_position(48.2082, 16.3738);
+	/// ```
+	pub fn
+	set_gps_position
+	(
+		&mut self,
+		latitude:  f64,
+		longitude: f64
+	)
+	{
+		let latitude_ref  = if latitude  < 0.0 { "S" } else { "N" };
+		let longitude_ref = if longitude < 0.0 { "W" } else { "E" };
+
+		if self.get_tag(&ExifTag::GPSVersionID(Vec::new())).next().is_none()
+		{
+			self.set_tag_unchecked(ExifTag::GPSVersionID(vec![2, 3, 0, 0]));
+		}
+
+		self.set_tag_unchecked(ExifTag::GPSLatitudeRef( latitude_ref.to_string()));
+		self.set_tag_unchecked(ExifTag::GPSLatitude(    decimal_to_dms(latitude)));
+		self.set_tag_unchecked(ExifTag::GPSLongitudeRef(longitude_ref.to_string()));
+		self.set_tag_unchecked(ExifTag::GPSLongitude(   decimal_to_dms(longitude)));
+	}
+}
@@ -0,0 +1,172 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::exif_tag_format::ExifTagFormat;
+use crate::u8conversion::from_u8_vec_macro;
+use crate::u8conversion::to_u8_vec_macro;
+use crate::u8conversion::U8conversion;
+
+use super::Metadata;
+use super::warning::MetadataWarning;
+
+// Nikon type 3 MakerNotes contain their own TIFF header and all offsets are
+// relative to that header, so these can be moved around freely
+const NIKON_TYPE_3_HEADER: [u8; 7] = [0x4e, 0x69, 0x6b, 0x6f, 0x6e, 0x00, 0x02]; // "Nikon\0\x02"
+
+/// Determines what happens with the contents of the `MakerNote` tag when the
+/// metadata gets encoded. The MakerNote is a proprietary data block whose
+/// internal offsets are - depending on the camera vendor - relative to the
+/// start of the TIFF data. As the MakerNote usually ends up at a different
+/// position than in the original file, these offsets may become invalid.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum
+MakerNoteHandling
+{
+	/// Adjusts the internal offsets for recognized formats (Canon) to the new
+	/// position of the MakerNote. Position independent formats (Nikon type 3)
+	/// are kept as they are. Other formats are kept verbatim as well, but
+	/// `MetadataWarning::StaleMakerNoteOffsets` is reported as their offsets
+	/// may be stale.
+	#[default]
+	FixOffsets,
+
+	/// Keeps the bytes of the MakerNote verbatim as an opaque blob. If its
+	/// position changes, `MetadataWarning::StaleMakerNoteOffsets` is reported
+	/// as internal offsets may be stale.
+	Opaque,
+}
+
+/// Adjusts the offsets in a MakerNote that is structured like an IFD with
+/// offsets relative to the start of the TIFF data (e.g. Canon).
+/// Returns `None` if the data can't be interpreted as such an IFD.
+fn
+relocate_ifd_makernote
+(
+	makernote: &[u8],
+	delta:     i64,
+	endian:    &Endian
+)
+-> Option<Vec<u8>>
+{
+	let mut relocated = makernote.to_vec();
+
+	let number_of_entries = from_u8_vec_macro!(u16, &makernote.get(0..2)?.to_vec(), endian) as usize;
+
+	if makernote.len() < 2 + 12 * number_of_entries
+	{
+		return None;
+	}
+
+	for entry_start in (0..number_of_entries).map(|index| 2 + 12 * index)
+	{
+		let format = ExifTagFormat::from_u16(
+			from_u8_vec_macro!(u16, &makernote[entry_start+2..entry_start+4].to_vec(), endian)
+		)?;
+		let number_of_components = from_u8_vec_macro!(u32, &makernote[entry_start+4..entry_start+8].to_vec(), endian);
+
+		// Data that fits into the 4 bytes is stored directly in the entry
+		if format.bytes_per_component() as u64 * number_of_components as u64 <= 4
+		{
+			continue;
+		}
+
+		let offset     = from_u8_vec_macro!(u32, &makernote[entry_start+8..entry_start+12].to_vec(), endian);
+		let new_offset = u32::try_from(offset as i64 + delta).ok()?;
+
+		relocated[entry_start+8..entry_start+12].copy_from_slice(
+			&to_u8_vec_macro!(u32, &new_offset, endian)
+		);
+	}
+
+	return Some(relocated);
+}
+
+impl
+Metadata
+{
+	/// Sets how the contents of the `MakerNote` tag are handled when the
+	/// metadata gets encoded. See `MakerNoteHandling` for details.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::makernote::MakerNoteHandling;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_makernote_handling(MakerNoteHandling::Opaque);
+	/// ```
+	pub fn
+	set_makernote_handling
+	(
+		&mut self,
+		handling: MakerNoteHandling
+	)
+	{
+		self.makernote_handling = handling;
+	}
+
+	/// Gets how the contents of the `MakerNote` tag are handled when the
+	/// metadata gets encoded.
+	pub fn
+	get_makernote_handling
+	(
+		&self
+	)
+	-> MakerNoteHandling
+	{
+		self.makernote_handling
+	}
+
+	/// Prepares the MakerNote data for being written at the given offset
+	/// (relative to the start of the TIFF data) by adjusting its internal
+	/// offsets if necessary and possible.
+	pub(crate) fn
+	relocate_makernote
+	(
+		&self,
+		makernote:  Vec<u8>,
+		new_offset: u32,
+		warnings:   &mut Vec<MetadataWarning>
+	)
+	-> Vec<u8>
+	{
+		// Nothing to do if the MakerNote has not been decoded from a file
+		// (and was e.g. set by the user) or stays at the same position
+		let (old_offset, endian) = match (self.makernote_offset, &self.makernote_endian)
+		{
+			(Some(old_offset), Some(endian)) if old_offset != new_offset => (old_offset, endian),
+			_                                                            => return makernote,
+		};
+
+		if makernote.starts_with(&NIKON_TYPE_3_HEADER)
+		{
+			return makernote;
+		}
+
+		if self.makernote_handling == MakerNoteHandling::FixOffsets
+		{
+			let is_canon = match self.get_tag_value_string(&ExifTag::Make(String::new()))
+			{
+				Some(make) => make.starts_with("Canon"),
+				None       => false,
+			};
+
+			if is_canon
+			{
+				if let Some(relocated) = relocate_ifd_makernote(
+					&makernote,
+					new_offset as i64 - old_offset as i64,
+					endian
+				)
+				{
+					return relocated;
+				}
+			}
+		}
+
+		warnings.push(MetadataWarning::StaleMakerNoteOffsets { old_offset, new_offset });
+		return makernote;
+	}
+}
@@ -0,0 +1,140 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::TagType;
+use crate::general_file_io::io_error;
+use crate::ifd::SUBIFDS_HEX;
+
+use super::Metadata;
+
+/// Determines how `Metadata::merge` resolves a tag that exists in both
+/// structs (in the same IFD) with different values
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+MergeStrategy
+{
+	/// Keep the tag of the struct that gets merged into
+	PreferSelf,
+
+	/// Replace the tag with the one of the other struct
+	PreferOther,
+
+	/// Return an error without changing anything
+	Error,
+}
+
+impl
+Metadata
+{
+	/// Merges the tags of another `Metadata` struct into this one, IFD by IFD
+	/// (i.e. based on the group and generic IFD number). Tags that don't
+	/// exist yet are added, tags with the same hex value but a different
+	/// value are resolved according to the given strategy.
+	/// The SubIFDs of an IFD (e.g. the full resolution image of a DNG file)
+	/// are merged as a whole, as there is no way of telling which of them
+	/// correspond to each other: If both structs have different SubIFDs for
+	/// the same IFD, the strategy decides which ones are kept.
+	/// The endianness of this struct is kept. As tag values are not stored
+	/// as bytes, they are converted automatically when encoding, except for
+	/// the opaque contents of `UNDEF` tags (e.g. `MakerNote`).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::merge::MergeStrategy;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("template.jpg")).unwrap();
+	/// let gps_data     = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.merge(&gps_data, MergeStrategy::PreferOther).unwrap();
+	/// ```
+	pub fn
+	merge
+	(
+		&mut self,
+		other:    &Metadata,
+		strategy: MergeStrategy
+	)
+	-> Result<(), std::io::Error>
+	{
+		// Offset tags (including the SubIFDs tag) are computed during 
+		// encoding and thus not merged
+		let other_tags = other.get_all_tags().filter(|(_, tag)|
+			!matches!(tag.get_tag_type(), TagType::IFD_OFFSET(_)) && tag.as_u16() != SUBIFDS_HEX
+		);
+
+		// Collect the tags to set first, so that nothing is changed in case
+		// of an error
+		let mut tags_to_set = Vec::new();
+		for (other_ifd, other_tag) in other_tags
+		{
+			let existing_tag = self
+				.get_ifd(other_ifd.get_ifd_type(), other_ifd.get_generic_ifd_nr())
+				.and_then(|ifd| ifd.get_tags().iter().find(|tag| tag.as_u16() == other_tag.as_u16()));
+
+			match existing_tag
+			{
+				None                          => (),
+				Some(tag) if tag == other_tag => continue,
+				Some(_)                       => match strategy
+				{
+					MergeStrategy::PreferSelf  => continue,
+					MergeStrategy::PreferOther => (),
+					MergeStrategy::Error       => return io_error!(
+						AlreadyExists,
+						format!(
+							"Conflicting values for tag {:#06x} in {:?} IFD {}",
+							other_tag.as_u16(),
+							other_ifd.get_ifd_type(),
+							other_ifd.get_generic_ifd_nr()
+						)
+					),
+				},
+			}
+
+			tags_to_set.push((other_ifd.get_ifd_type(), other_ifd.get_generic_ifd_nr(), other_tag.clone()));
+		}
+
+		let mut sub_ifds_to_set = Vec::new();
+		for other_ifd in other.get_ifds().iter().filter(|ifd| !ifd.get_sub_ifds().is_empty())
+		{
+			let existing_sub_ifds = self
+				.get_ifd(other_ifd.get_ifd_type(), other_ifd.get_generic_ifd_nr())
+				.map(|ifd| ifd.get_sub_ifds())
+				.filter(|sub_ifds| !sub_ifds.is_empty());
+
+			match existing_sub_ifds
+			{
+				None                                                   => (),
+				Some(sub_ifds) if sub_ifds == other_ifd.get_sub_ifds() => continue,
+				Some(_)                                                => match strategy
+				{
+					MergeStrategy::PreferSelf  => continue,
+					MergeStrategy::PreferOther => (),
+					MergeStrategy::Error       => return io_error!(
+						AlreadyExists,
+						format!(
+							"Conflicting SubIFDs in {:?} IFD {}",
+							other_ifd.get_ifd_type(),
+							other_ifd.get_generic_ifd_nr()
+						)
+					),
+				},
+			}
+
+			sub_ifds_to_set.push((other_ifd.get_ifd_type(), other_ifd.get_generic_ifd_nr(), other_ifd.get_sub_ifds().clone()));
+		}
+
+		for (group, generic_ifd_nr, tag) in tags_to_set
+		{
+			self.get_ifd_mut(group, generic_ifd_nr).set_tag_unchecked(tag);
+		}
+
+		for (group, generic_ifd_nr, sub_ifds) in sub_ifds_to_set
+		{
+			self.get_ifd_mut(group, generic_ifd_nr).set_sub_ifds(sub_ifds);
+		}
+
+		return Ok(());
+	}
+}
+
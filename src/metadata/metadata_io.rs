@@ -1,26 +1,41 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use std::io::Read;
 use std::path::Path;
 
 use crate::filetype::get_file_type;
+use crate::error::LittleExifError;
+use crate::exif_tag::ExifTag;
 use crate::filetype::FileExtension;
-use crate::general_file_io::io_error;
+use crate::general_file_io::detect_file_type;
+use crate::general_file_io::open_read_file;
 
+use crate::gif;
+use crate::heif;
 use crate::jpg;
 use crate::jxl;
 use crate::png;
+use crate::psd;
 use crate::tiff;
 use crate::webp;
+use crate::png::PngReadOptions;
+use crate::png::PngWriteOptions;
 
 use super::Metadata;
+use super::removal_plan::RemovalPlan;
+use super::warning::MetadataWarning;
 
 impl
 Metadata
 {
 	/// Constructs a new `Metadata` object with the metadata from an image that is stored as a `Vec<u8>`
-	/// - If unable to handle the file vector (e.g. unsupported file type, etc.), this (currently) panics.
-	/// - If unable to decode the metadata, a new, empty object gets created and returned.
+	/// - If unable to handle the file vector (e.g. unsupported file type, 
+	///   wrong signature, mismatching checksum), an error is returned.
+	/// - If the image contains no metadata or it can't be decoded, a new, 
+	///   empty object gets created and returned.
+	/// - A raw EXIF block (e.g. the payload of a JPEG APP1 segment, with or
+	///   without the "Exif\0\0" header) can be passed as `FileExtension::TIFF`.
 	/// # Examples
 	/// ```no_run
 	/// use std::fs;
@@ -37,36 +52,123 @@ Metadata
 		file_buffer: &Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<Metadata, std::io::Error>
+	-> Result<Metadata, LittleExifError>
 	{
 		let raw_pre_decode_general = match file_type
 		{
+			FileExtension::HEIF
+				=>  heif::read_metadata(file_buffer),
 			FileExtension::JPEG 
 				=>  jpg::read_metadata(file_buffer),
 			FileExtension::JXL
 				=>  jxl::read_metadata(file_buffer),
 			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::read_metadata(file_buffer),
+				=>  png::vec::read_metadata(file_buffer, &PngReadOptions::default(), &mut Vec::new()),
+			FileExtension::PSD
+				=>  psd::read_metadata(file_buffer),
 			FileExtension::TIFF
 				=> tiff::vec::read_metadata(file_buffer),
 			FileExtension::WEBP
 				=> webp::vec::read_metadata(file_buffer),
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'new_from_vec' not yet implemented for {:?}", 
 						file_type
 					)
-				),
+				)),
 		};
 
-		return Self::general_decoding_wrapper(raw_pre_decode_general);
+		return Ok(Self::general_decoding_wrapper(raw_pre_decode_general)?);
+	}
+
+	/// Constructs a new `Metadata` object with the metadata from a PNG image
+	/// stored as a `Vec<u8>` using the given PNG specific options, e.g. for
+	/// also reading the "Raw profile type APP1" text chunk.
+	/// See `new_from_vec` for further details.
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::png::PngReadOptions;
+	/// 
+	/// let file_data = std::fs::read("image.png").unwrap();
+	/// let options   = PngReadOptions { match_raw_profile_app1: true, ..Default::default() };
+	/// let metadata  = Metadata::new_from_png_vec(&file_data, &options).unwrap();
+	/// ```
+	pub fn
+	new_from_png_vec
+	(
+		file_buffer: &Vec<u8>,
+		options:     &PngReadOptions
+	)
+	-> Result<Metadata, LittleExifError>
+	{
+		return Ok(Self::general_decoding_wrapper(png::vec::read_metadata(file_buffer, options, &mut Vec::new()))?);
+	}
+
+	/// Same as `new_from_png_vec`, but additionally returns the warnings that
+	/// were encountered while reading, e.g. a missing IEND chunk.
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::png::PngReadOptions;
+	/// 
+	/// let file_data = std::fs::read("image.png").unwrap();
+	/// let (metadata, warnings) = Metadata::new_from_png_vec_with_warnings(&file_data, &PngReadOptions::default()).unwrap();
+	/// for warning in warnings
+	/// {
+	///     println!("{}", warning);
+	/// }
+	/// ```
+	pub fn
+	new_from_png_vec_with_warnings
+	(
+		file_buffer: &Vec<u8>,
+		options:     &PngReadOptions
+	)
+	-> Result<(Metadata, Vec<MetadataWarning>), LittleExifError>
+	{
+		let mut warnings = Vec::new();
+		let raw_pre_decode_general = png::vec::read_metadata(file_buffer, options, &mut warnings);
+		let metadata = Self::general_decoding_wrapper_with_warnings(raw_pre_decode_general, &mut warnings)?;
+		return Ok((metadata, warnings));
+	}
+
+	/// Constructs a new `Metadata` object with the metadata from an image that
+	/// is stored as a `Vec<u8>`. In contrast to `new_from_vec`, the type of
+	/// the image gets determined by looking at its first bytes (e.g. the PNG
+	/// signature), so no file extension or other external information is 
+	/// required.
+	/// Returns an error if the type can't be determined or is not supported.
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let file_data = fs::read("image.jpg").unwrap();
+	/// let mut metadata: Metadata = Metadata::new_from_vec_autodetect(&file_data).unwrap();
+	/// ```
+	pub fn
+	new_from_vec_autodetect
+	(
+		file_buffer: &Vec<u8>
+	)
+	-> Result<Metadata, LittleExifError>
+	{
+		match detect_file_type(file_buffer)
+		{
+			Some(file_type) => Self::new_from_vec(file_buffer, file_type),
+			None            => Err(LittleExifError::UnsupportedFormat("Can't detect file type from the given data!".to_string())),
+		}
 	}
 
 	/// Constructs a new `Metadata` object with the metadata from the image at the specified path.
-	/// - If unable to read the file (e.g. does not exist, unsupported file type, etc.), this (currently) panics.
-	/// - If unable to decode the metadata, a new, empty object gets created and returned.
+	/// - If unable to read the file (e.g. does not exist, unsupported file 
+	///   type, wrong signature, etc.), an error is returned.
+	/// - If the file contains no metadata or it can't be decoded, a new, 
+	///   empty object gets created and returned.
+	/// - If the file extension is missing or unknown, the file type gets 
+	///   determined by looking at the first bytes of the file.
 	///
 	/// # Examples
 	/// ```no_run
@@ -74,43 +176,156 @@ Metadata
 	/// 
 	/// let mut metadata: Metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
 	/// ```
-	#[allow(unreachable_patterns)]
 	pub fn
 	new_from_path
 	(
 		path: &Path
 	)
-	-> Result<Metadata, std::io::Error>
+	-> Result<Metadata, LittleExifError>
 	{
-		let file_type = get_file_type(path)?;
+		return Self::generic_new_from_path(path, None);
+	}
+
+	/// Same as `new_from_path`, but additionally returns the recoverable 
+	/// issues that were encountered while reading, like unknown tags, tags
+	/// with unexpected formats, IFDs that had to be skipped or (for PNG)
+	/// chunks with mismatching CRCs. In contrast to `new_from_path`, PNG files
+	/// with mismatching CRCs are read nonetheless.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let (metadata, warnings) = Metadata::new_from_path_with_warnings(std::path::Path::new("image.png")).unwrap();
+	/// for warning in warnings
+	/// {
+	///     println!("{}", warning);
+	/// }
+	/// ```
+	pub fn
+	new_from_path_with_warnings
+	(
+		path: &Path
+	)
+	-> Result<(Metadata, Vec<MetadataWarning>), LittleExifError>
+	{
+		let mut warnings = Vec::new();
+		let metadata = Self::generic_new_from_path(path, Some(&mut warnings))?;
+		return Ok((metadata, warnings));
+	}
+
+	#[allow(unreachable_patterns)]
+	fn
+	generic_new_from_path
+	(
+		path:     &Path,
+		warnings: Option<&mut Vec<MetadataWarning>>
+	)
+	-> Result<Metadata, LittleExifError>
+	{
+		let file_type = Self::get_file_type_or_detect(path)?;
+
+		// PNG chunks with mismatching CRCs are only tolerated if the caller
+		// is interested in warnings
+		let mut png_warnings = Vec::new();
 
 		// Call the file specific decoders as a starting point for obtaining
 		// the raw EXIF data that gets further processed
 		let raw_pre_decode_general = match file_type
 		{
+			FileExtension::HEIF
+				=>  heif::file_read_metadata(&path),
 			FileExtension::JPEG 
 				=>  jpg::file_read_metadata(&path),
 			FileExtension::JXL
 				=>  jxl::file_read_metadata(&path),
 			FileExtension::PNG { as_zTXt_chunk: _ } 
-				=>  png::file::read_metadata(&path),
+				=>  if warnings.is_some()
+				{
+					png::file::read_metadata_lenient(&path, &mut png_warnings)
+				}
+				else
+				{
+					png::file::read_metadata(&path)
+				},
+			FileExtension::PSD
+				=>  psd::file_read_metadata(&path),
 			FileExtension::TIFF
 				=> tiff::file::read_metadata(&path),
 			FileExtension::WEBP 
 				=> webp::file::read_metadata(&path),
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'new_from_path' not yet implemented for {:?}", 
 						file_type
 					)
-				),
+				)),
 		};
 
-		return Self::general_decoding_wrapper(raw_pre_decode_general);
+		match warnings
+		{
+			Some(warnings) => {
+				warnings.extend(png_warnings);
+				return Ok(Self::general_decoding_wrapper_with_warnings(raw_pre_decode_general, warnings)?);
+			},
+			None => return Ok(Self::general_decoding_wrapper(raw_pre_decode_general)?),
+		}
+	}
+
+	/// Determines the file type using the extension of the given path. If
+	/// the extension is missing or unknown, the type gets determined by 
+	/// looking at the first bytes of the file.
+	fn
+	get_file_type_or_detect
+	(
+		path: &Path
+	)
+	-> Result<FileExtension, LittleExifError>
+	{
+		match get_file_type(path)
+		{
+			Ok(file_type) => Ok(file_type),
+			Err(error)    => match Self::detect_file_type_from_path(path)
+			{
+				Some(file_type) => Ok(file_type),
+				None            => Err(error.into()),
+			}
+		}
 	}
 
+	/// Reads the first bytes of the file at the given path and tries to 
+	/// determine its type based on them
+	fn
+	detect_file_type_from_path
+	(
+		path: &Path
+	)
+	-> Option<FileExtension>
+	{
+		let mut buffer = Vec::new();
+		open_read_file(path).ok()?.take(16).read_to_end(&mut buffer).ok()?;
+		return detect_file_type(&buffer);
+	}
+
+	/// Removes the EXIF metadata from an image that is stored as `Vec<u8>`,
+	/// e.g. for privacy reasons, without having to write an empty `Metadata`
+	/// struct. Other data of the image is kept:
+	/// - JPEG: Only APP1 segments containing EXIF data are removed, so e.g.
+	///   the JFIF (APP0) and ICC profile (APP2) segments are preserved
+	/// - PNG: The eXIf chunk as well as zTXt and tEXt chunks using the
+	///   "Raw profile type exif" keyword are removed
+	/// - GIF: As GIF can't store EXIF data, the XMP metadata gets removed
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let mut file_data = fs::read("image.jpg").unwrap();
+	/// Metadata::clear_metadata(&mut file_data, FileExtension::JPEG).unwrap();
+	/// ```
 	#[allow(unreachable_patterns)]
 	pub fn
 	clear_metadata
@@ -118,29 +333,80 @@ Metadata
 		file_buffer: &mut Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::clear_metadata(file_buffer),
+				=>  jpg::clear_metadata(file_buffer)?,
 			FileExtension::JXL
-				=>  jxl::clear_metadata(file_buffer),
+				=>  jxl::clear_metadata(file_buffer)?,
 			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::clear_metadata(file_buffer),
+				=>  png::vec::clear_metadata(file_buffer, false)?,
 			FileExtension::TIFF
-				=> tiff::vec::clear_metadata(file_buffer),
+				=> tiff::vec::clear_metadata(file_buffer)?,
 			FileExtension::WEBP
-				=> webp::vec::clear_metadata(file_buffer),
+				=> webp::vec::clear_metadata(file_buffer)?,
+			FileExtension::GIF
+				=>  gif::clear_metadata(file_buffer)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'clear_metadata' not yet implemented for {:?}", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
+	}
+
+	/// Determines what `clear_metadata` would remove from the image, without
+	/// modifying it (e.g. for a dry run). Each `RemovalPlan` names the type of
+	/// the segment, chunk or box and gives its byte range in the image.
+	/// For WebP, `clear_metadata` additionally updates the file size and the
+	/// flags of the VP8X chunk. For TIFF, the metadata is not removed as a
+	/// single range but rewritten, so no plan can be given.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let file_data = fs::read("image.jpg").unwrap();
+	/// for plan in Metadata::plan_clear_metadata(&file_data, FileExtension::JPEG).unwrap()
+	/// {
+	///     println!("Would remove {}", plan);
+	/// }
+	/// ```
+	#[allow(unreachable_patterns)]
+	pub fn
+	plan_clear_metadata
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<Vec<RemovalPlan>, LittleExifError>
+	{
+		return Ok(match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::plan_clear_metadata(file_buffer)?,
+			FileExtension::JXL
+				=>  jxl::plan_clear_metadata(file_buffer)?,
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::plan_clear_metadata(file_buffer)?,
+			FileExtension::WEBP
+				=> webp::vec::plan_clear_metadata(file_buffer)?,
+			FileExtension::GIF
+				=>  gif::plan_clear_metadata(file_buffer)?,
+			_
+				=> return Err(LittleExifError::UnsupportedFormat(
+					format!(
+						"Function 'plan_clear_metadata' not implemented for {:?}", 
+						file_type
+					)
+				)),
+		});
 	}
 
 	/// Clears the APP12 segment in a JPEG file that contains data resulting
@@ -154,21 +420,20 @@ Metadata
 		file_buffer: &mut Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::clear_segment(file_buffer, 0xec),
+				=>  jpg::clear_segment(file_buffer, 0xec)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
 	}
 
 	/// Clears the APP13 segment in a JPEG file that contains data resulting
@@ -182,21 +447,20 @@ Metadata
 		file_buffer: &mut Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::clear_segment(file_buffer, 0xed),
+				=>  jpg::clear_segment(file_buffer, 0xed)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
 	}
 
 	/// Clears the APP12 segment in a JPEG file that contains data resulting
@@ -209,23 +473,22 @@ Metadata
 	(
 		path: &Path
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
 		let file_type = get_file_type(path)?;
 
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_clear_segment(path, 0xec),
+				=>  jpg::file_clear_segment(path, 0xec)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'file_clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
 	}
 
 	/// Clears the APP13 segment in a JPEG file that contains data resulting
@@ -238,23 +501,243 @@ Metadata
 	(
 		path: &Path
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
 		let file_type = get_file_type(path)?;
 
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_clear_segment(path, 0xed),
+				=>  jpg::file_clear_segment(path, 0xed)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'file_clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
 						file_type
 					)
-				),
+				)),
+		});
+	}
+
+	/// Reads the XMP packet from an image that is stored as a `Vec<u8>` and
+	/// returns it as UTF-8 string. Note that little_exif does not interpret
+	/// the XMP data in any way.
+	/// Currently only available for
+	/// - PNG, where the packet is stored in an iTXt chunk with the keyword
+	///   "XML:com.adobe.xmp"
+	/// - GIF, where the packet is stored in an "XMP DataXMP" application
+	///   extension
+	/// - WebP, where the packet is stored in the "XMP " chunk
+	#[allow(unreachable_patterns)]
+	pub fn
+	read_xmp
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<String, LittleExifError>
+	{
+		return Ok(match file_type
+		{
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_xmp(file_buffer)?,
+			FileExtension::GIF
+				=>  gif::read_metadata(file_buffer)?,
+			FileExtension::WEBP
+				=> webp::vec::read_xmp(file_buffer)?,
+			_
+				=> return Err(LittleExifError::UnsupportedFormat(
+					format!(
+						"Function 'read_xmp' not yet implemented for {:?}", 
+						file_type
+					)
+				)),
+		});
+	}
+
+	/// Gets the embedded ICC color profile from an image that is stored as a
+	/// `Vec<u8>`. Returns `None` if there is no (valid) profile. 
+	/// Currently only available for
+	/// - PNG, where the profile is stored compressed in the iCCP chunk
+	/// - JPEG, where the profile is stored in one or more APP2 segments with
+	///   the identifier "ICC_PROFILE" that get concatenated
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let file_data   = std::fs::read("image.jpg").unwrap();
+	/// let icc_profile = Metadata::get_icc_profile(&file_data, FileExtension::JPEG);
+	/// ```
+	#[allow(unreachable_patterns)]
+	pub fn
+	get_icc_profile
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Option<Vec<u8>>
+	{
+		let icc_profile_result = match file_type
+		{
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_icc_profile(file_buffer),
+			FileExtension::JPEG
+				=>  jpg::read_icc_profile(file_buffer),
+			_
+				=> return None,
+		};
+
+		return icc_profile_result.ok();
+	}
+
+	/// Gets the comment stored in the COM segment of a JPEG image that is 
+	/// stored as a `Vec<u8>`. Returns `None` if there is no comment.
+	/// As the encoding of the comment is not specified, the raw bytes are
+	/// returned. See `decode_jpeg_comment` for converting them to a string.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let file_data = std::fs::read("image.jpg").unwrap();
+	/// if let Some(comment) = Metadata::get_jpeg_comment(&file_data).unwrap()
+	/// {
+	///     println!("{}", Metadata::decode_jpeg_comment(&comment));
+	/// }
+	/// ```
+	pub fn
+	get_jpeg_comment
+	(
+		file_buffer: &Vec<u8>
+	)
+	-> Result<Option<Vec<u8>>, LittleExifError>
+	{
+		return Ok(jpg::read_comment(file_buffer)?);
+	}
+
+	/// Sets the comment stored in the COM segment of a JPEG image that is
+	/// stored as a `Vec<u8>`, replacing any existing comments. The comment
+	/// is limited to 65533 bytes.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let mut file_data = std::fs::read("image.jpg").unwrap();
+	/// Metadata::set_jpeg_comment(&mut file_data, "Hello World!".as_bytes()).unwrap();
+	/// ```
+	pub fn
+	set_jpeg_comment
+	(
+		file_buffer: &mut Vec<u8>,
+		comment:     &[u8]
+	)
+	-> Result<(), LittleExifError>
+	{
+		return Ok(jpg::write_comment(file_buffer, comment)?);
+	}
+
+	/// Converts the raw bytes of a JPEG comment to a string. They are decoded
+	/// as UTF-8 if possible, otherwise each byte is interpreted as Latin-1
+	/// character.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// assert_eq!(Metadata::decode_jpeg_comment("Grüße".as_bytes()), "Grüße");
+	/// assert_eq!(Metadata::decode_jpeg_comment(&[0x47, 0x72, 0xfc, 0xdf, 0x65]), "Grüße");
+	/// ```
+	pub fn
+	decode_jpeg_comment
+	(
+		comment: &[u8]
+	)
+	-> String
+	{
+		if let Ok(comment_string) = String::from_utf8(comment.to_vec())
+		{
+			return comment_string;
 		}
+		return comment.iter().map(|byte| *byte as char).collect::<String>();
+	}
+
+	/// Writes the given XMP packet to an image that is stored as a `Vec<u8>`,
+	/// replacing any existing XMP data. The packet is written as is, i.e.
+	/// little_exif does not check whether this is valid XMP. The EXIF data
+	/// of the image is not affected by this.
+	/// Currently only available for
+	/// - GIF, where the packet is stored in an "XMP DataXMP" application
+	///   extension right after the global color table
+	/// - WebP, where the packet is stored in the "XMP " chunk after the image
+	///   data and the EXIF chunk. The XMP flag of the VP8X chunk gets set,
+	///   which requires converting files in the simple format to the extended
+	///   format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let mut file_data = fs::read("image.webp").unwrap();
+	/// let xmp = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>";
+	/// Metadata::write_xmp(&mut file_data, FileExtension::WEBP, xmp).unwrap();
+	/// ```
+	#[allow(unreachable_patterns)]
+	pub fn
+	write_xmp
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension,
+		xmp:         &str
+	)
+	-> Result<(), LittleExifError>
+	{
+		return Ok(match file_type
+		{
+			FileExtension::GIF
+				=>  gif::write_metadata(file_buffer, xmp)?,
+			FileExtension::WEBP
+				=> webp::vec::write_xmp(file_buffer, xmp)?,
+			_
+				=> return Err(LittleExifError::UnsupportedFormat(
+					format!(
+						"Function 'write_xmp' not yet implemented for {:?}", 
+						file_type
+					)
+				)),
+		});
+	}
+
+	/// Removes the XMP packet from an image that is stored as a `Vec<u8>`. 
+	/// The EXIF data of the image is not affected by this.
+	/// Currently only available for GIF and WebP, see `write_xmp`
+	#[allow(unreachable_patterns)]
+	pub fn
+	clear_xmp
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<(), LittleExifError>
+	{
+		return Ok(match file_type
+		{
+			FileExtension::GIF
+				=>  gif::clear_metadata(file_buffer)?,
+			FileExtension::WEBP
+				=> webp::vec::clear_xmp(file_buffer)?,
+			_
+				=> return Err(LittleExifError::UnsupportedFormat(
+					format!(
+						"Function 'clear_xmp' not yet implemented for {:?}", 
+						file_type
+					)
+				)),
+		});
 	}
 
 	#[allow(unreachable_patterns)]
@@ -263,31 +746,78 @@ Metadata
 	(
 		path: &Path
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
 		let file_type = get_file_type(path)?;
 
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_clear_metadata(&path),
+				=>  jpg::file_clear_metadata(&path)?,
 			FileExtension::JXL
-				=>  jxl::file_clear_metadata(&path),
+				=>  jxl::file_clear_metadata(&path)?,
 			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::file::clear_metadata(&path),
+				=>  png::file::clear_metadata(&path, false, false)?,
 			FileExtension::TIFF
-				=> tiff::file::clear_metadata(&path),
+				=> tiff::file::clear_metadata(&path)?,
 			FileExtension::WEBP 
-				=> webp::file::clear_metadata(&path),
+				=> webp::file::clear_metadata(&path)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'file_clear_metadata' not yet implemented for {:?}", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
+	}
+
+	/// Sets the `ExifImageWidth` (PixelXDimension) and `ExifImageHeight` 
+	/// (PixelYDimension) tags to the dimensions of the image as declared by 
+	/// the container, i.e. the IHDR chunk for PNG, the start of frame segment
+	/// for JPEG and the VP8X chunk (or the image data of simple files) for
+	/// WebP. 
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let mut file_data = std::fs::read("image.jpg").unwrap();
+	/// let mut metadata  = Metadata::new();
+	/// metadata.set_dimensions_from_buffer(&file_data, FileExtension::JPEG).unwrap();
+	/// metadata.write_to_vec(&mut file_data, FileExtension::JPEG).unwrap();
+	/// ```
+	#[allow(unreachable_patterns)]
+	pub fn
+	set_dimensions_from_buffer
+	(
+		&mut self,
+		file_buffer: &[u8],
+		file_type:   FileExtension
+	)
+	-> Result<(), LittleExifError>
+	{
+		let (width, height) = match file_type
+		{
+			FileExtension::JPEG
+				=>  jpg::read_dimensions(file_buffer)?,
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_dimensions(file_buffer)?,
+			FileExtension::WEBP
+				=> webp::vec::read_dimensions(file_buffer)?,
+			_
+				=> return Err(LittleExifError::UnsupportedFormat(
+					format!(
+						"Function 'set_dimensions_from_buffer' not yet implemented for {:?}", 
+						file_type
+					)
+				)),
+		};
+
+		self.set_tag(ExifTag::ExifImageWidth( vec![width ]))?;
+		self.set_tag(ExifTag::ExifImageHeight(vec![height]))?;
+
+		return Ok(());
 	}
 
 	/// Converts the metadata into a file specific vector of bytes
@@ -304,7 +834,7 @@ Metadata
 		&self,
 		for_file_type: FileExtension
 	)
-	-> Result<Vec<u8>, std::io::Error>
+	-> Result<Vec<u8>, LittleExifError>
 	{
 		let general_encoded_metadata = self.encode()?;
 
@@ -313,7 +843,7 @@ Metadata
 			FileExtension::PNG { as_zTXt_chunk } 
 				=>  png::as_u8_vec(&general_encoded_metadata, as_zTXt_chunk),
 			FileExtension::JPEG 
-				=>  jpg::as_u8_vec(&general_encoded_metadata),
+				=>  jpg::as_u8_vec(&general_encoded_metadata)?,
 			FileExtension::WEBP 
 				=> webp::as_u8_vec(&general_encoded_metadata),
 			_
@@ -322,8 +852,8 @@ Metadata
 	}
 
 	/// Writes the metadata to an image stored as a Vec<u8>
-	/// For now, this only works for JPGs
-	#[allow(unreachable_patterns)]
+	/// For PNG images, setting `as_zTXt_chunk` to false writes the metadata
+	/// to an eXIf chunk instead of the legacy zTXt chunk
 	pub fn
 	write_to_vec
 	(
@@ -331,29 +861,127 @@ Metadata
 		file_buffer: &mut Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
 	{
-		match file_type
+		return self.generic_write_to_vec(file_buffer, file_type, &mut Vec::new());
+	}
+
+	/// Writes the metadata to an image stored as a Vec<u8> like `write_to_vec`
+	/// and additionally returns the warnings that were encountered, e.g. if
+	/// the thumbnail got dropped due to the `ThumbnailPolicy`.
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::thumbnail::ThumbnailPolicy;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let mut file_data = std::fs::read("image.jpg").unwrap();
+	/// let mut metadata  = Metadata::new_from_vec(&file_data, FileExtension::JPEG).unwrap();
+	/// metadata.set_thumbnail_policy(ThumbnailPolicy::DropIfTooLarge);
+	/// 
+	/// for warning in metadata.write_to_vec_with_warnings(&mut file_data, FileExtension::JPEG).unwrap()
+	/// {
+	///     println!("{}", warning);
+	/// }
+	/// ```
+	pub fn
+	write_to_vec_with_warnings
+	(
+		&self,
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<Vec<MetadataWarning>, LittleExifError>
+	{
+		let mut warnings = Vec::new();
+		self.generic_write_to_vec(file_buffer, file_type, &mut warnings)?;
+		return Ok(warnings);
+	}
+
+	#[allow(unreachable_patterns)]
+	fn
+	generic_write_to_vec
+	(
+		&self,
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension,
+		warnings:    &mut Vec<MetadataWarning>
+	)
+	-> Result<(), LittleExifError>
+	{
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::write_metadata(file_buffer, &self),
+				=>  jpg::write_metadata(file_buffer, &self, warnings)?,
 			FileExtension::JXL 
-				=>  jxl::write_metadata(file_buffer, &self),
-			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::write_metadata(file_buffer, &self),
+				=>  jxl::write_metadata(file_buffer, &self, warnings)?,
+			FileExtension::PNG { as_zTXt_chunk }
+				=>  png::vec::write_metadata(
+					file_buffer, 
+					&self, 
+					&PngWriteOptions { as_zTXt_chunk, ..Default::default() },
+					warnings
+				)?,
 			FileExtension::TIFF
-				=> tiff::vec::write_metadata(file_buffer, &self),
+				=> tiff::vec::write_metadata(file_buffer, &self, warnings)?,
 			FileExtension::WEBP
-				=> webp::vec::write_metadata(file_buffer, &self),
+				=> webp::vec::write_metadata(file_buffer, &self, warnings)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'file_clear_metadata' not yet implemented for {:?}", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
+	}
+
+	/// Writes the metadata to an image stored as an owned Vec<u8> and returns
+	/// the resulting image data, e.g. for directly passing it on as body of 
+	/// an HTTP response without touching the file system.
+	/// See `write_to_vec` for further details.
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let file_data = std::fs::read("image.jpg").unwrap();
+	/// let new_data  = Metadata::new().to_file_buffer(file_data, FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	to_file_buffer
+	(
+		&self,
+		mut file_buffer: Vec<u8>,
+		file_type:       FileExtension
+	)
+	-> Result<Vec<u8>, LittleExifError>
+	{
+		self.write_to_vec(&mut file_buffer, file_type)?;
+		return Ok(file_buffer);
+	}
+
+	/// Writes the metadata to a PNG image stored as a Vec<u8> using the given
+	/// PNG specific options, e.g. for writing to images with broken CRCs
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::png::PngWriteOptions;
+	/// 
+	/// let mut file_data = std::fs::read("image.png").unwrap();
+	/// let options = PngWriteOptions { lenient_crc: true, ..Default::default() };
+	/// Metadata::new().write_to_png_vec(&mut file_data, &options).unwrap();
+	/// ```
+	pub fn
+	write_to_png_vec
+	(
+		&self,
+		file_buffer: &mut Vec<u8>,
+		options:     &PngWriteOptions
+	)
+	-> Result<(), LittleExifError>
+	{
+		return Ok(png::vec::write_metadata(file_buffer, &self, options, &mut Vec::new())?);
 	}
 
 	/// Writes the metadata to the specified file.
@@ -361,37 +989,69 @@ Metadata
 	/// - The file does not exist at the given path
 	/// - Interpreting the given path fails
 	/// - The file type is not supported
-	#[allow(unreachable_patterns)]
 	pub fn
 	write_to_file
 	(
 		&self,
 		path: &Path
 	)
-	-> Result<(), std::io::Error>
+	-> Result<(), LittleExifError>
+	{
+		return self.generic_write_to_file(path, &mut Vec::new());
+	}
+
+	/// Writes the metadata to the specified file like `write_to_file` and
+	/// additionally returns the warnings that were encountered, e.g. if the
+	/// thumbnail got dropped due to the `ThumbnailPolicy`.
+	pub fn
+	write_to_file_with_warnings
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<Vec<MetadataWarning>, LittleExifError>
+	{
+		let mut warnings = Vec::new();
+		self.generic_write_to_file(path, &mut warnings)?;
+		return Ok(warnings);
+	}
+
+	#[allow(unreachable_patterns)]
+	fn
+	generic_write_to_file
+	(
+		&self,
+		path:     &Path,
+		warnings: &mut Vec<MetadataWarning>
+	)
+	-> Result<(), LittleExifError>
 	{
 		let file_type = get_file_type(path)?;
 
-		match file_type
+		return Ok(match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_write_metadata(&path, &self),
+				=>  jpg::file_write_metadata(&path, &self, warnings)?,
 			FileExtension::JXL 
-				=>  jxl::file_write_metadata(&path, &self),
-			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::file::write_metadata(&path, &self),
+				=>  jxl::file_write_metadata(&path, &self, warnings)?,
+			FileExtension::PNG { as_zTXt_chunk }
+				=>  png::file::write_metadata(
+					&path, 
+					&self, 
+					&PngWriteOptions { as_zTXt_chunk, ..Default::default() },
+					warnings
+				)?,
 			FileExtension::TIFF
-				=> tiff::file::write_metadata(&path, &self),
+				=> tiff::file::write_metadata(&path, &self, warnings)?,
 			FileExtension::WEBP 
-				=> webp::file::write_metadata(&path, &self),
+				=> webp::file::write_metadata(&path, &self, warnings)?,
 			_
-				=> return io_error!(
-					Other, 
+				=> return Err(LittleExifError::UnsupportedFormat(
 					format!(
 						"Function 'write_to_file' not yet implemented for {:?}", 
 						file_type
 					)
-				),
-		}
+				)),
+		});
 	}
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::path::Path;
+
+use crate::error::LittleExifError;
+use crate::filetype::get_file_type;
+use crate::general_file_io::detect_file_type;
+
+use super::Metadata;
+
+impl
+Metadata
+{
+	/// Async variant of `new_from_path` (requires the `async` feature).
+	/// Only reading the file is done asynchronously using `tokio::fs`, the 
+	/// decoding of the metadata happens in-memory via `new_from_vec`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// # async fn example() -> Result<(), std::io::Error> {
+	/// let metadata = Metadata::new_from_path_async(std::path::Path::new("image.jpg")).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn
+	new_from_path_async
+	(
+		path: &Path
+	)
+	-> Result<Metadata, LittleExifError>
+	{
+		let file_buffer = tokio::fs::read(path).await?;
+
+		// Same as `new_from_path`: Fall back to looking at the first bytes
+		// in case the extension is missing or unknown
+		let file_type = match get_file_type(path)
+		{
+			Ok(file_type) => file_type,
+			Err(error)    => detect_file_type(&file_buffer).ok_or(error)?,
+		};
+
+		return Self::new_from_vec(&file_buffer, file_type);
+	}
+
+	/// Async variant of `write_to_file` (requires the `async` feature).
+	/// The file gets read and written back asynchronously using `tokio::fs`,
+	/// while the metadata is written to the in-memory data via `write_to_vec`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// # async fn example() -> Result<(), std::io::Error> {
+	/// let metadata = Metadata::new();
+	/// metadata.write_to_file_async(std::path::Path::new("image.jpg")).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn
+	write_to_file_async
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<(), LittleExifError>
+	{
+		let file_type       = get_file_type(path)?;
+		let mut file_buffer = tokio::fs::read(path).await?;
+
+		self.write_to_vec(&mut file_buffer, file_type)?;
+
+		return Ok(tokio::fs::write(path, file_buffer).await?);
+	}
+}
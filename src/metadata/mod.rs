@@ -6,6 +6,24 @@ pub mod iterator;
 pub mod edit;
 pub mod get;
 pub mod set;
+pub mod gps;
+pub mod datetime;
+pub mod thumbnail;
+pub mod makernote;
+pub mod warning;
+pub mod builder;
+pub mod orientation;
+pub mod merge;
+pub mod dedup;
+pub mod diff;
+pub mod xp;
+pub mod display;
+pub mod removal_plan;
+pub mod user_comment;
+pub mod validate;
+
+#[cfg(feature = "async")]
+pub mod metadata_io_async;
 
 use core::panic;
 use std::io::Cursor;
@@ -14,6 +32,7 @@ use std::io::Seek;
 use std::io::Write;
 
 use crate::endian::*;
+use crate::error::LittleExifError;
 use crate::general_file_io::io_error;
 use crate::general_file_io::EXIF_HEADER;
 use crate::ifd::ExifTagGroup;
@@ -21,12 +40,63 @@ use crate::ifd::ImageFileDirectory;
 use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::U8conversion;
 
-#[derive(Clone)]
+use makernote::MakerNoteHandling;
+use thumbnail::ThumbnailPolicy;
+use warning::MetadataWarning;
+
+/// Two `Metadata` structs are considered equal if they consist of the same
+/// IFDs (see the `PartialEq` implementation of `ImageFileDirectory`), i.e. 
+/// only the decoded values are compared. The endianness does not take part
+/// in this, as it only affects how the values are encoded. Note that the
+/// contents of `UNDEF` tags (e.g. `MakerNote`) are opaque bytes that are not
+/// converted when changing the endianness.
+///
+/// With the `serde` feature enabled, the metadata can be (de)serialized. This
+/// only covers the endianness and the IFDs, all other settings (e.g. the
+/// `MakerNoteHandling`) are reset to their defaults when deserializing.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct
 Metadata
 {
 	endian:                 Endian,
-	image_file_directories: Vec<ImageFileDirectory>
+	image_file_directories: Vec<ImageFileDirectory>,
+
+	/// Offset of the MakerNote data at the time of decoding
+	#[cfg_attr(feature = "serde", serde(skip))]
+	makernote_offset:       Option<u32>,
+
+	/// Byte order of the MakerNote data at the time of decoding, which may
+	/// differ from `endian` after calling `set_endian`
+	#[cfg_attr(feature = "serde", serde(skip))]
+	makernote_endian:       Option<Endian>,
+	#[cfg_attr(feature = "serde", serde(skip))]
+	makernote_handling:     MakerNoteHandling,
+
+	#[cfg_attr(feature = "serde", serde(skip))]
+	thumbnail_policy:       ThumbnailPolicy,
+
+	/// The TIFF/EXIF data as it was read from the file, see `raw_exif`
+	#[cfg_attr(feature = "serde", serde(skip))]
+	raw_exif:               Option<Vec<u8>>,
+}
+
+impl
+PartialEq
+for
+Metadata
+{
+	fn
+	eq
+	(
+		&self,
+		other: &Self
+	)
+	-> bool
+	{
+		return self.image_file_directories.len() == other.image_file_directories.len()
+			&& self.image_file_directories.iter().all(|ifd| other.image_file_directories.contains(ifd));
+	}
 }
 
 impl
@@ -48,7 +118,15 @@ Metadata
 	()
 	-> Metadata
 	{
-		Metadata { endian: Endian::Little, image_file_directories: Vec::new() }
+		Metadata { 
+			endian:                 Endian::Little, 
+			image_file_directories: Vec::new(),
+			makernote_offset:       None,
+			makernote_endian:       None,
+			makernote_handling:     MakerNoteHandling::default(),
+			thumbnail_policy:       ThumbnailPolicy::default(),
+			raw_exif:               None,
+		}
 	}
 
 	/// Creates an IFD in this struct if it does not exist yet.
@@ -74,8 +152,12 @@ Metadata
 			self.create_ifd(parent_ifd_group, generic_ifd_nr);
 		}
 
-		self.image_file_directories.push(new_ifd);
-		self.sort_data();
+		// Insert the new IFD at its sorted position instead of sorting all
+		// IFDs again, see `sort_data`
+		let position = self.image_file_directories
+			.binary_search_by(|ifd| Self::compare_ifds(ifd, &new_ifd))
+			.unwrap_or_else(|position| position);
+		self.image_file_directories.insert(position, new_ifd);
 	}
 
 
@@ -85,25 +167,45 @@ Metadata
 		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>
 	)
 	-> Result<Metadata, std::io::Error>
+	{
+		return Self::general_decoding_wrapper_with_warnings(raw_pre_decode_general, &mut Vec::new());
+	}
+
+	/// Same as `general_decoding_wrapper`, but collects recoverable issues
+	/// encountered during decoding in the given vector
+	pub(crate) fn
+	general_decoding_wrapper_with_warnings
+	(
+		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>,
+		warnings:               &mut Vec<MetadataWarning>
+	)
+	-> Result<Metadata, std::io::Error>
 	{
 		if let Ok(pre_decode_general) = raw_pre_decode_general
 		{
-			let mut pre_decode_cursor = Cursor::new(&pre_decode_general);
-			let     decoding_result   = Self::decode(&mut pre_decode_cursor);
-			if let Ok((endian, image_file_directories)) = decoding_result
+			let decoding_result = Self::decode_from_vec(&pre_decode_general, warnings);
+			if let Ok(data) = decoding_result
 			{
-				let mut data = Metadata { endian, image_file_directories };
-				data.sort_data();
 				return Ok(data);
 			}
 			else
 			{
-				eprintln!("{}", decoding_result.err().unwrap());
+				let error = decoding_result.err().unwrap();
+				eprintln!("{}", error);
+				warnings.push(MetadataWarning::DecodingFailed(error.to_string()));
 			}
 		}
 		else
 		{
-			eprintln!("Error during decoding: {:?}", raw_pre_decode_general.err().unwrap());
+			// A file without any metadata results in a new, empty struct,
+			// while all other errors (e.g. a wrong signature) are passed on
+			let error = raw_pre_decode_general.err().unwrap();
+			let inner_error = error.get_ref().and_then(|inner| inner.downcast_ref::<LittleExifError>());
+			if !matches!(inner_error, Some(LittleExifError::NoMetadata(_)))
+			{
+				return Err(error);
+			}
+			return Ok(Metadata::new());
 		}
 
 		eprintln!("WARNING: Can't read metadata - Create new & empty struct");
@@ -111,13 +213,85 @@ Metadata
 	}
 
 
-	/// Assumes that the data is sorted according to `sort_data`
+	/// Decodes the given TIFF/EXIF data (with or without the "Exif\0\0" 
+	/// header). In contrast to `general_decoding_wrapper`, a failure is
+	/// returned as error instead of resulting in empty metadata.
+	pub(crate) fn
+	decode_from_vec
+	(
+		pre_decode_general: &Vec<u8>,
+		warnings:           &mut Vec<MetadataWarning>
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let (endian, image_file_directories, makernote_offset) = Self::decode(
+			&mut Cursor::new(pre_decode_general), 
+			warnings
+		)?;
+
+		// Keep the raw data without the "Exif\0\0" header
+		let raw_exif = pre_decode_general
+			.strip_prefix(&EXIF_HEADER)
+			.unwrap_or(pre_decode_general)
+			.to_vec();
+
+		let mut data = Metadata { 
+			makernote_endian:   makernote_offset.map(|_| endian.clone()),
+			endian, 
+			image_file_directories,
+			makernote_offset,
+			makernote_handling: MakerNoteHandling::default(),
+			thumbnail_policy:   ThumbnailPolicy::default(),
+			raw_exif:           Some(raw_exif),
+		};
+		data.sort_data();
+		return Ok(data);
+	}
+
+	/// Encodes the metadata as TIFF data, starting with the TIFF header.
+	/// The IFDs are written in the order established by `sort_data`, the
+	/// entries of each IFD in ascending order by their tag as required by the
+	/// TIFF specification, independent of the order they have been set in.
 	pub fn
 	encode
 	(
 		&self
 	)
 	-> Result<Vec<u8>, std::io::Error>
+	{
+		return self.encode_with_warnings(&mut Vec::new());
+	}
+
+	/// Encodes the metadata like `encode`, collecting the warnings that were
+	/// encountered, e.g. if the offsets of the MakerNote may be stale.
+	pub(crate) fn
+	encode_with_warnings
+	(
+		&self,
+		warnings: &mut Vec<MetadataWarning>
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		// The header already links to the first IFD at offset 8, which is
+		// where `encode_ifds` places it
+		let mut encode_vec = Vec::from(self.endian.header());
+		encode_vec.extend(self.encode_ifds(8, warnings)?);
+
+		return Ok(encode_vec);
+	}
+
+	/// Encodes the IFDs without the TIFF header, for storing them at the
+	/// given offset of the TIFF data. All offsets (e.g. to the SubIFDs) are
+	/// computed accordingly. The first IFD is located at `start_offset`, which
+	/// needs to be a multiple of 4.
+	pub(crate) fn
+	encode_ifds
+	(
+		&self,
+		start_offset: u32,
+		warnings:     &mut Vec<MetadataWarning>
+	)
+	-> Result<Vec<u8>, std::io::Error>
 	{
 		// Prepare offset information
 		let mut ifds_with_offset_info_only: Vec<ImageFileDirectory> = Vec::new();
@@ -145,7 +319,7 @@ Metadata
 						candidate_ifd.get_generic_ifd_nr() == ifd.get_generic_ifd_nr()
 					)
 				{
-					parent_ifd.set_tag(offset_tag);
+					parent_ifd.set_tag_unchecked(offset_tag);
 				}
 				else
 				{
@@ -170,10 +344,10 @@ Metadata
 		// so on up to IFD-n.
 		let generic_ifd_count = self.get_max_generic_ifd_number();
 		
-		let mut index_of_previous_ifds_link_section: Option<u64> = Some(4);
+		let mut index_of_previous_ifds_link_section: Option<u64> = None;
 
-		let mut encode_vec     = Vec::from(self.endian.header());
-		let mut current_offset = 8;
+		let mut encode_vec     = Vec::new();
+		let mut current_offset = start_offset;
 
 		for n in 0..=generic_ifd_count
 		{
@@ -190,13 +364,14 @@ Metadata
 				&self, 
 				&mut ifds_with_offset_info_only, 
 				&mut encode_vec, 
-				&mut current_offset
+				&mut current_offset,
+				warnings
 			)
 			{
 				if let Some(index) = index_of_previous_ifds_link_section
 				{
 					let mut cursor = Cursor::new(&mut encode_vec);
-					cursor.set_position(index);
+					cursor.set_position(index - start_offset as u64);
 					cursor.write_all(&link_vec)?;
 				}
 
@@ -204,45 +379,154 @@ Metadata
 			}
 		}
 
-		Ok(encode_vec)
+		return Ok(encode_vec);
 	}
 
-	fn
+	/// Gets the size in bytes of the encoded metadata, i.e. the length of the
+	/// vector returned by `encode`. Note that the metadata gets encoded for 
+	/// this, as the size depends on the layout and alignment of the IFDs and
+	/// their values. When writing to a file, the format specific overhead 
+	/// (e.g. the "Exif" header and the APP1 marker for JPEG) comes on top.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	///
+	/// // A JPEG APP1 segment can hold at most 65533 bytes, including the
+	/// // 6 bytes of the "Exif" header
+	/// if metadata.encoded_size().unwrap() > 65533 - 6
+	/// {
+	///     println!("Metadata is too large for a JPEG file!");
+	/// }
+	/// ```
+	pub fn
+	encoded_size
+	(
+		&self
+	)
+	-> Result<usize, std::io::Error>
+	{
+		return Ok(self.encode()?.len());
+	}
+
+	/// Sorts the IFDs by their generic IFD number first and then by their
+	/// group (in the order given by `ExifTagGroup::all`), e.g. IFD0, its
+	/// ExifIFD, Interop and GPS IFDs, followed by IFD1. The tags within each
+	/// IFD get sorted in ascending order by their hex value, as required by
+	/// the TIFF specification. The sorting is stable, i.e. tags with the same
+	/// hex value keep their relative order.
+	/// The IFDs are already kept in this order when decoding, setting tags
+	/// and creating IFDs, and `encode` writes the tags of each IFD in 
+	/// ascending order in any case, so calling this is usually not required.
+	///
+	/// # Panics
+	/// Panics if there are two IFDs with the same group and generic IFD
+	/// number.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::GPSAltitudeRef(vec![0])).unwrap();
+	/// metadata.set_tag(ExifTag::ISO(vec![100])).unwrap();
+	/// metadata.sort_data();
+	///
+	/// let groups = metadata.get_ifds().iter().map(|ifd| ifd.get_ifd_type()).collect::<Vec<_>>();
+	/// assert_eq!(groups, vec![ExifTagGroup::GENERIC, ExifTagGroup::EXIF, ExifTagGroup::GPS]);
+	/// ```
+	pub fn
 	sort_data
 	(
 		&mut self
 	)
 	{
+		for ifd in self.image_file_directories.iter_mut()
+		{
+			ifd.sort_tags();
+		}
+
 		self.image_file_directories.sort_by(
 			|a, b|
-			if a.get_generic_ifd_nr() != b.get_generic_ifd_nr()
 			{
-				a.get_generic_ifd_nr().cmp(&b.get_generic_ifd_nr())
-			}
-			else
-			{
-				if a.get_ifd_type() == b.get_ifd_type()
+				if a.get_generic_ifd_nr() == b.get_generic_ifd_nr() && a.get_ifd_type() == b.get_ifd_type()
 				{
 					panic!("Should not have two different IFDs with same group & number!");
 				}
-				if a.get_ifd_type() < b.get_ifd_type()
-				{
-					std::cmp::Ordering::Less
-				}
-				else
-				{
-					std::cmp::Ordering::Greater
-				}
+				Self::compare_ifds(a, b)
 			}
 		);
 	}
+
+	/// The order of the IFDs established by `sort_data`: By generic IFD 
+	/// number first, then by group
+	fn
+	compare_ifds
+	(
+		a: &ImageFileDirectory,
+		b: &ImageFileDirectory
+	)
+	-> std::cmp::Ordering
+	{
+		if a.get_generic_ifd_nr() != b.get_generic_ifd_nr()
+		{
+			return a.get_generic_ifd_nr().cmp(&b.get_generic_ifd_nr());
+		}
+		return a.get_ifd_type().partial_cmp(&b.get_ifd_type()).unwrap();
+	}
  
 	fn
 	decode
 	(
-		data_cursor: &mut Cursor<&Vec<u8>>
+		data_cursor: &mut Cursor<&Vec<u8>>,
+		warnings:    &mut Vec<MetadataWarning>
 	)
-	-> Result<(Endian, Vec<ImageFileDirectory>), std::io::Error>
+	-> Result<(Endian, Vec<ImageFileDirectory>, Option<u32>), std::io::Error>
+	{
+		return Self::decode_with_big_tiff_option(data_cursor, false, warnings);
+	}
+
+	/// Decodes the metadata of a BigTIFF file (i.e. with magic number 43 
+	/// instead of 42), which uses 8 byte offsets. Only TIFF files may use this
+	/// layout, so this is not called for EXIF data embedded in other formats.
+	pub(crate) fn
+	decode_big_tiff
+	(
+		data: &Vec<u8>
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let (endian, image_file_directories, makernote_offset) = Self::decode_with_big_tiff_option(
+			&mut Cursor::new(data), 
+			true, 
+			&mut Vec::new()
+		)?;
+
+		let mut metadata = Metadata { 
+			makernote_endian:   makernote_offset.map(|_| endian.clone()),
+			endian, 
+			image_file_directories,
+			makernote_offset,
+			makernote_handling: MakerNoteHandling::default(),
+			thumbnail_policy:   ThumbnailPolicy::default(),
+			raw_exif:           None,
+		};
+		metadata.sort_data();
+		return Ok(metadata);
+	}
+
+	fn
+	decode_with_big_tiff_option
+	(
+		data_cursor:    &mut Cursor<&Vec<u8>>,
+		allow_big_tiff:      bool,
+		warnings:       &mut Vec<MetadataWarning>
+	)
+	-> Result<(Endian, Vec<ImageFileDirectory>, Option<u32>), std::io::Error>
 	{
 		// Get the start position
 		let mut data_start_position = data_cursor.position();
@@ -282,29 +566,73 @@ Metadata
 			_            => { return io_error!(Other, format!("Illegal endian information: {:?}", endian_buffer)) } 
 		};
 
-		// Validate magic number
+		// Validate magic number, which is 42 for regular TIFF data and 43 
+		// for BigTIFF files
 		let mut magic_number_buffer = vec![0u8; 2];
 		data_cursor.read_exact(&mut magic_number_buffer)?;
-		if !(
-			(endian == Endian::Little && magic_number_buffer == [0x2a, 0x00]) ||
-			(endian == Endian::Big    && magic_number_buffer == [0x00, 0x2a])
-		)
+		let magic_number = from_u8_vec_macro!(u16, &magic_number_buffer.to_vec(), &endian);
+
+		let big_tiff = match magic_number
 		{
-			return io_error!(Other, "Could not verify magic number!");
+			42 => false,
+			43 => true,
+			_  => return io_error!(Other, "Could not verify magic number!"),
+		};
+
+		if big_tiff && !allow_big_tiff
+		{
+			return io_error!(InvalidData, "Found BigTIFF magic number 43 in EXIF data, which is only valid for TIFF files!");
 		}
 
 		// Get offset to IFD0
-		let mut ifd0_offset_buffer = vec![0u8; 4];
-		data_cursor.read_exact(&mut ifd0_offset_buffer)?;
-		let mut ifd_offset_option = Some(from_u8_vec_macro!(u32, &ifd0_offset_buffer.to_vec(), &endian));
+		// For BigTIFF this is preceded by the byte size of offsets (always 8)
+		// and two reserved bytes (always 0)
+		let mut ifd_offset_option;
+		if big_tiff
+		{
+			let mut offset_info_buffer = vec![0u8; 4];
+			data_cursor.read_exact(&mut offset_info_buffer)?;
+			if from_u8_vec_macro!(u16, &offset_info_buffer[0..2].to_vec(), &endian) != 8
+			|| from_u8_vec_macro!(u16, &offset_info_buffer[2..4].to_vec(), &endian) != 0
+			{
+				return io_error!(InvalidData, "Unsupported BigTIFF offset byte size or reserved value!");
+			}
+
+			let mut ifd0_offset_buffer = vec![0u8; 8];
+			data_cursor.read_exact(&mut ifd0_offset_buffer)?;
+			let ifd0_offset = from_u8_vec_macro!(u64, &ifd0_offset_buffer.to_vec(), &endian);
+			if ifd0_offset > u32::MAX as u64
+			{
+				return io_error!(Unsupported, format!("BigTIFF offset {} to IFD0 is not supported!", ifd0_offset));
+			}
+			ifd_offset_option = Some(ifd0_offset as u32);
+		}
+		else
+		{
+			let mut ifd0_offset_buffer = vec![0u8; 4];
+			data_cursor.read_exact(&mut ifd0_offset_buffer)?;
+			ifd_offset_option = Some(from_u8_vec_macro!(u32, &ifd0_offset_buffer.to_vec(), &endian));
+		}
 
 		// Decode all the IFDs
 		let mut ifds = Vec::new();
 		let mut generic_ifd_nr = 0;
+		let mut makernote_offset = None;
+		// Shared by all IFDs, including the SubIFDs and the EXIF, GPS and
+		// Interop IFDs, so that any link back to an already decoded IFD is
+		// detected by `decode_ifd`
+		let mut visited_offsets  = Vec::new();
 		loop
 		{
 			if let Some(ifd_offset) = ifd_offset_option
 			{
+				// Skip IFDs that are located past the end of the data
+				if data_start_position + ifd_offset as u64 >= data_cursor.get_ref().len() as u64
+				{
+					warnings.push(MetadataWarning::SkippedIfd { group: ExifTagGroup::GENERIC, offset: ifd_offset });
+					break;
+				}
+
 				data_cursor.set_position(data_start_position);
 				data_cursor.seek(std::io::SeekFrom::Current(ifd_offset as i64))?;
 
@@ -312,9 +640,14 @@ Metadata
 					data_cursor,
 					data_start_position,
 					&endian,
+					big_tiff,
 					&ExifTagGroup::GENERIC,
 					generic_ifd_nr,
-					&mut ifds
+					&mut ifds,
+					&mut makernote_offset,
+					warnings,
+					&mut visited_offsets,
+					0
 				);
 
 				if let Ok(new_ifd_offset_option) = decode_result
@@ -336,7 +669,7 @@ Metadata
 
 
 
-		return Ok((endian, ifds));
+		return Ok((endian, ifds, makernote_offset));
 	}
 }
 
@@ -356,11 +689,109 @@ use super::Metadata;
 	{
 		let image_data = read("tests/read_sample.tif").unwrap();
 
-		Metadata::decode(&mut Cursor::new(&image_data))?;
+		Metadata::decode(&mut Cursor::new(&image_data), &mut Vec::new())?;
 
 		Ok(())
 	}
 
+	#[test]
+	fn
+	big_tiff_in_exif_data()
+	{
+		// Magic number 43 is only valid for TIFF files, not for EXIF data 
+		// embedded in other formats
+		let mut exif_data = b"Exif\0\0".to_vec();
+		exif_data.extend([0x49, 0x49, 0x2b, 0x00, 0x08, 0x00, 0x00, 0x00]);
+		exif_data.extend(16u64.to_le_bytes());
+		exif_data.extend([0u8; 16]);
+
+		let result = Metadata::decode(&mut Cursor::new(&exif_data), &mut Vec::new());
+		assert_eq!(result.err().unwrap().kind(), std::io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn
+	circular_ifd_reference()
+	{
+		// IFD0 at offset 8 links to IFD1 at offset 26, which links back to
+		// IFD0 - each containing a single ImageWidth tag
+		let mut tiff_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		for next_ifd_offset in [26u32, 8u32]
+		{
+			tiff_data.extend([0x01, 0x00, 0x00, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+			tiff_data.extend(next_ifd_offset.to_le_bytes());
+		}
+
+		let result = Metadata::decode(&mut Cursor::new(&tiff_data), &mut Vec::new());
+		let error  = result.err().unwrap();
+		assert_eq!(error.kind(),      std::io::ErrorKind::InvalidData);
+		assert_eq!(error.to_string(), "Circular IFD reference");
+	}
+
+	#[test]
+	fn
+	circular_subifd_reference()
+	{
+		// IFD0 at offset 8 contains a SubIFDs tag pointing to IFD0 itself
+		let tiff_data = vec![
+			0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x4a, 0x01, 0x04,
+			0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+		];
+
+		let result = Metadata::decode(&mut Cursor::new(&tiff_data), &mut Vec::new());
+		let error  = result.err().unwrap();
+		assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+		assert!(error.to_string().ends_with("Circular IFD reference"));
+	}
+
+	#[test]
+	fn
+	circular_exif_offset_reference()
+	{
+		// IFD0 at offset 8 with an ExifOffset tag pointing to IFD0 itself
+		let tiff_data = vec![
+			0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x69, 0x87, 0x04,
+			0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+		];
+
+		let result = Metadata::decode(&mut Cursor::new(&tiff_data), &mut Vec::new());
+		let error  = result.err().unwrap();
+		assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+		assert!(error.to_string().ends_with("Circular IFD reference"));
+
+		// The EXIF IFD at offset 26 (linked via ExifOffset) is decoded first,
+		// the link from IFD0 to IFD1 points to the very same IFD
+		let mut tiff_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		tiff_data.extend([0x01, 0x00, 0x69, 0x87, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00]);
+		tiff_data.extend(26u32.to_le_bytes());
+		tiff_data.extend([0x00; 6]);
+
+		let result = Metadata::decode(&mut Cursor::new(&tiff_data), &mut Vec::new());
+		let error  = result.err().unwrap();
+		assert_eq!(error.kind(),      std::io::ErrorKind::InvalidData);
+		assert_eq!(error.to_string(), "Circular IFD reference");
+	}
+
+	#[test]
+	fn
+	deeply_nested_subifds()
+	{
+		// Each IFD contains a SubIFDs tag pointing to the next one
+		let mut tiff_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		for index in 0..100u32
+		{
+			tiff_data.extend([0x01, 0x00, 0x4a, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00]);
+			tiff_data.extend((8 + 18 * (index + 1)).to_le_bytes());
+			tiff_data.extend([0x00; 4]);
+		}
+		tiff_data.extend([0x00; 6]);
+
+		let result = Metadata::decode(&mut Cursor::new(&tiff_data), &mut Vec::new());
+		let error  = result.err().unwrap();
+		assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+		assert!(error.to_string().ends_with("IFDs are nested deeper than 16 levels!"));
+	}
+
 	#[ignore]
 	#[test]
 	fn
@@ -370,7 +801,7 @@ use super::Metadata;
 		// let image_data = read("tests/multi_page.tif").unwrap();
 		let image_data = read("tests/multi_page_mod.tif").unwrap();
 
-		let data = Metadata::decode(&mut Cursor::new(&image_data))?;
+		let data = Metadata::decode(&mut Cursor::new(&image_data), &mut Vec::new())?;
 
 		for ifd in data.1
 		{
@@ -379,4 +810,36 @@ use super::Metadata;
 
 		Ok(())
 	}
+
+	/// Decoding corrupted or truncated data must never panic, only return an
+	/// error (or skip the affected parts)
+	#[test]
+	fn
+	decode_corrupted_data()
+	{
+		let samples = [
+			crate::jpg::read_metadata(&read("tests/read_sample.jpg").unwrap()).unwrap(),
+			read("tests/read_sample.tif").unwrap(),
+		];
+
+		for exif_data in samples
+		{
+			for length in 0..exif_data.len()
+			{
+				let truncated = exif_data[..length].to_vec();
+				let _ = Metadata::decode(&mut Cursor::new(&truncated), &mut Vec::new());
+			}
+
+			for position in 0..exif_data.len()
+			{
+				for value in [0x00, 0x01, 0x7f, 0xff]
+				{
+					let mut corrupted = exif_data.clone();
+					let end = (position + 4).min(corrupted.len());
+					corrupted[position..end].fill(value);
+					let _ = Metadata::decode(&mut Cursor::new(&corrupted), &mut Vec::new());
+				}
+			}
+		}
+	}
 }
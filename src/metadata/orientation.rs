@@ -0,0 +1,159 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The values of the `Orientation` tag, describing how the stored image data
+/// needs to be transformed in order to be displayed correctly. The names
+/// follow the transformation that needs to be applied.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Orientation
+{
+	/// 1: No transformation required
+	Normal,
+
+	/// 2: Mirror horizontally
+	MirrorHorizontal,
+
+	/// 3: Rotate by 180 degrees
+	Rotate180,
+
+	/// 4: Mirror vertically
+	MirrorVertical,
+
+	/// 5: Mirror horizontally, then rotate by 270 degrees clockwise
+	MirrorHorizontalRotate270CW,
+
+	/// 6: Rotate by 90 degrees clockwise
+	Rotate90CW,
+
+	/// 7: Mirror horizontally, then rotate by 90 degrees clockwise
+	MirrorHorizontalRotate90CW,
+
+	/// 8: Rotate by 270 degrees clockwise
+	Rotate270CW,
+}
+
+impl
+Orientation
+{
+	/// Gets the orientation for the value of the `Orientation` tag. Returns
+	/// `None` for values outside of the range 1 to 8.
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Option<Orientation>
+	{
+		match value
+		{
+			1 => Some(Orientation::Normal),
+			2 => Some(Orientation::MirrorHorizontal),
+			3 => Some(Orientation::Rotate180),
+			4 => Some(Orientation::MirrorVertical),
+			5 => Some(Orientation::MirrorHorizontalRotate270CW),
+			6 => Some(Orientation::Rotate90CW),
+			7 => Some(Orientation::MirrorHorizontalRotate90CW),
+			8 => Some(Orientation::Rotate270CW),
+			_ => None,
+		}
+	}
+
+	/// Gets the value of the `Orientation` tag for this orientation
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			Orientation::Normal                      => 1,
+			Orientation::MirrorHorizontal            => 2,
+			Orientation::Rotate180                   => 3,
+			Orientation::MirrorVertical              => 4,
+			Orientation::MirrorHorizontalRotate270CW => 5,
+			Orientation::Rotate90CW                  => 6,
+			Orientation::MirrorHorizontalRotate90CW  => 7,
+			Orientation::Rotate270CW                 => 8,
+		}
+	}
+
+	/// Gets the transformation that is required to display the image
+	/// correctly: Whether it needs to be mirrored horizontally (first) and by
+	/// how many degrees it needs to be rotated clockwise (second).
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::orientation::Orientation;
+	///
+	/// assert_eq!(Orientation::Rotate90CW.transform_needed(),     (false, 90));
+	/// assert_eq!(Orientation::MirrorVertical.transform_needed(), (true, 180));
+	/// ```
+	pub fn
+	transform_needed
+	(
+		&self
+	)
+	-> (bool, u16)
+	{
+		match self
+		{
+			Orientation::Normal                      => (false,   0),
+			Orientation::MirrorHorizontal            => (true,    0),
+			Orientation::Rotate180                   => (false, 180),
+			Orientation::MirrorVertical              => (true,  180),
+			Orientation::MirrorHorizontalRotate270CW => (true,  270),
+			Orientation::Rotate90CW                  => (false,  90),
+			Orientation::MirrorHorizontalRotate90CW  => (true,   90),
+			Orientation::Rotate270CW                 => (false, 270),
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the orientation of the image from the `Orientation` tag.
+	/// Returns `None` if the tag is absent or its value is out of range.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(orientation) = metadata.get_orientation()
+	/// {
+	///     let (mirror, degrees) = orientation.transform_needed();
+	/// }
+	/// ```
+	pub fn
+	get_orientation
+	(
+		&self
+	)
+	-> Option<Orientation>
+	{
+		match self.get_tag(&ExifTag::Orientation(Vec::new())).next()?
+		{
+			ExifTag::Orientation(values) => Orientation::from_u16(*values.first()?),
+			_                            => None,
+		}
+	}
+
+	/// Sets the `Orientation` tag to the given orientation
+	pub fn
+	set_orientation
+	(
+		&mut self,
+		orientation: Orientation
+	)
+	{
+		self.set_tag_unchecked(ExifTag::Orientation(vec![orientation.as_u16()]));
+	}
+}
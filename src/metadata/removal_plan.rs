@@ -0,0 +1,71 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+use std::ops::Range;
+
+/// Describes a part of an image file that `Metadata::clear_metadata` would
+/// remove, see `Metadata::plan_clear_metadata`. The name is the type of the
+/// segment, chunk or box (e.g. "APP1" for JPEG or "eXIf" for PNG) and the
+/// range covers all of its bytes, including e.g. length fields and CRCs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+RemovalPlan
+{
+	pub name:  String,
+	pub range: Range<usize>,
+}
+
+impl
+RemovalPlan
+{
+	pub(crate) fn
+	new
+	(
+		name:  &str,
+		range: Range<usize>
+	)
+	-> RemovalPlan
+	{
+		return RemovalPlan { name: name.to_string(), range };
+	}
+
+	/// Gets the number of bytes that would be removed
+	pub fn
+	len
+	(
+		&self
+	)
+	-> usize
+	{
+		return self.range.len();
+	}
+
+	/// Checks if no bytes would be removed
+	pub fn
+	is_empty
+	(
+		&self
+	)
+	-> bool
+	{
+		return self.range.is_empty();
+	}
+}
+
+impl
+fmt::Display
+for
+RemovalPlan
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		write!(f, "{} at {:#x}..{:#x} ({} bytes)", self.name, self.range.start, self.range.end, self.len())
+	}
+}
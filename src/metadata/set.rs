@@ -1,13 +1,51 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use crate::endian::Endian;
 use crate::exif_tag::ExifTag;
 
 use super::Metadata;
 
+/// The result of `Metadata::set_tags`, i.e. how many of the given tags got
+/// added to an IFD and how many replaced an existing tag with the same id.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct
+SetTagsSummary
+{
+	pub added:    usize,
+	pub replaced: usize,
+}
+
 impl
 Metadata
 {
+	/// Sets the endianness that is used when encoding the metadata, e.g. for
+	/// writing a big endian EXIF block read from a camera file back as little
+	/// endian. As the tag values are stored as actual values (and not as 
+	/// bytes) in the struct, all multi-byte values (e.g. `INT16U`, `INT32U`,
+	/// the numerators and denominators of rationals, ...) are automatically
+	/// encoded using the new byte order. Single byte and `STRING` values are
+	/// not affected. Note that the contents of `UNDEF` tags (e.g. `MakerNote`)
+	/// are opaque to little_exif and thus can't be converted.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::endian::Endian;
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_endian(Endian::Little);
+	/// ```
+	pub fn
+	set_endian
+	(
+		&mut self,
+		endian: Endian
+	)
+	{
+		self.endian = endian;
+	}
+
 	/// Sets the tag in the metadata struct. Tries to determine what IFD the 
 	/// tag belongs to and should be inserted into, starting with IFD0.
 	/// If the tag should e.g. be inserted into IFD0's EXIF SubIFD and that does
@@ -16,13 +54,81 @@ Metadata
 	/// For more fine-control (e.g. when handling multi-page TIFFs) it is 
 	/// strongly advised to instead first get a mutable reference to the 
 	/// preferred IFD and calling `set_tag` on that one instead. 
+	/// Returns an error if the format of the tag does not match the format
+	/// required for its hex value, see `ImageFileDirectory::set_tag`.
 	pub fn
 	set_tag
 	(
 		&mut self,
 		input_tag: ExifTag
 	)
+	-> Result<(), std::io::Error>
+	{
+		return self.get_ifd_mut(input_tag.get_group(), 0).set_tag(input_tag);
+	}
+
+	/// Same as `set_tag`, but does not check the format of the tag. Intended
+	/// for advanced users who intentionally write non-standard formats.
+	pub fn
+	set_tag_unchecked
+	(
+		&mut self,
+		input_tag: ExifTag
+	)
+	{
+		self.get_ifd_mut(input_tag.get_group(), 0).set_tag_unchecked(input_tag);
+	}
+
+	/// Sets all of the given tags, each in the IFD of its group that is 
+	/// associated with IFD0 (see `set_tag`). Existing tags with the same hex
+	/// value get replaced. Returns how many tags were added and replaced.
+	/// Returns an error if the format of a tag does not match the format
+	/// required for its hex value. The tags preceding this tag are set 
+	/// nonetheless.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::Make("Canon".to_string())).unwrap();
+	///
+	/// let summary = metadata.set_tags(vec![
+	///     ExifTag::Make("Nikon".to_string()),
+	///     ExifTag::ISO(vec![100]),
+	///     ExifTag::GPSAltitudeRef(vec![0]),
+	/// ]).unwrap();
+	/// assert_eq!((summary.added, summary.replaced), (2, 1));
+	/// ```
+	pub fn
+	set_tags
+	(
+		&mut self,
+		tags: impl IntoIterator<Item = ExifTag>
+	)
+	-> Result<SetTagsSummary, std::io::Error>
 	{
-		self.get_ifd_mut(input_tag.get_group(), 0).set_tag(input_tag);
+		let mut summary = SetTagsSummary::default();
+
+		for tag in tags
+		{
+			let exists = self.get_ifd(tag.get_group(), 0)
+				.map(|ifd| ifd.get_tags().iter().any(|existing_tag| existing_tag.as_u16() == tag.as_u16()))
+				.unwrap_or(false);
+
+			self.set_tag(tag)?;
+
+			if exists
+			{
+				summary.replaced += 1;
+			}
+			else
+			{
+				summary.added += 1;
+			}
+		}
+
+		return Ok(summary);
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,193 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+use super::warning::MetadataWarning;
+
+// The thumbnail is stored in IFD1
+const THUMBNAIL_GENERIC_IFD_NR: u32 = 1;
+
+// Compression value for JPEG compressed thumbnails
+const JPEG_COMPRESSION: u16 = 6;
+
+/// Determines what happens with the thumbnail stored in IFD1 if the encoded
+/// metadata exceeds the size limit of the file format, e.g. the 64KB of a
+/// JPEG APP1 segment.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum
+ThumbnailPolicy
+{
+	/// Keeps the thumbnail, so writing the metadata fails if it is too large.
+	#[default]
+	Keep,
+
+	/// Removes the thumbnail and its tags from IFD1 and encodes the metadata
+	/// again. This is reported via `MetadataWarning::ThumbnailDropped`.
+	DropIfTooLarge,
+}
+
+impl
+Metadata
+{
+	/// Gets the bytes of the embedded JPEG thumbnail that is located via the
+	/// `ThumbnailOffset` (JPEGInterchangeFormat) and `ThumbnailLength`
+	/// (JPEGInterchangeFormatLength) tags in IFD1. The data gets read in
+	/// while decoding the metadata.
+	/// Returns `None` if there is no IFD1 or no thumbnail stored in it.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(thumbnail) = metadata.get_thumbnail()
+	/// {
+	///     std::fs::write("thumbnail.jpg", thumbnail).unwrap();
+	/// }
+	/// ```
+	pub fn
+	get_thumbnail
+	(
+		&self
+	)
+	-> Option<Vec<u8>>
+	{
+		let ifd = self.get_ifd(ExifTagGroup::GENERIC, THUMBNAIL_GENERIC_IFD_NR)?;
+
+		for tag in ifd.get_tags()
+		{
+			if let ExifTag::ThumbnailOffset(_, thumbnail_data) = tag
+			{
+				if thumbnail_data.is_empty()
+				{
+					return None;
+				}
+				return Some(thumbnail_data.clone());
+			}
+		}
+
+		return None;
+	}
+
+	/// Sets the embedded JPEG thumbnail by storing the given bytes in IFD1
+	/// and updating the `ThumbnailOffset` and `ThumbnailLength` tags. The
+	/// offset gets computed when encoding the metadata.
+	/// If IFD1 does not specify a `Compression` yet, it gets set to 6 (JPEG)
+	/// as required by the specification for JPEG thumbnails.
+	pub fn
+	set_thumbnail
+	(
+		&mut self,
+		jpeg: &[u8]
+	)
+	{
+		let ifd = self.get_ifd_mut(ExifTagGroup::GENERIC, THUMBNAIL_GENERIC_IFD_NR);
+
+		if !ifd.get_tags().iter().any(|tag| tag.as_u16() == ExifTag::Compression(Vec::new()).as_u16())
+		{
+			ifd.set_tag_unchecked(ExifTag::Compression(vec![JPEG_COMPRESSION]));
+		}
+
+		ifd.set_tag_unchecked(ExifTag::ThumbnailOffset(Vec::new(), jpeg.to_vec()));
+		ifd.set_tag_unchecked(ExifTag::ThumbnailLength(vec![jpeg.len() as u32]));
+	}
+
+	/// Removes the embedded JPEG thumbnail by removing the `ThumbnailOffset`
+	/// and `ThumbnailLength` tags from IFD1 and returns the thumbnail bytes.
+	/// If IFD1 is empty afterwards, it gets removed as well.
+	/// Returns `None` if there is no thumbnail stored in IFD1.
+	pub fn
+	remove_thumbnail
+	(
+		&mut self
+	)
+	-> Option<Vec<u8>>
+	{
+		let thumbnail = self.get_thumbnail();
+
+		self.remove_tag_by_hex(
+			ExifTag::ThumbnailLength(Vec::new()).as_u16(),
+			ExifTagGroup::GENERIC,
+			THUMBNAIL_GENERIC_IFD_NR,
+			false
+		);
+		self.remove_tag_by_hex(
+			ExifTag::ThumbnailOffset(Vec::new(), Vec::new()).as_u16(),
+			ExifTagGroup::GENERIC,
+			THUMBNAIL_GENERIC_IFD_NR,
+			false
+		);
+
+		return thumbnail;
+	}
+
+	/// Sets what happens with the thumbnail if the encoded metadata exceeds
+	/// the size limit of the file format. See `ThumbnailPolicy` for details.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::thumbnail::ThumbnailPolicy;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_thumbnail_policy(ThumbnailPolicy::DropIfTooLarge);
+	/// ```
+	pub fn
+	set_thumbnail_policy
+	(
+		&mut self,
+		policy: ThumbnailPolicy
+	)
+	{
+		self.thumbnail_policy = policy;
+	}
+
+	/// Gets what happens with the thumbnail if the encoded metadata exceeds
+	/// the size limit of the file format.
+	pub fn
+	get_thumbnail_policy
+	(
+		&self
+	)
+	-> ThumbnailPolicy
+	{
+		self.thumbnail_policy
+	}
+
+	/// Encodes the metadata like `encode`. If the result exceeds the given
+	/// size limit and the thumbnail policy allows it, the thumbnail gets
+	/// dropped and the metadata is encoded again without it.
+	pub(crate) fn
+	encode_with_size_limit
+	(
+		&self,
+		size_limit: usize,
+		warnings:   &mut Vec<MetadataWarning>
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		let mut encoding_warnings = Vec::new();
+		let encoded = self.encode_with_warnings(&mut encoding_warnings)?;
+
+		if encoded.len() <= size_limit || self.thumbnail_policy == ThumbnailPolicy::Keep
+		{
+			warnings.extend(encoding_warnings);
+			return Ok(encoded);
+		}
+
+		let mut without_thumbnail = self.clone();
+		match without_thumbnail.remove_thumbnail()
+		{
+			Some(thumbnail) => warnings.push(MetadataWarning::ThumbnailDropped { size: thumbnail.len() }),
+			None            => {
+				warnings.extend(encoding_warnings);
+				return Ok(encoded);
+			},
+		}
+
+		return without_thumbnail.encode_with_warnings(warnings);
+	}
+}
@@ -0,0 +1,170 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::util::decode_utf16;
+use crate::util::encode_utf16;
+
+use super::Metadata;
+
+// The first 8 bytes of the UserComment tag identify the character code
+const ASCII_PREFIX:     [u8; 8] = [0x41, 0x53, 0x43, 0x49, 0x49, 0x00, 0x00, 0x00]; // "ASCII"
+const UNICODE_PREFIX:   [u8; 8] = [0x55, 0x4e, 0x49, 0x43, 0x4f, 0x44, 0x45, 0x00]; // "UNICODE"
+const UNDEFINED_PREFIX: [u8; 8] = [0x00; 8];
+
+/// The character codes for writing the `UserComment` tag, see
+/// `Metadata::set_user_comment_with_charset`. The JIS character code is not
+/// supported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+UserCommentCharset
+{
+	/// Only for comments consisting of ASCII characters
+	Ascii,
+
+	/// UTF-16, using the endianness of the metadata
+	Unicode,
+
+	/// No character code is given, the comment is stored as UTF-8
+	Undefined,
+}
+
+/// Removes the padding (NUL characters and spaces) some cameras add to fill
+/// up the space reserved for the comment
+fn
+trim_padding
+(
+	comment: &str
+)
+-> String
+{
+	return comment.trim_end_matches(|character| character == '\0' || character == ' ').to_string();
+}
+
+impl
+Metadata
+{
+	/// Gets the comment stored in the `UserComment` tag, decoded according
+	/// to the character code given by its first 8 bytes: ASCII, Unicode
+	/// (UTF-16, using the endianness of the metadata) or undefined (decoded
+	/// as UTF-8). Trailing NUL characters and spaces are removed.
+	/// Returns `None` if the tag is absent, uses the JIS character code or
+	/// can't be decoded.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_user_comment("Taken at dawn");
+	/// assert_eq!(metadata.get_user_comment(), Some("Taken at dawn".to_string()));
+	/// ```
+	pub fn
+	get_user_comment
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		let bytes = match self.get_tag(&ExifTag::UserComment(Vec::new())).next()?
+		{
+			ExifTag::UserComment(bytes) => bytes,
+			_                           => return None,
+		};
+
+		if bytes.len() < 8
+		{
+			return None;
+		}
+
+		let (prefix, comment) = bytes.split_at(8);
+
+		let decoded = if prefix == ASCII_PREFIX
+		{
+			if !comment.is_ascii()
+			{
+				return None;
+			}
+			String::from_utf8(comment.to_vec()).ok()?
+		}
+		else if prefix == UNICODE_PREFIX
+		{
+			decode_utf16(comment, &self.endian)?
+		}
+		else if prefix == UNDEFINED_PREFIX
+		{
+			String::from_utf8(comment.to_vec()).ok()?
+		}
+		else
+		{
+			return None;
+		};
+
+		return Some(trim_padding(&decoded));
+	}
+
+	/// Sets the `UserComment` tag to the given comment, using the ASCII
+	/// character code. If the comment contains non-ASCII characters, the
+	/// Unicode character code is used instead.
+	pub fn
+	set_user_comment
+	(
+		&mut self,
+		comment: &str
+	)
+	{
+		let charset = match comment.is_ascii()
+		{
+			true  => UserCommentCharset::Ascii,
+			false => UserCommentCharset::Unicode,
+		};
+
+		// Can't fail, as the ASCII character code is only used for ASCII
+		self.set_user_comment_with_charset(comment, charset).unwrap();
+	}
+
+	/// Sets the `UserComment` tag to the given comment, using the given
+	/// character code. Returns an error if the ASCII character code is
+	/// requested for a comment containing non-ASCII characters.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::user_comment::UserCommentCharset;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_user_comment_with_charset("Grüße", UserCommentCharset::Unicode).unwrap();
+	/// assert_eq!(metadata.get_user_comment(), Some("Grüße".to_string()));
+	/// assert!(metadata.set_user_comment_with_charset("Grüße", UserCommentCharset::Ascii).is_err());
+	/// ```
+	pub fn
+	set_user_comment_with_charset
+	(
+		&mut self,
+		comment: &str,
+		charset: UserCommentCharset
+	)
+	-> Result<(), std::io::Error>
+	{
+		let mut bytes = match charset
+		{
+			UserCommentCharset::Ascii     => ASCII_PREFIX.to_vec(),
+			UserCommentCharset::Unicode   => UNICODE_PREFIX.to_vec(),
+			UserCommentCharset::Undefined => UNDEFINED_PREFIX.to_vec(),
+		};
+
+		match charset
+		{
+			UserCommentCharset::Ascii if !comment.is_ascii()
+				=> return io_error!(InvalidInput, "UserComment with ASCII character code must only contain ASCII characters!"),
+			UserCommentCharset::Unicode
+				=> bytes.extend(encode_utf16(comment, &self.endian)),
+			_
+				=> bytes.extend(comment.as_bytes()),
+		}
+
+		self.set_tag_unchecked(ExifTag::UserComment(bytes));
+		return Ok(());
+	}
+}
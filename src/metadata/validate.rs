@@ -0,0 +1,217 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+
+use crate::exif_datetime::ExifDateTime;
+use crate::exif_tag::ExifTag;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+
+use super::Metadata;
+
+/// How severe a `ValidationIssue` is. Warnings describe metadata most readers
+/// can cope with (e.g. a missing version tag), errors describe values that
+/// are invalid according to the EXIF specification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum
+ValidationSeverity
+{
+	Warning,
+	Error,
+}
+
+/// An issue found by `Metadata::validate`. The tag is the hex value of the
+/// offending tag, or `None` if the issue concerns the IFD as a whole.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+ValidationIssue
+{
+	pub severity:       ValidationSeverity,
+	pub group:          ExifTagGroup,
+	pub generic_ifd_nr: u32,
+	pub tag:            Option<u16>,
+	pub message:        String,
+}
+
+impl
+fmt::Display
+for
+ValidationIssue
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		write!(f, "{:?}: ", self.severity)?;
+		if let Some(hex) = self.tag
+		{
+			write!(f, "Tag {:#06x} in ", hex)?;
+		}
+		write!(f, "{:?} IFD {}: {}", self.group, self.generic_ifd_nr, self.message)
+	}
+}
+
+/// The tag each (non-empty) IFD of the given group must contain
+fn
+mandatory_tag
+(
+	group: ExifTagGroup
+)
+-> Option<(u16, &'static str)>
+{
+	match group
+	{
+		ExifTagGroup::EXIF    => Some((0x9000, "ExifVersion")),
+		ExifTagGroup::INTEROP => Some((0x0001, "InteroperabilityIndex")),
+		ExifTagGroup::GPS     => Some((0x0000, "GPSVersionID")),
+		_                     => None,
+	}
+}
+
+/// Checks the value of a single tag, returning the severity and message of
+/// each issue found
+fn
+validate_tag
+(
+	tag: &ExifTag
+)
+-> Vec<(ValidationSeverity, String)>
+{
+	let mut issues = Vec::new();
+
+	if !tag.unknown_is_justified()
+	{
+		if let Ok(known_tag) = ExifTag::from_u16(tag.as_u16(), &tag.get_group())
+		{
+			issues.push((ValidationSeverity::Warning, format!(
+				"{} has format {:?} instead of {:?}",
+				known_tag.name(),
+				tag.format(),
+				known_tag.format()
+			)));
+		}
+	}
+
+	let zero_denominator = match (tag.as_rational_vec(), tag.as_srational_vec())
+	{
+		(Some(rationals), _) => rationals.iter().any(|(_, denominator)| *denominator == 0),
+		(_, Some(rationals)) => rationals.iter().any(|(_, denominator)| *denominator == 0),
+		_                    => false,
+	};
+	if zero_denominator
+	{
+		issues.push((ValidationSeverity::Error, format!("{} contains a rational with a denominator of zero", tag.name())));
+	}
+
+	match tag
+	{
+		ExifTag::ModifyDate(datetime)       |
+		ExifTag::DateTimeOriginal(datetime) |
+		ExifTag::CreateDate(datetime)
+		if ExifDateTime::from_exif_string(datetime).is_none()
+			=> issues.push((ValidationSeverity::Error, format!(
+				"{} value {:?} is not of the form \"YYYY:MM:DD HH:MM:SS\"",
+				tag.name(),
+				datetime.trim_end_matches('\0')
+			))),
+
+		ExifTag::Orientation(values)
+		if values.len() != 1 || !(1..=8).contains(&values[0])
+			=> issues.push((ValidationSeverity::Error, format!(
+				"Orientation value {:?} is not a single value between 1 and 8",
+				values
+			))),
+
+		_ => (),
+	}
+
+	return issues;
+}
+
+fn
+validate_ifd
+(
+	ifd:    &ImageFileDirectory,
+	issues: &mut Vec<ValidationIssue>
+)
+{
+	let group          = ifd.get_ifd_type();
+	let generic_ifd_nr = ifd.get_generic_ifd_nr();
+
+	if let Some((hex, name)) = mandatory_tag(group)
+	{
+		if !ifd.get_tags().is_empty() && !ifd.get_tags().iter().any(|tag| tag.as_u16() == hex)
+		{
+			issues.push(ValidationIssue {
+				severity: ValidationSeverity::Warning,
+				group,
+				generic_ifd_nr,
+				tag:      None,
+				message:  format!("Mandatory tag {} is missing", name),
+			});
+		}
+	}
+
+	for tag in ifd.get_tags()
+	{
+		for (severity, message) in validate_tag(tag)
+		{
+			issues.push(ValidationIssue {
+				severity,
+				group,
+				generic_ifd_nr,
+				tag: Some(tag.as_u16()),
+				message,
+			});
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Checks the metadata for conformance with the EXIF specification and
+	/// returns the issues found, in the order of the IFDs and their tags.
+	/// Currently checked are:
+	/// - mandatory tags of the EXIF, Interop and GPS IFDs (e.g. a GPS IFD
+	///   without `GPSVersionID`)
+	/// - known tags stored using a format other than the specified one
+	/// - rationals with a denominator of zero
+	/// - the format of the `ModifyDate`, `DateTimeOriginal` and `CreateDate`
+	///   tags
+	/// - `Orientation` values outside of the range 1 to 8
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::validate::ValidationSeverity;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::Orientation(vec![9])).unwrap();
+	///
+	/// let issues = metadata.validate();
+	/// assert_eq!(issues.len(), 1);
+	/// assert_eq!(issues[0].severity, ValidationSeverity::Error);
+	/// assert_eq!(issues[0].tag,      Some(0x0112));
+	/// ```
+	pub fn
+	validate
+	(
+		&self
+	)
+	-> Vec<ValidationIssue>
+	{
+		let mut issues = Vec::new();
+		for ifd in self.get_ifds()
+		{
+			validate_ifd(ifd, &mut issues);
+		}
+		return issues;
+	}
+}
@@ -0,0 +1,119 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+
+use crate::exif_tag_format::ExifTagFormat;
+use crate::ifd::ExifTagGroup;
+
+/// Describes a recoverable issue that was encountered while reading or
+/// writing the metadata of a file, see `Metadata::new_from_path_with_warnings`
+/// and `Metadata::write_to_vec_with_warnings`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum
+MetadataWarning
+{
+	/// A tag that is not known to little_exif. It is kept as one of the
+	/// `Unknown...` tags.
+	UnknownTag
+	{
+		hex:   u16,
+		group: ExifTagGroup,
+	},
+
+	/// A known tag that is stored using a format different from the one
+	/// given by the specification. The value got converted if possible.
+	UnexpectedFormat
+	{
+		hex:      u16,
+		group:    ExifTagGroup,
+		expected: ExifTagFormat,
+		found:    ExifTagFormat,
+	},
+
+	/// An IFD that could not be decoded as its offset points past the end of
+	/// the data. The IFD and its tags got skipped.
+	SkippedIfd
+	{
+		group:  ExifTagGroup,
+		offset: u32,
+	},
+
+	/// Image data (strips or thumbnail) referenced by the tag with the given
+	/// hex value that could not be read, e.g. as it is located past the end
+	/// of the data. The offsets of strips are kept as plain values, a 
+	/// thumbnail got skipped.
+	UnreadableImageData
+	{
+		hex:   u16,
+		group: ExifTagGroup,
+	},
+
+	/// A PNG chunk whose CRC does not match its contents. The chunk got read
+	/// nonetheless.
+	CrcMismatch(String),
+
+	/// A PNG that ends without an IEND chunk, e.g. due to a truncated 
+	/// download. The chunks located before the point of truncation got read
+	/// nonetheless, see `PngReadOptions::tolerate_missing_IEND`.
+	MissingIend,
+
+	/// The thumbnail of the given size in bytes got removed while writing
+	/// as the encoded metadata would have exceeded the size limit of the
+	/// file format, see `ThumbnailPolicy::DropIfTooLarge`.
+	ThumbnailDropped
+	{
+		size: usize,
+	},
+
+	/// The MakerNote got moved from the old to the new offset (relative to
+	/// the start of the TIFF data) while writing and its internal offsets
+	/// could not be adjusted, so they may be stale.
+	/// See `MakerNoteHandling` for details.
+	StaleMakerNoteOffsets
+	{
+		old_offset: u32,
+		new_offset: u32,
+	},
+
+	/// The metadata could not be decoded at all, resulting in an empty
+	/// `Metadata` struct.
+	DecodingFailed(String),
+}
+
+impl
+fmt::Display
+for
+MetadataWarning
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter
+	)
+	-> fmt::Result
+	{
+		match self
+		{
+			MetadataWarning::UnknownTag { hex, group }
+				=> write!(f, "Unknown tag {:#06x} in {:?} IFD", hex, group),
+			MetadataWarning::UnexpectedFormat { hex, group, expected, found }
+				=> write!(f, "Tag {:#06x} in {:?} IFD has format {:?} instead of {:?}", hex, group, found, expected),
+			MetadataWarning::SkippedIfd { group, offset }
+				=> write!(f, "Skipped {:?} IFD at offset {} as it is located past the end of the data", group, offset),
+			MetadataWarning::UnreadableImageData { hex, group }
+				=> write!(f, "Could not read the image data referenced by tag {:#06x} in {:?} IFD", hex, group),
+			MetadataWarning::CrcMismatch(message)
+				=> write!(f, "{}", message),
+			MetadataWarning::MissingIend
+				=> write!(f, "PNG ends without an IEND chunk, possibly truncated"),
+			MetadataWarning::ThumbnailDropped { size }
+				=> write!(f, "Dropped thumbnail of {} bytes as the metadata exceeds the size limit", size),
+			MetadataWarning::StaleMakerNoteOffsets { old_offset, new_offset }
+				=> write!(f, "MakerNote moved from offset {} to {}, its internal offsets may be stale", old_offset, new_offset),
+			MetadataWarning::DecodingFailed(message)
+				=> write!(f, "Could not decode metadata: {}", message),
+		}
+	}
+}
@@ -0,0 +1,181 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::util::decode_utf16;
+use crate::util::encode_utf16;
+
+use super::Metadata;
+
+/// Decodes the UTF-16LE bytes of a Windows XP tag into a string, dropping
+/// the trailing null character(s). Returns `None` if the number of bytes is
+/// odd or the data is not valid UTF-16.
+fn
+decode_utf16le
+(
+	bytes: &[u8]
+)
+-> Option<String>
+{
+	let value = decode_utf16(bytes, &Endian::Little)?;
+	return Some(value.trim_end_matches('\0').to_string());
+}
+
+/// Encodes a string as UTF-16LE bytes, terminated by a double-null
+fn
+encode_utf16le
+(
+	value: &str
+)
+-> Vec<u8>
+{
+	let mut bytes = encode_utf16(value, &Endian::Little);
+	bytes.extend([0, 0]);
+
+	return bytes;
+}
+
+impl
+Metadata
+{
+	fn
+	get_xp_string
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> Option<String>
+	{
+		match self.get_tag(tag).next()?
+		{
+			ExifTag::XPTitle(bytes)    |
+			ExifTag::XPComment(bytes)  |
+			ExifTag::XPAuthor(bytes)   |
+			ExifTag::XPKeywords(bytes) |
+			ExifTag::XPSubject(bytes)  => decode_utf16le(bytes),
+			_                          => None,
+		}
+	}
+
+	/// Gets the title stored in the UTF-16LE encoded `XPTitle` tag, which is
+	/// e.g. shown by the Windows Explorer. Returns `None` if the tag is absent
+	/// or can't be decoded.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_xp_title("Sunset");
+	/// assert_eq!(metadata.get_xp_title(), Some("Sunset".to_string()));
+	/// ```
+	pub fn
+	get_xp_title
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		return self.get_xp_string(&ExifTag::XPTitle(Vec::new()));
+	}
+
+	/// Sets the `XPTitle` tag to the UTF-16LE encoding of the given title
+	pub fn
+	set_xp_title
+	(
+		&mut self,
+		title: &str
+	)
+	{
+		self.set_tag_unchecked(ExifTag::XPTitle(encode_utf16le(title)));
+	}
+
+	/// Gets the comment stored in the `XPComment` tag, see `get_xp_title`
+	pub fn
+	get_xp_comment
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		return self.get_xp_string(&ExifTag::XPComment(Vec::new()));
+	}
+
+	/// Sets the `XPComment` tag to the UTF-16LE encoding of the given comment
+	pub fn
+	set_xp_comment
+	(
+		&mut self,
+		comment: &str
+	)
+	{
+		self.set_tag_unchecked(ExifTag::XPComment(encode_utf16le(comment)));
+	}
+
+	/// Gets the author stored in the `XPAuthor` tag, see `get_xp_title`
+	pub fn
+	get_xp_author
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		return self.get_xp_string(&ExifTag::XPAuthor(Vec::new()));
+	}
+
+	/// Sets the `XPAuthor` tag to the UTF-16LE encoding of the given author
+	pub fn
+	set_xp_author
+	(
+		&mut self,
+		author: &str
+	)
+	{
+		self.set_tag_unchecked(ExifTag::XPAuthor(encode_utf16le(author)));
+	}
+
+	/// Gets the keywords stored in the `XPKeywords` tag, see `get_xp_title`
+	pub fn
+	get_xp_keywords
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		return self.get_xp_string(&ExifTag::XPKeywords(Vec::new()));
+	}
+
+	/// Sets the `XPKeywords` tag to the UTF-16LE encoding of the given keywords
+	pub fn
+	set_xp_keywords
+	(
+		&mut self,
+		keywords: &str
+	)
+	{
+		self.set_tag_unchecked(ExifTag::XPKeywords(encode_utf16le(keywords)));
+	}
+
+	/// Gets the subject stored in the `XPSubject` tag, see `get_xp_title`
+	pub fn
+	get_xp_subject
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		return self.get_xp_string(&ExifTag::XPSubject(Vec::new()));
+	}
+
+	/// Sets the `XPSubject` tag to the UTF-16LE encoding of the given subject
+	pub fn
+	set_xp_subject
+	(
+		&mut self,
+		subject: &str
+	)
+	{
+		self.set_tag_unchecked(ExifTag::XPSubject(encode_utf16le(subject)));
+	}
+}
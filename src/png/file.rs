@@ -11,17 +11,29 @@ use std::fs::File;
 use crc::Crc;
 use crc::CRC_32_ISO_HDLC;
 use miniz_oxide::deflate::compress_to_vec_zlib;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
 
 use crate::general_file_io::*;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
 
 use super::PNG_SIGNATURE;
+use super::PngReadOptions;
+use super::PngTextMode;
+use super::PngWriteOptions;
 use super::RAW_PROFILE_TYPE_EXIF;
+use super::RAW_PROFILE_TYPE_EXIF_TEXT;
 
 use super::png_chunk::PngChunk;
-use super::decode_metadata_png;
+use super::vec::encode_pHYs_chunk;
+use super::vec::encode_tIME_chunk;
+use super::vec::get_position_after_IHDR;
+use super::vec::replace_or_insert_chunk;
+use super::get_chunk_descriptors;
+use super::decode_raw_profile_exif_chunk;
+use super::encode_orNT_orientation;
+use super::is_raw_profile_exif_chunk;
 use super::encode_metadata_png;
+use super::get_metadata_insert_position;
 
 fn
 check_signature
@@ -34,7 +46,7 @@ check_signature
 	
 	// Check the signature
 	let mut signature_buffer = [0u8; 8];
-	file.read(&mut signature_buffer).unwrap();
+	file.read(&mut signature_buffer)?;
 	let signature_is_valid = signature_buffer.iter()
 		.zip(PNG_SIGNATURE.iter())
 		.filter(|&(read, constant)| read == constant)
@@ -42,90 +54,17 @@ check_signature
 
 	if !signature_is_valid
 	{
-		return io_error!(InvalidData, "Can't open PNG file - Wrong signature!");
+		return little_exif_error!(InvalidSignature, "Can't open PNG file - Wrong signature!");
 	}
 
 	// Signature is valid - can proceed using the file as PNG file
 	return Ok(file);
 }
 
-// TODO: Check if this is also affected by endianness
-// Edit: Should... not? I guess?
-fn
-get_next_chunk_descriptor
-(
-	file: &mut File
-)
--> Result<PngChunk, std::io::Error>
-{
-	// Read the start of the chunk
-	let mut chunk_start = [0u8; 8];
-	let mut bytes_read = file.read(&mut chunk_start).unwrap();
-
-	// Check that indeed 8 bytes were read
-	if bytes_read != 8
-	{
-		return io_error!(Other, "Could not read start of chunk");
-	}
-
-	// Construct name of chunk and its length
-	let chunk_name = String::from_utf8((&chunk_start[4..8]).to_vec());
-	let mut chunk_length = 0u32;
-	for byte in &chunk_start[0..4]
-	{
-		chunk_length = chunk_length * 256 + *byte as u32;
-	}
-
-	// Read chunk data ...
-	let mut chunk_data_buffer = vec![0u8; chunk_length as usize];
-	bytes_read = file.read(&mut chunk_data_buffer).unwrap();
-	if bytes_read != chunk_length as usize
-	{
-		return io_error!(Other, "Could not read chunk data");
-	}
-
-	// ... and CRC values
-	let mut chunk_crc_buffer = [0u8; 4];
-	bytes_read = file.read(&mut chunk_crc_buffer).unwrap();
-	if bytes_read != 4
-	{
-		return io_error!(Other, "Could not read chunk CRC");
-	}
-
-	// Compute CRC on chunk
-	let mut crc_input = Vec::new();
-	crc_input.extend(chunk_start[4..8].iter());
-	crc_input.extend(chunk_data_buffer.iter());
-
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&crc_input) as u32;
-
-	for i in 0..4
-	{
-		if ((checksum >> (8 * (3-i))) as u8) != chunk_crc_buffer[i]
-		{
-			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
-		}
-	}
-
-	// If validating the chunk using the CRC was successful, return its descriptor
-	// Note: chunk_length does NOT include the +4 for the CRC area!
-	if let Ok(png_chunk) = PngChunk::from_string(
-		&chunk_name.unwrap(),
-		chunk_length
-	)
-	{
-		return Ok(png_chunk);
-	}
-	else
-	{
-		return io_error!(Other, "Invalid PNG chunk name");
-	}
-}
-
 /// "Parses" the PNG by checking various properties:
 /// - Can the file be opened and is the signature valid?
-/// - Are the various chunks OK or not? For this, the local subroutine `get_next_chunk_descriptor` is used
+/// - Are the various chunks OK or not? For this, the subroutine `get_next_chunk_descriptor` is used
+/// - Does the PNG end with an IEND chunk?
 pub(crate) fn
 parse_png
 (
@@ -134,83 +73,97 @@ parse_png
 -> Result<Vec<PngChunk>, std::io::Error>
 {
 	let mut file = check_signature(path)?;
-	let mut chunks = Vec::new();
-
-	loop
-	{
-		let chunk_descriptor = get_next_chunk_descriptor(&mut file)?;
-		chunks.push(chunk_descriptor);
-
-		if chunks.last().unwrap().as_string() == "IEND".to_string()
-		{
-			break;
-		}
-	}
-
+	let (chunks, _) = get_chunk_descriptors(&mut file, false, None)?;
 	return Ok(chunks);
 }
 
-// Clears existing metadata chunk from a png file
+/// Same as `parse_png`, but does not fail if the CRC check of a chunk fails.
+/// Instead, the mismatches get recorded as warnings and returned alongside
+/// the chunks.
+pub(crate) fn
+parse_png_lenient
+(
+	path: &Path
+)
+-> Result<(Vec<PngChunk>, Vec<String>), std::io::Error>
+{
+	let mut file = check_signature(path)?;
+	let mut crc_mismatches = Vec::new();
+	let (chunks, _) = get_chunk_descriptors(&mut file, false, Some(&mut crc_mismatches))?;
+	return Ok((chunks, crc_mismatches));
+}
+
+// Clears existing metadata chunks (zTXt and tEXt with the "Raw profile type
+// exif" keyword as well as eXIf) from a png file
 // Gets called before writing any new metadata
 #[allow(non_snake_case)]
 pub(crate) fn
 clear_metadata
 (
-	path: &Path
+	path:                   &Path,
+	lenient_crc:            bool,
+	match_raw_profile_app1: bool
 )
 -> Result<(), std::io::Error>
 {
 
 	// Parse the PNG - if this fails, the clear operation fails as well
-	let parse_png_result = parse_png(path)?;
+	let parse_png_result = if lenient_crc
+	{
+		parse_png_lenient(path)?.0
+	}
+	else
+	{
+		parse_png(path)?
+	};
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
 	let mut file = open_write_file(path)?;
-	let mut seek_counter = 8u64;
+	perform_file_action!(file.seek(SeekFrom::Start(8)));
 
 	for chunk in &parse_png_result
 	{
-		// If this is not a zTXt chunk, jump to the next chunk
-		if chunk.as_string() != String::from("zTXt")
-		{
-			seek_counter += chunk.length() as u64 + 12;
-			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
-			continue;
-		}
-
-		// Skip chunk length and type (4+4 Bytes)
-		perform_file_action!(file.seek(SeekFrom::Current(8)));
-
-		// Read chunk data into buffer for checking that this is the 
-		// correct chunk to delete
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
-		{
-			return io_error!(Other, "Could not read chunk data");
-		}
+		// Remember where this chunk starts in case it needs to be removed
+		let seek_counter = file.stream_position()?;
 
-		// Compare to the "Raw profile type exif" string constant
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
+		match chunk.as_string().as_str()
 		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
+			"eXIf" => {
+				// Always remove eXIf chunks
+				perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
+			},
+
+			"zTXt" | "tEXt" => {
+				// Skip chunk length and type (4+4 Bytes)
+				perform_file_action!(file.seek(SeekFrom::Current(8)));
+
+				// Read chunk data into buffer for checking that this is the 
+				// correct chunk to delete
+				let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
+				if file.read(&mut zTXt_chunk_data)? != chunk.length() as usize
+				{
+					return io_error!(Other, "Could not read chunk data");
+				}
+
+				// Skip the CRC as it is not important at this point
+				perform_file_action!(file.seek(SeekFrom::Current(4)));
+
+				// If this is not the correct text chunk, ignore current
+				// (wrong) chunk and continue with next chunk
+				if !is_raw_profile_exif_chunk(&chunk.as_string(), &zTXt_chunk_data, match_raw_profile_app1)
+				{	
+					continue;
+				}
+			},
+
+			_ => {
+				// Not a metadata chunk, jump to the next chunk
+				perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
+				continue;
 			}
 		}
-
-		// Skip the CRC as it is not important at this point
-		perform_file_action!(file.seek(SeekFrom::Current(4)));
-
-		// If this is not the correct zTXt chunk, ignore current
-		// (wrong) zTXt chunk and continue with next chunk
-		if !correct_zTXt_chunk
-		{	
-			continue;
-		}
 		
-		// We have now established that this is the correct chunk to delete
+		// We have now established that this is a chunk to delete
 		// Therefore: Copy data from here (after CRC) onwards into a buffer...
 		let mut buffer = Vec::new();
 		perform_file_action!(file.read_to_end(&mut buffer));
@@ -233,136 +186,277 @@ clear_metadata
 	return Ok(());
 }
 
-#[allow(non_snake_case)]
+/// Reads the raw EXIF data from the PNG file. If the file contains an eXIf
+/// chunk its payload is returned as is, as it already is a TIFF/EXIF block.
+/// Otherwise, the legacy zTXt (or tEXt) chunk with the "Raw profile type exif"
+/// keyword gets decompressed (if necessary) and decoded. If both exist, the eXIf chunk is preferred.
+/// If there are multiple of these text chunks, the first one that can be 
+/// decoded is used.
 pub(crate) fn
 read_metadata
 (
 	path: &Path
 )
 -> Result<Vec<u8>, std::io::Error>
+{
+	return generic_read_metadata(path, &PngReadOptions::default(), None);
+}
+
+/// Same as `read_metadata`, but does not fail if the CRC check of a chunk 
+/// fails. Instead, the mismatches get recorded as warnings, as well as a 
+/// missing IEND chunk.
+pub(crate) fn
+read_metadata_lenient
+(
+	path:     &Path,
+	warnings: &mut Vec<MetadataWarning>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return generic_read_metadata(path, &PngReadOptions::default(), Some(warnings));
+}
+
+#[allow(non_snake_case)]
+fn
+generic_read_metadata
+(
+	path:     &Path,
+	options:  &PngReadOptions,
+	warnings: Option<&mut Vec<MetadataWarning>>
+)
+-> Result<Vec<u8>, std::io::Error>
 {
 	// Parse the PNG - if this fails, the read fails as well
-	let parse_png_result = parse_png(path)?;
+	// A missing IEND chunk is tolerated (if enabled) so that metadata located
+	// before the point of truncation can still be recovered
+	let mut crc_mismatches = Vec::new();
+	let (parse_png_result, iend_found) = get_chunk_descriptors(
+		&mut check_signature(path)?, 
+		options.tolerate_missing_IEND, 
+		warnings.as_ref().map(|_| &mut crc_mismatches)
+	)?;
+
+	if let Some(warnings) = warnings
+	{
+		warnings.extend(crc_mismatches.into_iter().map(MetadataWarning::CrcMismatch));
+		if !iend_found
+		{
+			warnings.push(MetadataWarning::MissingIend);
+		}
+	}
+
+	// Store the result of decoding the text chunk in case there is no eXIf
+	// chunk further down the file
+	let mut zTXt_result = None;
+
+	// Same for the orientation stored in an orNT chunk, if enabled
+	let mut orNT_result = None;
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	let mut file = check_signature(path).unwrap();
+	let mut file = check_signature(path)?;
 	for chunk in &parse_png_result
 	{
 		// Wrong chunk? Seek to the next one
-		if chunk.as_string() != String::from("zTXt")
+		if chunk.as_string() != String::from("zTXt") && chunk.as_string() != String::from("tEXt") && chunk.as_string() != String::from("eXIf")
+		&& chunk.as_string() != String::from("orNT")
 		{
 			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
 			continue;
 		}
 
-		// We now have a zTXt chunk:
+		// We now have a zTXt, tEXt, eXIf or orNT chunk:
 		// Skip chunk length and type (4+4 Bytes)
 		perform_file_action!(file.seek(SeekFrom::Current(8)));
 
 		// Read chunk data into buffer
 		// No need to verify this using CRC as already done by parse_png(path)
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		let mut chunk_data = vec![0u8; chunk.length() as usize];
+		if file.read(&mut chunk_data)? != chunk.length() as usize
 		{
 			return io_error!(Other, "Could not read chunk data");
 		}
 
-		// Check that this is the correct zTXt chunk...
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
+		// Skip CRC of the current chunk
+		perform_file_action!(file.seek(SeekFrom::Current(4)));
+
+		// The eXIf chunk stores the TIFF/EXIF data directly. An empty eXIf
+		// chunk does not contain any metadata, so it gets ignored in favor
+		// of the other chunks
+		if chunk.as_string() == String::from("eXIf")
 		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
+			if chunk_data.is_empty()
 			{
-				correct_zTXt_chunk = false;
-				break;
+				continue;
 			}
+			return Ok(chunk_data);
 		}
 
-		if !correct_zTXt_chunk
+		// The orNT chunk is only used if there is no EXIF data at all
+		if chunk.as_string() == String::from("orNT")
 		{
-			// Skip CRC from current (wrong) zTXt chunk and continue
-			perform_file_action!(file.seek(SeekFrom::Current(4)));
+			if options.orientation_from_orNT
+			{
+				orNT_result = encode_orNT_orientation(&chunk_data);
+			}
 			continue;
 		}
 
-		// Decode zlib data...
-		if let Ok(decompressed_data) = decompress_to_vec_zlib(&zTXt_chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
+		// Check that this is the correct text chunk and that there is not
+		// already a previous one that got decoded successfully
+		if matches!(zTXt_result, Some(Ok(_))) || !is_raw_profile_exif_chunk(&chunk.as_string(), &chunk_data, options.match_raw_profile_app1)
 		{
-			// ...and perform PNG-specific decoding & return the result
-			return Ok(decode_metadata_png(&decompressed_data).unwrap());
+			continue;
 		}
-		else
+
+		// Inflate zlib data if necessary and perform PNG-specific decoding
+		// Files edited by multiple tools may contain several of these chunks,
+		// some of them stale or corrupt. So if decoding fails, the next one
+		// is tried and the first error is only returned if all of them fail
+		let decode_result = decode_raw_profile_exif_chunk(&chunk.as_string(), &chunk_data, options);
+		if decode_result.is_ok() || zTXt_result.is_none()
 		{
-			return io_error!(Other, "Could not inflate compressed chunk data!");
+			zTXt_result = Some(decode_result);
 		}
 	}
 
-	return io_error!(Other, "No metadata found!");
+	if let Some(result) = zTXt_result
+	{
+		return result;
+	}
+
+	if let Some(orientation_exif) = orNT_result
+	{
+		return Ok(orientation_exif);
+	}
+
+	return little_exif_error!(NoMetadata, "No metadata found!");
 
 }
 
 
 
+/// Writes the metadata to the PNG file, either as zTXt chunk using the 
+/// "Raw profile type exif" keyword (if `options.as_zTXt_chunk` is true) or as eXIf
+/// chunk that stores the TIFF/EXIF data directly.
+/// Any previously stored EXIF metadata gets removed first, i.e. all eXIf and
+/// "Raw profile type exif" text chunks, so that the file contains exactly one
+/// EXIF chunk afterwards.
 #[allow(non_snake_case)]
 pub(crate) fn
 write_metadata
 (
-	path:     &Path,
-	metadata: &Metadata
+	path:          &Path,
+	metadata:      &Metadata,
+	options:       &PngWriteOptions,
+	warnings:      &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
+	// Everything that may fail is done before modifying the file, so that
+	// it is left untouched in case of an error (see `vec::write_metadata`)
+	let encoded_metadata = metadata.encode_with_warnings(warnings)?;
 
-	// First clear the existing metadata
-	// This also parses the PNG and checks its validity, so it is safe to
-	// assume that is, in fact, a usable PNG file
-	let _ = clear_metadata(path)?;
+	// Without an offset to UTC the time in the tIME chunk would be wrong
+	let tIME_chunk = match options.mirror_datetime_to_tIME
+	{
+		true  => metadata.get_datetime()
+			.filter(|datetime| datetime.offset_minutes.is_some())
+			.map(|datetime| encode_tIME_chunk(&datetime)),
+		false => None,
+	};
+
+	let pHYs_chunk = match options.sync_pHYs_from_exif
+	{
+		true  => encode_pHYs_chunk(metadata)?,
+		false => None,
+	};
 
-	let mut IHDR_length = 0u32;
-	if let Ok(chunks) = parse_png(path)
+	if tIME_chunk.is_some() || pHYs_chunk.is_some()
 	{
-		IHDR_length = chunks[0].length();
+		get_position_after_IHDR(&parse_png_lenient(path)?.0)?;
 	}
 
-	// Encode the data specifically for PNG and open the image file
-	let encoded_metadata = encode_metadata_png(&metadata.encode()?);
-	let seek_start = 0u64         // Skip ...
-	+ PNG_SIGNATURE.len() as u64  // PNG Signature
-	+ IHDR_length         as u64  // IHDR data section
-	+ 12                  as u64; // rest of IHDR chunk (length, type, CRC)
+	// First clear the existing metadata
+	// This also parses the PNG and checks its validity, so it is safe to
+	// assume that is, in fact, a usable PNG file
+	let _ = clear_metadata(path, options.lenient_crc, options.match_raw_profile_app1)?;
+
+	// Insert the new chunk after the IHDR chunk (zTXt) or before the first
+	// IDAT chunk (eXIf), which keeps the relative order of all other chunks
+	// (see `vec::write_metadata`)
+	let (chunks, _) = parse_png_lenient(path)?;
+	let seek_start  = get_metadata_insert_position(&chunks, options.as_zTXt_chunk) as u64;
 
-	// Get to first chunk after IHDR, copy all the data starting from there
+	// Get to the insert position, copy all the data starting from there
 	let mut file   = open_write_file(path)?;
 	let mut buffer = Vec::new();
 	perform_file_action!(file.seek(SeekFrom::Start(seek_start)));
 	perform_file_action!(file.read_to_end(&mut buffer));
 	perform_file_action!(file.seek(SeekFrom::Start(seek_start)));
 
-	// Build data of new chunk using zlib compression (level=8 -> default)
-	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
-	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
-	zTXt_chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, 8).iter());
+	let mut chunk_data: Vec<u8>;
+	if options.as_zTXt_chunk
+	{
+		// Encode the data specifically for PNG
+		let encoded_metadata = encode_metadata_png(&encoded_metadata);
+
+		match options.text_mode
+		{
+			PngTextMode::ZTxtCompressed => {
+				// Build data of new chunk using zlib compression with the given level
+				chunk_data = vec![0x7a, 0x54, 0x58, 0x74];
+				chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
+				chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, options.zlib_level).iter());
+			},
+			PngTextMode::TextUncompressed => {
+				// The tEXt chunk stores the encoded data as it is
+				chunk_data = vec![0x74, 0x45, 0x58, 0x74];
+				chunk_data.extend(RAW_PROFILE_TYPE_EXIF_TEXT.iter());
+				chunk_data.extend(encoded_metadata.iter());
+			},
+		}
+	}
+	else
+	{
+		// The eXIf chunk simply contains the encoded metadata
+		chunk_data = vec![0x65, 0x58, 0x49, 0x66];
+		chunk_data.extend(encoded_metadata.iter());
+	}
 
 	// Compute CRC and append it to the chunk data
 	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&zTXt_chunk_data) as u32;
+	let checksum = crc_struct.checksum(&chunk_data) as u32;
 	for i in 0..4
 	{
-		zTXt_chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
+		chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
 	}
 
 	// Write new data to PNG file
 	// Start with length of the new chunk (subtracting 8 for type and CRC)
-	let chunk_data_len = zTXt_chunk_data.len() as u32 - 8;
+	let chunk_data_len = chunk_data.len() as u32 - 8;
 	for i in 0..4
 	{
 		perform_file_action!(file.write( &[(chunk_data_len >> (8 * (3-i))) as u8] ));
 	}
 
 	// Write data of new chunk and rest of PNG file
-	perform_file_action!(file.write_all(&zTXt_chunk_data));
+	perform_file_action!(file.write_all(&chunk_data));
 	perform_file_action!(file.write_all(&buffer));
 
+	if tIME_chunk.is_some() || pHYs_chunk.is_some()
+	{
+		// Update the tIME and pHYs chunks using the entire file in memory
+		let mut file_buffer = std::fs::read(path)?;
+		for (chunk_name, chunk) in [("tIME", tIME_chunk), ("pHYs", pHYs_chunk)]
+		{
+			if let Some(chunk) = chunk
+			{
+				replace_or_insert_chunk(&mut file_buffer, chunk_name, chunk)?;
+			}
+		}
+		std::fs::write(path, file_buffer)?;
+	}
+
 	return Ok(());
 }
 
@@ -379,5 +473,42 @@ mod tests
 		).unwrap();
 		assert_eq!(chunks.len(), 3);
 	}
+
+	#[test]
+	fn
+	write_exif_chunk_matches_vec_test()
+	-> Result<(), std::io::Error>
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::metadata::Metadata;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("eXIf".to_string()))?;
+
+		// Put a PLTE chunk between IHDR and the first IDAT chunk
+		let mut file_buffer = std::fs::read("tests/sample2.png")?;
+		let mut tail        = file_buffer.split_off(33);
+		let mut plte_chunk  = vec![0x00, 0x00, 0x00, 0x03, 0x50, 0x4c, 0x54, 0x45, 0x00, 0x00, 0x00];
+		let checksum = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&plte_chunk[4..]);
+		plte_chunk.extend(checksum.to_be_bytes());
+		file_buffer.append(&mut plte_chunk);
+		file_buffer.append(&mut tail);
+
+		let options = super::PngWriteOptions { as_zTXt_chunk: false, ..Default::default() };
+
+		let     path = std::path::Path::new("tests/sample2_exif_chunk_file.png");
+		std::fs::write(path, &file_buffer)?;
+		super::write_metadata(path, &metadata, &options, &mut Vec::new())?;
+		let file_result = std::fs::read(path)?;
+		std::fs::remove_file(path)?;
+
+		crate::png::vec::write_metadata(&mut file_buffer, &metadata, &options, &mut Vec::new())?;
+		assert_eq!(file_result, file_buffer);
+
+		// The eXIf chunk follows the PLTE chunk
+		assert_eq!(&file_buffer[33+15+4..33+15+8], b"eXIf");
+
+		Ok(())
+	}
 	
 }
@@ -3,17 +3,31 @@
 
 pub mod file;
 pub mod vec;
-mod png_chunk;
+pub mod png_chunk;
+pub mod png_chunk_iter;
 
 use std::collections::VecDeque;
+use std::io::Read;
 
+use crc::Crc;
+use crc::CRC_32_ISO_HDLC;
 use miniz_oxide::deflate::compress_to_vec_zlib;
 
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::general_file_io::little_exif_error;
 use crate::general_file_io::EXIF_HEADER;
 use crate::general_file_io::NEWLINE;
 use crate::general_file_io::SPACE;
+use crate::limits::decompress_zlib;
+use crate::limits::DEFAULT_MAX_DECOMPRESSED_SIZE;
+use crate::metadata::Metadata;
+
+use png_chunk::PngChunk;
 
 pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+pub(crate) const XMP_KEYWORD: &str = "XML:com.adobe.xmp";
+const PNG_MAX_CHUNK_LENGTH: u32 = 0x7fff_ffff;
 pub(crate) const RAW_PROFILE_TYPE_EXIF: [u8; 23] = [
 	0x52, 0x61, 0x77, 0x20,                             // Raw
 	0x70, 0x72, 0x6F, 0x66, 0x69, 0x6C, 0x65, 0x20,     // profile
@@ -21,6 +35,163 @@ pub(crate) const RAW_PROFILE_TYPE_EXIF: [u8; 23] = [
 	0x65, 0x78, 0x69, 0x66, 0x00, 0x00                  // exif NUL NUL
 ];
 
+// The tEXt chunk has no compression method byte, so the keyword is only
+// followed by a single NUL
+pub(crate) const RAW_PROFILE_TYPE_EXIF_TEXT: [u8; 22] = [
+	0x52, 0x61, 0x77, 0x20,                             // Raw
+	0x70, 0x72, 0x6F, 0x66, 0x69, 0x6C, 0x65, 0x20,     // profile
+	0x74, 0x79, 0x70, 0x65, 0x20,                       // type
+	0x65, 0x78, 0x69, 0x66, 0x00                        // exif NUL
+];
+
+/// Creates the TIFF data containing only an `Orientation` tag with the value
+/// stored in the given orNT chunk data (a single byte using the same values
+/// as EXIF). Returns `None` if the data is not a single byte with a valid
+/// orientation value (1-8).
+#[allow(non_snake_case)]
+pub(crate) fn
+encode_orNT_orientation
+(
+	chunk_data: &[u8]
+)
+-> Option<Vec<u8>>
+{
+	let orientation = match chunk_data
+	{
+		[value] if (1..=8).contains(value) => *value as u16,
+		_                                  => return None,
+	};
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Orientation(vec![orientation])).ok()?;
+	return metadata.encode().ok();
+}
+
+/// Determines the type of the text chunk that is used for storing the EXIF
+/// data using the "Raw profile type exif" keyword
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum
+PngTextMode
+{
+	/// Write a zlib-compressed zTXt chunk
+	#[default]
+	ZTxtCompressed,
+
+	/// Write an uncompressed tEXt chunk
+	TextUncompressed,
+}
+
+/// Options for writing metadata to PNG images
+#[allow(non_snake_case)]
+#[derive(Clone, Copy, Debug)]
+pub struct
+PngWriteOptions
+{
+	/// Write the metadata to a zTXt chunk using the "Raw profile type exif"
+	/// keyword (default). Otherwise, an eXIf chunk gets written.
+	pub as_zTXt_chunk: bool,
+
+	/// The type of text chunk to use if `as_zTXt_chunk` is true. Defaults to
+	/// a compressed zTXt chunk.
+	pub text_mode:     PngTextMode,
+
+	/// Skip the CRC validation of the existing chunks, so that metadata can
+	/// be written to images produced by buggy encoders. Chunks with a wrong
+	/// CRC are left as they are.
+	pub lenient_crc:   bool,
+
+	/// The zlib compression level (0 to 10) used for the zTXt chunk, where 1
+	/// is the fastest and 10 gives the smallest result. Defaults to 8.
+	pub zlib_level:    u8,
+
+	/// Additionally write the `ModifyDate` (DateTime) of the metadata to the
+	/// tIME chunk that stores the last-modification time of the image in
+	/// UTC. This requires the `OffsetTime` tag for converting to UTC, so 
+	/// nothing is written if either tag is missing. Defaults to false.
+	pub mirror_datetime_to_tIME: bool,
+
+	/// Additionally write the resolution given by the `XResolution`, 
+	/// `YResolution` and `ResolutionUnit` tags of the metadata (if present)
+	/// to the pHYs chunk, see `vec::sync_phys_from_exif`. Defaults to false.
+	pub sync_pHYs_from_exif: bool,
+
+	/// Also replace zTXt and tEXt chunks using the "Raw profile type APP1"
+	/// keyword, see `PngReadOptions::match_raw_profile_app1`. Defaults to
+	/// false.
+	pub match_raw_profile_app1: bool,
+}
+
+impl
+Default
+for
+PngWriteOptions
+{
+	fn
+	default
+	()
+	-> Self
+	{
+		return PngWriteOptions {
+			as_zTXt_chunk: true,
+			text_mode:     PngTextMode::ZTxtCompressed,
+			lenient_crc:   false,
+			zlib_level:    8,
+			mirror_datetime_to_tIME: false,
+			sync_pHYs_from_exif:     false,
+			match_raw_profile_app1:  false,
+		};
+	}
+}
+
+/// Options for reading metadata from PNG images
+#[allow(non_snake_case)]
+#[derive(Clone, Copy, Debug)]
+pub struct
+PngReadOptions
+{
+	/// Also read zTXt and tEXt chunks using the "Raw profile type APP1"
+	/// keyword, which some tools use instead of "Raw profile type exif" for
+	/// storing the EXIF data in the same way. Defaults to false.
+	pub match_raw_profile_app1: bool,
+
+	/// Synthesize an `Orientation` tag from the orNT chunk when the PNG
+	/// contains no EXIF data at all, so that the orientation can be handled
+	/// uniformly. The resulting metadata consists of only this tag. Defaults
+	/// to false.
+	pub orientation_from_orNT:  bool,
+
+	/// The maximum number of bytes the compressed EXIF data may inflate to.
+	/// Data exceeding the limit results in an error. Defaults to 
+	/// `DEFAULT_MAX_DECOMPRESSED_SIZE`.
+	pub max_decompressed_size:  usize,
+
+	/// Read the chunks of a PNG that ends without an IEND chunk (e.g. due to
+	/// a truncated download), so that metadata located before the point of
+	/// truncation can still be recovered. This is reported via 
+	/// `MetadataWarning::MissingIend`. Otherwise, such a PNG results in an 
+	/// error. Defaults to true.
+	pub tolerate_missing_IEND:  bool,
+}
+
+impl
+Default
+for
+PngReadOptions
+{
+	fn
+	default
+	()
+	-> Self
+	{
+		return PngReadOptions {
+			match_raw_profile_app1: false,
+			orientation_from_orNT:  false,
+			max_decompressed_size:  DEFAULT_MAX_DECOMPRESSED_SIZE,
+			tolerate_missing_IEND:  true,
+		};
+	}
+}
+
 // The bytes during encoding need to be encoded themselves:
 // A given byte (e.g. 0x30 for the char '0') has two values in the string of its hex representation ('3' and '0')
 // These two characters need to be encoded themselves (51 for '3', 48 for '0'), resulting in the final encoded
@@ -130,6 +301,11 @@ decode_metadata_png
 
 	while !exif_header_found
 	{
+		if exif_all.len() < EXIF_HEADER.len()
+		{
+			return io_error!(InvalidData, "Could not find EXIF header in PNG data!");
+		}
+
 		let mut counter = 0;
 		for header_value in &EXIF_HEADER
 		{
@@ -155,7 +331,10 @@ decode_metadata_png
 	//    that will now get extracted
 	// Consider this part optional as it might be removed in the future and
 	// isn't strictly necessary and just for validating the data we get
-	assert!(pop_storage.len() > 0);
+	if pop_storage.is_empty()
+	{
+		return io_error!(InvalidData, "Could not find size information in PNG data!");
+	}
 
 	// Using the encode_byte function re-encode the bytes regarding the size
 	// information and construct its value using decimal based shifting
@@ -166,20 +345,383 @@ decode_metadata_png
 	for i in 0..std::cmp::min(4, pop_storage.len())
 	{
 		let re_encoded_byte = encode_byte(&pop_storage[pop_storage.len() -1 -i]);
-		let tens_place = u64::from_str_radix(&(re_encoded_byte[0] as char).to_string(), 10).unwrap();
-		let ones_place = u64::from_str_radix(&(re_encoded_byte[1] as char).to_string(), 10).unwrap();
+		let (tens_place, ones_place) = match (
+			(re_encoded_byte[0] as char).to_digit(10),
+			(re_encoded_byte[1] as char).to_digit(10)
+		)
+		{
+			(Some(tens_place), Some(ones_place)) => (tens_place as u64, ones_place as u64),
+			_ => return io_error!(InvalidData, "Invalid size information in PNG data!"),
+		};
 		given_exif_len = given_exif_len + tens_place * 10 * 10_u64.pow((2 * i).try_into().unwrap());
 		given_exif_len = given_exif_len + ones_place *  1 * 10_u64.pow((2 * i).try_into().unwrap());
 	}
 
-	assert!(given_exif_len == exif_all.len().try_into().unwrap());
+	if given_exif_len != exif_all.len() as u64
+	{
+		return io_error!(
+			InvalidData, 
+			format!("Size information {} does not match the size {} of the EXIF data in PNG data!", given_exif_len, exif_all.len())
+		);
+	}
 	// End optional part
 
 	return Ok(Vec::from(exif_all));
 }
 
-/// Provides the WebP specific encoding result as vector of bytes to be used
+// TODO: Check if this is also affected by endianness
+// Edit: Should... not? I guess?
+/// Reads the next chunk from the given source, validates it using its CRC
+/// and returns the descriptor of the chunk. Shared by the `Vec<u8>` and
+/// file based PNG functions as well as the `PngChunkIter`
+/// If `crc_mismatches` is provided, a failed CRC check does not result in an
+/// error but gets recorded as warning message in the given vector instead.
+pub(crate) fn
+get_next_chunk_descriptor<R: Read>
+(
+	reader:         &mut R,
+	crc_mismatches: Option<&mut Vec<String>>
+)
+-> Result<PngChunk, std::io::Error>
+{
+	// Read the start of the chunk
+	let mut chunk_start = [0u8; 8];
+	let mut bytes_read = reader.read(&mut chunk_start)?;
+
+	// Check that indeed 8 bytes were read
+	if bytes_read != 8
+	{
+		return io_error!(UnexpectedEof, "Could not read start of chunk");
+	}
+
+	// Construct name of chunk and its length
+	let chunk_name = String::from_utf8((&chunk_start[4..8]).to_vec());
+	let mut chunk_length = 0u32;
+	for byte in &chunk_start[0..4]
+	{
+		chunk_length = chunk_length * 256 + *byte as u32;
+	}
+
+	// The PNG specification limits the length of a chunk to 2^31-1 bytes
+	if chunk_length > PNG_MAX_CHUNK_LENGTH
+	{
+		return io_error!(InvalidData, format!("PNG chunk length {} exceeds the maximum of {}", chunk_length, PNG_MAX_CHUNK_LENGTH));
+	}
+
+	// Read chunk data ...
+	// Note: This only allocates memory for the data that is actually there,
+	// so that a corrupted length can't result in a huge allocation. Chunks
+	// with a length of 0 are valid and result in an empty buffer.
+	let mut chunk_data_buffer = Vec::new();
+	reader.by_ref().take(chunk_length as u64).read_to_end(&mut chunk_data_buffer)?;
+	if chunk_data_buffer.len() != chunk_length as usize
+	{
+		return io_error!(UnexpectedEof, "Could not read chunk data");
+	}
+
+	// ... and CRC values
+	let mut chunk_crc_buffer = [0u8; 4];
+	bytes_read = reader.read(&mut chunk_crc_buffer)?;
+	if bytes_read != 4
+	{
+		return io_error!(UnexpectedEof, "Could not read chunk CRC");
+	}
+
+	// Compute CRC on chunk
+	// For chunks without data (e.g. IEND), this only covers the chunk type
+	let mut crc_input = Vec::new();
+	crc_input.extend(chunk_start[4..8].iter());
+	crc_input.extend(chunk_data_buffer.iter());
+
+	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+	let checksum = crc_struct.checksum(&crc_input) as u32;
+
+	let chunk_name = match chunk_name
+	{
+		Ok(chunk_name) => chunk_name,
+		Err(_)         => return io_error!(InvalidData, "PNG chunk name is not valid UTF-8"),
+	};
+
+	if checksum.to_be_bytes() != chunk_crc_buffer
+	{
+		if let Some(crc_mismatches) = crc_mismatches
+		{
+			crc_mismatches.push(format!("Checksum check failed for {} chunk", chunk_name));
+		}
+		else
+		{
+			return little_exif_error!(ChecksumMismatch, "Checksum check failed while reading PNG!");
+		}
+	}
+
+	// If validating the chunk using the CRC was successful, return its descriptor
+	// Note: chunk_length does NOT include the +4 for the CRC area!
+
+	if let Ok(png_chunk) = PngChunk::from_string(
+		&chunk_name,
+		chunk_length
+	)
+	{
+		return Ok(png_chunk);
+	}
+	else
+	{
+		return io_error!(Other, "Invalid PNG chunk name");
+	}
+}
+
+/// Determines the position in the PNG file where a new metadata chunk gets
+/// inserted, based on the chunks currently stored in the file.
+/// zTXt and tEXt chunks are inserted directly after the IHDR chunk. For 
+/// maximal compatibility, an eXIf chunk is inserted after the PLTE and iCCP
+/// chunks (if present), but always before the first IDAT chunk.
+#[allow(non_snake_case)]
+pub(crate) fn
+get_metadata_insert_position<'a>
+(
+	chunks:        impl IntoIterator<Item = &'a PngChunk>,
+	as_zTXt_chunk: bool
+)
+-> usize
+{
+	let mut chunks = chunks.into_iter();
+
+	// The IHDR chunk is always the first chunk
+	let mut position        = PNG_SIGNATURE.len();
+	let mut insert_position = match chunks.next()
+	{
+		Some(IHDR) => position + IHDR.length() as usize + 12,
+		None       => position,
+	};
+
+	if as_zTXt_chunk
+	{
+		return insert_position;
+	}
+
+	position = insert_position;
+	for chunk in chunks
+	{
+		let chunk_name = chunk.as_string();
+		if chunk_name == "IDAT"
+		{
+			break;
+		}
+
+		position += chunk.length() as usize + 12;
+		if chunk_name == "PLTE" || chunk_name == "iCCP"
+		{
+			insert_position = position;
+		}
+	}
+
+	return insert_position;
+}
+
+/// Reads the chunk descriptors until the IEND chunk is reached. 
+/// If `tolerate_missing_iend` is set, a stream that ends before the IEND 
+/// chunk (e.g. due to an interrupted download) does not result in an error.
+/// Instead, the chunks read so far are returned. The returned flag indicates
+/// whether the IEND chunk was found.
+/// CRC mismatches are handled as described for `get_next_chunk_descriptor`
+pub(crate) fn
+get_chunk_descriptors<R: Read>
+(
+	reader:                &mut R,
+	tolerate_missing_iend: bool,
+	mut crc_mismatches:    Option<&mut Vec<String>>
+)
+-> Result<(Vec<PngChunk>, bool), std::io::Error>
+{
+	let mut chunks = Vec::new();
+
+	loop
+	{
+		let chunk_descriptor = match get_next_chunk_descriptor(reader, crc_mismatches.as_deref_mut())
+		{
+			Ok(chunk_descriptor) => chunk_descriptor,
+			Err(error) => {
+				if tolerate_missing_iend && error.kind() == std::io::ErrorKind::UnexpectedEof
+				{
+					return Ok((chunks, false));
+				}
+				return Err(error);
+			}
+		};
+		chunks.push(chunk_descriptor);
+
+		if chunks.last().unwrap().as_string() == "IEND".to_string()
+		{
+			return Ok((chunks, true));
+		}
+	}
+}
+
+/// Decodes the data of an iTXt chunk, which is structured as follows:
+/// - Keyword (1-79 bytes), followed by a NUL separator
+/// - Compression flag (1 byte), 0 for uncompressed and 1 for compressed text
+/// - Compression method (1 byte), 0 for zlib
+/// - Language tag (0 or more bytes), followed by a NUL separator
+/// - Translated keyword (0 or more bytes), followed by a NUL separator
+/// - Text (0 or more bytes), which is UTF-8 encoded and possibly compressed
+/// Returns the keyword and the (decompressed) text
+#[allow(non_snake_case)]
+fn
+decode_iTXt_chunk_data
+(
+	chunk_data: &[u8]
+)
+-> Result<(String, String), std::io::Error>
+{
+	// Splits off the next NUL separated field of the chunk data
+	fn
+	next_field
+	(
+		data: &[u8]
+	)
+	-> Result<(&[u8], &[u8]), std::io::Error>
+	{
+		if let Some(nul_position) = data.iter().position(|byte| *byte == 0x00)
+		{
+			return Ok((&data[..nul_position], &data[nul_position+1..]));
+		}
+		return io_error!(InvalidData, "Missing NUL separator in iTXt chunk!");
+	}
+
+	let (keyword, remaining) = next_field(chunk_data)?;
+
+	if remaining.len() < 2
+	{
+		return io_error!(InvalidData, "iTXt chunk too short for compression information!");
+	}
+	let compression_flag   = remaining[0];
+	let compression_method = remaining[1];
+
+	let (_language_tag,       remaining) = next_field(&remaining[2..])?;
+	let (_translated_keyword, text     ) = next_field(remaining)?;
+
+	let text_data = match (compression_flag, compression_method)
+	{
+		(0, _) => text.to_vec(),
+		(1, 0) => decompress_zlib(text, DEFAULT_MAX_DECOMPRESSED_SIZE)?,
+		_      => return io_error!(InvalidData, format!("Unknown iTXt compression: flag {} method {}", compression_flag, compression_method)),
+	};
+
+	if let (Ok(keyword_string), Ok(text_string)) = (String::from_utf8(keyword.to_vec()), String::from_utf8(text_data))
+	{
+		return Ok((keyword_string, text_string));
+	}
+	return io_error!(InvalidData, "iTXt chunk is not valid UTF-8!");
+}
+
+/// Decodes the data of a tEXt or zTXt chunk, which consists of the keyword
+/// (1-79 bytes), a NUL separator and the text. For zTXt chunks, the NUL 
+/// separator is followed by the compression method (0 for zlib) and the 
+/// compressed text. Both keyword and text are Latin-1 encoded.
+/// Returns the keyword and the (decompressed) text
+#[allow(non_snake_case)]
+pub(crate) fn
+decode_tEXt_zTXt_chunk_data
+(
+	chunk_name: &str,
+	chunk_data: &[u8]
+)
+-> Result<(String, String), std::io::Error>
+{
+	let nul_position = match chunk_data.iter().position(|byte| *byte == 0x00)
+	{
+		Some(nul_position) => nul_position,
+		None               => return io_error!(InvalidData, format!("Missing NUL separator in {} chunk!", chunk_name)),
+	};
+
+	let text_data = match (chunk_name, chunk_data.get(nul_position + 1))
+	{
+		("zTXt", Some(0x00)) => decompress_zlib(&chunk_data[nul_position + 2..], DEFAULT_MAX_DECOMPRESSED_SIZE)?,
+		("zTXt", _         ) => return io_error!(InvalidData, "Unknown compression method in zTXt chunk!"),
+		_                    => chunk_data[nul_position + 1..].to_vec(),
+	};
+
+	let latin1_to_string = |data: &[u8]| data.iter().map(|byte| *byte as char).collect::<String>();
+	return Ok((latin1_to_string(&chunk_data[..nul_position]), latin1_to_string(&text_data)));
+}
+
+/// Gets the position of the (possibly compressed) text in a zTXt or tEXt
+/// chunk that stores the EXIF data using the "Raw profile type exif" keyword 
+/// (or "Raw profile type APP1", if `match_raw_profile_app1` is set). The 
+/// keyword is read up to its NUL separator and compared ignoring the case
+/// and trailing whitespace. Returns `None` for any other chunk.
+fn
+get_raw_profile_exif_text_position
+(
+	chunk_name:             &str,
+	chunk_data:             &[u8],
+	match_raw_profile_app1: bool
+)
+-> Option<usize>
+{
+	let nul_position = chunk_data.iter().position(|byte| *byte == 0x00)?;
+	let keyword      = String::from_utf8_lossy(&chunk_data[..nul_position]).trim_end().to_lowercase();
+
+	if keyword != "raw profile type exif"
+	&& !(keyword == "raw profile type app1" && match_raw_profile_app1)
+	{
+		return None;
+	}
+
+	match chunk_name
+	{
+		// The keyword of a zTXt chunk is followed by the compression method
+		"zTXt" if chunk_data.get(nul_position + 1) == Some(&0x00) => Some(nul_position + 2),
+		"tEXt"                                                      => Some(nul_position + 1),
+		_                                                           => None,
+	}
+}
+
+/// Checks if the given chunk is a zTXt or tEXt chunk that stores the EXIF
+/// data using the "Raw profile type exif" keyword
+pub(crate) fn
+is_raw_profile_exif_chunk
+(
+	chunk_name:             &str,
+	chunk_data:             &[u8],
+	match_raw_profile_app1: bool
+)
+-> bool
+{
+	return get_raw_profile_exif_text_position(chunk_name, chunk_data, match_raw_profile_app1).is_some();
+}
+
+/// Decodes the EXIF data stored in a "Raw profile type exif" zTXt or tEXt
+/// chunk. Only the data of a zTXt chunk needs to be inflated first.
+pub(crate) fn
+decode_raw_profile_exif_chunk
+(
+	chunk_name: &str,
+	chunk_data: &[u8],
+	options:    &PngReadOptions
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let text_position = match get_raw_profile_exif_text_position(chunk_name, chunk_data, options.match_raw_profile_app1)
+	{
+		Some(text_position) => text_position,
+		None                => return io_error!(InvalidData, "Not a raw profile EXIF chunk!"),
+	};
+
+	if chunk_name == "tEXt"
+	{
+		return decode_metadata_png(&chunk_data[text_position..].to_vec());
+	}
+
+	let decompressed_data = decompress_zlib(&chunk_data[text_position..], options.max_decompressed_size)?;
+	return decode_metadata_png(&decompressed_data);
+}
+
+/// Provides the PNG specific encoding result as vector of bytes to be used
 /// by the user (e.g. in combination with another library)
+/// If `as_zTXt_chunk` is false, this is the data of an eXIf chunk, i.e. the
+/// TIFF encoded metadata without any further header. Otherwise this is the
+/// type and data of a zTXt chunk with the "Raw profile type exif" keyword.
 #[allow(non_snake_case)]
 pub(crate) fn
 as_u8_vec
@@ -189,13 +731,14 @@ as_u8_vec
 )
 -> Vec<u8>
 {
-	let basic_png_encode_result = encode_metadata_png(general_encoded_metadata);
-
 	if !as_zTXt_chunk
 	{
-		return basic_png_encode_result;
+		// The eXIf chunk stores the encoded metadata as it is
+		return general_encoded_metadata.clone();
 	}
 
+	let basic_png_encode_result = encode_metadata_png(general_encoded_metadata);
+
 	// Build data of new chunk using zlib compression (level=8 -> default)
 	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
 	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
@@ -28,17 +28,23 @@ macro_rules! build_png_chunk_type_enum {
 		/// These are the different PNG chunk types currently known to
 		/// little_exif. These might be expanded in the future if necessary.
 		#[allow(non_camel_case_types)]
-		pub(crate) enum
+		pub enum
 		PngChunk
 		{
 			$(
 				$tag(u32),
 			)*
+
+			/// Any other chunk (e.g. a private one), which retains its four
+			/// letter type in order to be preserved as is
+			Unknown([u8; 4], u32),
 		}
 
 		impl PngChunk
 		{
-			pub(crate) fn
+			/// Length of the chunk data, which does NOT include the length,
+			/// type and CRC fields of the chunk
+			pub fn
 			length
 			(
 				&self
@@ -50,10 +56,12 @@ macro_rules! build_png_chunk_type_enum {
 					$(
 						PngChunk::$tag(length) => length,
 					)*
+					PngChunk::Unknown(_, length) => length,
 				}
 			}
 
-			pub(crate) fn
+			/// The four letter type of the chunk, e.g. "IHDR"
+			pub fn
 			as_string
 			(
 				&self
@@ -65,6 +73,7 @@ macro_rules! build_png_chunk_type_enum {
 					$(
 						PngChunk::$tag(_) => String::from(stringify!($tag)),
 					)*
+					PngChunk::Unknown(name, _) => String::from_utf8_lossy(&name).to_string(),
 				}
 			}
 
@@ -81,9 +90,55 @@ macro_rules! build_png_chunk_type_enum {
 					$(
 						stringify!($tag) => Ok(PngChunk::$tag(length)),
 					)*
-					_ => Err("Invalid chunk name".to_string()),
+					_ => {
+						// Chunk types consist of exactly four ASCII letters
+						if let Ok(name) = <[u8; 4]>::try_from(string_name.as_bytes())
+						{
+							if name.iter().all(u8::is_ascii_alphabetic)
+							{
+								return Ok(PngChunk::Unknown(name, length));
+							}
+						}
+						Err("Invalid chunk name".to_string())
+					},
 				}
 			}
+
+			/// Critical chunks (uppercase first letter) are required for
+			/// displaying the image
+			pub fn
+			is_critical
+			(
+				&self
+			)
+			-> bool
+			{
+				return self.as_string().as_bytes()[0].is_ascii_uppercase();
+			}
+
+			/// Ancillary chunks (lowercase first letter) may be ignored by 
+			/// decoders, e.g. the text chunks storing the metadata
+			pub fn
+			is_ancillary
+			(
+				&self
+			)
+			-> bool
+			{
+				return !self.is_critical();
+			}
+
+			/// Chunks with a lowercase fourth letter may be copied by editors
+			/// that don't know the chunk, even if the image data is modified
+			pub fn
+			is_safe_to_copy
+			(
+				&self
+			)
+			-> bool
+			{
+				return self.as_string().as_bytes()[3].is_ascii_lowercase();
+			}
 		}
 	}
 }
@@ -94,5 +149,27 @@ build_png_chunk_type_enum![
 	(PLTE,  true,       false,      BEFORE_IDAT),
 	(IDAT,  true,       true,       NONE),
 	(IEND,  true,       false,      LAST),
-	(zTXt,  false,      true,       NONE)
+	(cHRM,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(cICP,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(gAMA,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(iCCP,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(mDCV,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(cLLI,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(sBIT,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(sRGB,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(bKGD,  false,      false,      AFTER_PLTE_BEFORE_IDAT),
+	(hIST,  false,      false,      AFTER_PLTE_BEFORE_IDAT),
+	(tRNS,  false,      false,      AFTER_PLTE_BEFORE_IDAT),
+	(pHYs,  false,      false,      BEFORE_IDAT),
+	(sPLT,  false,      true,       BEFORE_IDAT),
+	(acTL,  false,      false,      BEFORE_IDAT),
+	(fcTL,  false,      true,       NONE),
+	(fdAT,  false,      true,       NONE),
+	(tIME,  false,      false,      NONE),
+	(tEXt,  false,      true,       NONE),
+	(zTXt,  false,      true,       NONE),
+	(iTXt,  false,      true,       NONE),
+	(eXIf,  false,      false,      BEFORE_IDAT),
+	(caNv,  false,      false,      BEFORE_IDAT),
+	(orNT,  false,      false,      BEFORE_IDAT)
 ];
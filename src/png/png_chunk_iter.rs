@@ -0,0 +1,91 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use crate::general_file_io::little_exif_error;
+
+use super::PNG_SIGNATURE;
+use super::get_next_chunk_descriptor;
+use super::png_chunk::PngChunk;
+
+/// Lazily iterates over the chunks of a PNG, one chunk at a time.
+/// Each chunk is validated using its CRC before its descriptor is returned.
+/// Malformed chunks result in an `Err` item, after which the iteration
+/// stops. The same applies after the IEND chunk has been reached.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::png_chunk_iter::PngChunkIter;
+///
+/// let file = std::fs::File::open("image.png").unwrap();
+/// for chunk in PngChunkIter::new(file).unwrap()
+/// {
+///     println!("{}", chunk.unwrap().as_string());
+/// }
+/// ```
+pub struct
+PngChunkIter<R: Read + Seek>
+{
+	reader: R,
+	done:   bool,
+}
+
+impl<R: Read + Seek>
+PngChunkIter<R>
+{
+	/// Creates a new iterator over the chunks of the PNG provided by the
+	/// reader. Fails if the PNG signature is not valid.
+	pub fn
+	new
+	(
+		mut reader: R
+	)
+	-> Result<PngChunkIter<R>, std::io::Error>
+	{
+		reader.seek(SeekFrom::Start(0))?;
+
+		let mut signature_buffer = [0u8; 8];
+		reader.read_exact(&mut signature_buffer)?;
+
+		if signature_buffer != PNG_SIGNATURE
+		{
+			return little_exif_error!(InvalidSignature, "Can't open PNG file - Wrong signature!");
+		}
+
+		return Ok(PngChunkIter { reader, done: false });
+	}
+}
+
+impl<R: Read + Seek>
+Iterator
+for
+PngChunkIter<R>
+{
+	type Item = Result<PngChunk, std::io::Error>;
+
+	fn
+	next
+	(
+		&mut self
+	)
+	-> Option<Self::Item>
+	{
+		if self.done
+		{
+			return None;
+		}
+
+		let next_chunk = get_next_chunk_descriptor(&mut self.reader, None);
+
+		match &next_chunk
+		{
+			Ok(chunk) => self.done = chunk.as_string() == "IEND",
+			Err(_)    => self.done = true,
+		}
+
+		return Some(next_chunk);
+	}
+}
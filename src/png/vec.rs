@@ -8,19 +8,35 @@ use std::io::Seek;
 use crc::Crc;
 use crc::CRC_32_ISO_HDLC;
 use miniz_oxide::deflate::compress_to_vec_zlib;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
 
+use crate::exif_datetime::ExifDateTime;
+use crate::exif_tag::ExifTag;
 use crate::general_file_io::*;
+use crate::limits::decompress_zlib;
+use crate::limits::DEFAULT_MAX_DECOMPRESSED_SIZE;
 use crate::metadata::Metadata;
+use crate::metadata::removal_plan::RemovalPlan;
+use crate::metadata::warning::MetadataWarning;
 use crate::util::insert_multiple_at;
 use crate::util::range_remove;
 
 use super::PNG_SIGNATURE;
+use super::PngReadOptions;
+use super::PngTextMode;
+use super::PngWriteOptions;
 use super::RAW_PROFILE_TYPE_EXIF;
+use super::RAW_PROFILE_TYPE_EXIF_TEXT;
+use super::XMP_KEYWORD;
 
 use super::png_chunk::PngChunk;
-use super::decode_metadata_png;
+use super::get_chunk_descriptors;
+use super::decode_iTXt_chunk_data;
+use super::decode_tEXt_zTXt_chunk_data;
+use super::decode_raw_profile_exif_chunk;
+use super::encode_orNT_orientation;
+use super::is_raw_profile_exif_chunk;
 use super::encode_metadata_png;
+use super::get_metadata_insert_position;
 
 fn
 check_signature
@@ -30,6 +46,11 @@ check_signature
 -> Result<Cursor<&Vec<u8>>, std::io::Error>
 {	
 	// Check the signature
+	if file_buffer.len() < PNG_SIGNATURE.len()
+	{
+		return io_error!(UnexpectedEof, "Can't open PNG file - Too short for signature!");
+	}
+
 	let signature_is_valid = file_buffer[0..8].iter()
 		.zip(PNG_SIGNATURE.iter())
 		.filter(|&(read, constant)| read == constant)
@@ -37,7 +58,7 @@ check_signature
 
 	if !signature_is_valid
 	{
-		return io_error!(InvalidData, "Can't open PNG file - Wrong signature!");
+		return little_exif_error!(InvalidSignature, "Can't open PNG file - Wrong signature!");
 	}
 
 	// Signature is valid - can proceed using the data as PNG file
@@ -46,305 +67,1098 @@ check_signature
 	return Ok(cursor);
 }
 
-// TODO: Check if this is also affected by endianness
-// Edit: Should... not? I guess?
-fn
-get_next_chunk_descriptor
+/// Gets the width and height of the image as declared in the IHDR chunk,
+/// which is always the first chunk of a PNG file.
+pub(crate) fn
+read_dimensions
 (
-	cursor: &mut Cursor<&Vec<u8>>
+	file_buffer: &[u8]
 )
--> Result<PngChunk, std::io::Error>
+-> Result<(u32, u32), std::io::Error>
 {
-	// Read the start of the chunk
-	let mut chunk_start = [0u8; 8];
-	let mut bytes_read = cursor.read(&mut chunk_start).unwrap();
+	if !file_buffer.starts_with(&PNG_SIGNATURE)
+	{
+		return little_exif_error!(InvalidSignature, "Can't open PNG file - Wrong signature!");
+	}
 
-	// Check that indeed 8 bytes were read
-	if bytes_read != 8
+	// Skip the signature and the chunk length (8+4 bytes), followed by the
+	// chunk type and 4 bytes each for width and height
+	if file_buffer.len() < 24 || &file_buffer[12..16] != b"IHDR"
 	{
-		return io_error!(Other, "Could not read start of chunk");
+		return io_error!(InvalidData, "Could not find IHDR chunk in PNG data!");
 	}
 
-	// Construct name of chunk and its length
-	let chunk_name = String::from_utf8((&chunk_start[4..8]).to_vec());
-	let mut chunk_length = 0u32;
-	for byte in &chunk_start[0..4]
+	let width  = u32::from_be_bytes([file_buffer[16], file_buffer[17], file_buffer[18], file_buffer[19]]);
+	let height = u32::from_be_bytes([file_buffer[20], file_buffer[21], file_buffer[22], file_buffer[23]]);
+
+	return Ok((width, height));
+}
+
+/// "Parses" the PNG by checking various properties:
+/// - Can the file be opened and is the signature valid?
+/// - Are the various chunks OK or not? For this, the subroutine `get_next_chunk_descriptor` is used
+/// - Does the PNG end with an IEND chunk?
+pub(crate) fn
+parse_png
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<PngChunk>, std::io::Error>
+{
+	let mut cursor = check_signature(file_buffer)?;
+	let (chunks, _) = get_chunk_descriptors(&mut cursor, false, None)?;
+	return Ok(chunks);
+}
+
+/// Parses the PNG like `parse_png` and returns each chunk together with its
+/// absolute byte offset in the file buffer, i.e. the position of its length
+/// field. Allows e.g. visualizing the layout of the file.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::parse_png_with_offsets;
+///
+/// let file_data = std::fs::read("image.png").unwrap();
+/// for (chunk, offset) in parse_png_with_offsets(&file_data).unwrap()
+/// {
+///     println!("{:>10}: {} ({} bytes)", offset, chunk.as_string(), chunk.length());
+/// }
+/// ```
+pub fn
+parse_png_with_offsets
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<(PngChunk, u64)>, std::io::Error>
+{
+	let mut position = PNG_SIGNATURE.len() as u64;
+	let mut chunks   = Vec::new();
+
+	for chunk in parse_png(file_buffer)?
 	{
-		chunk_length = chunk_length * 256 + *byte as u32;
+		// Length, type and CRC fields take up 12 bytes in addition to the data
+		let chunk_size = chunk.length() as u64 + 12;
+		chunks.push((chunk, position));
+		position += chunk_size;
 	}
 
-	// Read chunk data ...
-	let mut chunk_data_buffer = vec![0u8; chunk_length as usize];
-	bytes_read = cursor.read(&mut chunk_data_buffer).unwrap();
-	if bytes_read != chunk_length as usize
+	return Ok(chunks);
+}
+
+/// Same as `parse_png`, but tolerates a PNG that ends without an IEND chunk,
+/// e.g. due to a truncated download. Returns the chunks that could be read
+/// and a flag that indicates whether the IEND chunk was found.
+pub(crate) fn
+parse_png_tolerant
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<(Vec<PngChunk>, bool), std::io::Error>
+{
+	let mut cursor = check_signature(file_buffer)?;
+	return get_chunk_descriptors(&mut cursor, true, None);
+}
+
+/// Parses the PNG for reading metadata from it. If enabled by the options, a
+/// missing IEND chunk is tolerated and reported as warning, otherwise it 
+/// results in an error like with `parse_png`.
+#[allow(non_snake_case)]
+fn
+parse_png_for_reading
+(
+	file_buffer: &Vec<u8>,
+	options:     &PngReadOptions,
+	warnings:    &mut Vec<MetadataWarning>
+)
+-> Result<Vec<PngChunk>, std::io::Error>
+{
+	if !options.tolerate_missing_IEND
 	{
-		return io_error!(Other, "Could not read chunk data");
+		return parse_png(file_buffer);
 	}
 
-	// ... and CRC values
-	let mut chunk_crc_buffer = [0u8; 4];
-	bytes_read = cursor.read(&mut chunk_crc_buffer).unwrap();
-	if bytes_read != 4
+	let (chunks, iend_found) = parse_png_tolerant(file_buffer)?;
+	if !iend_found
 	{
-		return io_error!(Other, "Could not read chunk CRC");
+		warnings.push(MetadataWarning::MissingIend);
 	}
+	return Ok(chunks);
+}
 
-	// Compute CRC on chunk
-	let mut crc_input = Vec::new();
-	crc_input.extend(chunk_start[4..8].iter());
-	crc_input.extend(chunk_data_buffer.iter());
+/// Same as `parse_png`, but does not fail if the CRC check of a chunk fails.
+/// Instead, the mismatches get recorded as warnings and returned alongside
+/// the chunks.
+pub(crate) fn
+parse_png_lenient
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<(Vec<PngChunk>, Vec<String>), std::io::Error>
+{
+	let mut cursor = check_signature(file_buffer)?;
+	let mut crc_mismatches = Vec::new();
+	let (chunks, _) = get_chunk_descriptors(&mut cursor, false, Some(&mut crc_mismatches))?;
+	return Ok((chunks, crc_mismatches));
+}
 
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&crc_input) as u32;
+// Clears existing metadata chunks (zTXt and tEXt with the "Raw profile type
+// exif" keyword as well as eXIf) from a png file
+// Gets called before writing any new metadata
+#[allow(non_snake_case)]
+pub(crate) fn
+clear_metadata
+(
+	file_buffer : &mut Vec<u8>,
+	lenient_crc: bool
+)
+-> Result<(), std::io::Error>
+{
+	// Parse the PNG - if this fails, the clear operation fails as well
+	let parse_png_result = parse_png_for_writing(file_buffer, lenient_crc)?;
+	clear_metadata_chunks(file_buffer, &parse_png_result, false)?;
+	return Ok(());
+}
 
-	for i in 0..4
+/// Determines the metadata chunks that `clear_metadata` would remove, without
+/// modifying the file
+pub(crate) fn
+plan_clear_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<RemovalPlan>, std::io::Error>
+{
+	let mut plans = Vec::new();
+
+	for (chunk, offset) in parse_png_with_offsets(file_buffer)?
 	{
-		if ((checksum >> (8 * (3-i))) as u8) != chunk_crc_buffer[i]
+		let chunk_start = offset as usize;
+		let data_start  = chunk_start + 8;
+		let data_end    = data_start + chunk.length() as usize;
+
+		let is_metadata_chunk = match chunk.as_string().as_str()
 		{
-			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
+			"eXIf"          => true,
+			"zTXt" | "tEXt" => is_raw_profile_exif_chunk(&chunk.as_string(), &file_buffer[data_start..data_end], false),
+			_               => false,
+		};
+
+		if is_metadata_chunk
+		{
+			// Length, type and CRC fields take up 12 bytes in addition to the data
+			plans.push(RemovalPlan::new(&chunk.as_string(), chunk_start..data_end + 4));
 		}
 	}
 
-	// If validating the chunk using the CRC was successful, return its descriptor
-	// Note: chunk_length does NOT include the +4 for the CRC area!
-	if let Ok(png_chunk) = PngChunk::from_string(
-		&chunk_name.unwrap(),
-		chunk_length
-	)
+	return Ok(plans);
+}
+
+/// Builds a complete PNG chunk (length, type, data and CRC) from the given
+/// chunk type and data. The CRC covers the type and the data.
+fn
+encode_png_chunk
+(
+	chunk_type_and_data: &[u8]
+)
+-> Vec<u8>
+{
+	let length   = chunk_type_and_data.len() as u32 - 4;
+	let checksum = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(chunk_type_and_data);
+
+	let mut chunk = Vec::with_capacity(chunk_type_and_data.len() + 8);
+	chunk.extend(length.to_be_bytes().iter());
+	chunk.extend(chunk_type_and_data.iter());
+	chunk.extend(checksum.to_be_bytes().iter());
+
+	return chunk;
+}
+
+/// Parses the PNG before modifying it, either strictly or - if `lenient_crc`
+/// is set - ignoring chunks with mismatching CRCs
+fn
+parse_png_for_writing
+(
+	file_buffer: &Vec<u8>,
+	lenient_crc: bool
+)
+-> Result<Vec<PngChunk>, std::io::Error>
+{
+	if lenient_crc
 	{
-		return Ok(png_chunk);
+		return Ok(parse_png_lenient(file_buffer)?.0);
 	}
-	else
+	return parse_png(file_buffer);
+}
+
+/// Removes the metadata chunks, using the chunks of the already parsed PNG.
+/// Text chunks using the "Raw profile type APP1" keyword are only removed if
+/// `match_raw_profile_app1` is set.
+/// Returns the chunks that are still stored in the file afterwards.
+#[allow(non_snake_case)]
+fn
+clear_metadata_chunks<'a>
+(
+	file_buffer:            &mut Vec<u8>,
+	parse_png_result:       &'a [PngChunk],
+	match_raw_profile_app1: bool
+)
+-> Result<Vec<&'a PngChunk>, std::io::Error>
+{
+	let mut remaining_chunks = Vec::new();
+
+	// Parsed PNG is Ok to use - Go through the chunks
+	let mut cursor = Cursor::new(file_buffer);
+	cursor.set_position(8);
+
+	for chunk in parse_png_result
 	{
-		return io_error!(Other, "Invalid PNG chunk name");
+		// Remember where this chunk starts in case it needs to be removed
+		let chunk_start = cursor.position();
+
+		match chunk.as_string().as_str()
+		{
+			"eXIf" => {
+				// Always remove eXIf chunks
+				cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
+			},
+
+			"zTXt" | "tEXt" => {
+				// Skip chunk length and type (4+4 Bytes)
+				cursor.seek(std::io::SeekFrom::Current(4+4))?;
+
+				// Read chunk data into buffer for checking that this is the 
+				// correct chunk to delete
+				let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
+
+				if cursor.read(&mut zTXt_chunk_data)? != chunk.length() as usize
+				{
+					return io_error!(Other, "Could not read chunk data");
+				}
+
+				// Skip the CRC as it is not important at this point
+				cursor.seek(std::io::SeekFrom::Current(4))?;
+
+				// If this is not the correct text chunk, ignore current
+				// (wrong) chunk and continue with next chunk
+				if !is_raw_profile_exif_chunk(&chunk.as_string(), &zTXt_chunk_data, match_raw_profile_app1)
+				{	
+					remaining_chunks.push(chunk);
+					continue;
+				}
+			},
+
+			_ => {
+				// Not a metadata chunk, jump to the next chunk
+				cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
+				remaining_chunks.push(chunk);
+				continue;
+			}
+		}
+
+		// We have now established that this is a chunk to delete
+		// Afterwards, the next chunk starts where the removed one started
+		let remove_start = chunk_start as usize;
+		let remove_end   = cursor.position() as usize;
+		range_remove(cursor.get_mut(), remove_start, remove_end);
+		cursor.set_position(chunk_start);
 	}
+
+	return Ok(remaining_chunks);
 }
 
-/// "Parses" the PNG by checking various properties:
-/// - Can the file be opened and is the signature valid?
-/// - Are the various chunks OK or not? For this, the local subroutine `get_next_chunk_descriptor` is used
+/// Reads the raw EXIF data from the PNG file, see `read_exif_chunk_payload`.
+/// If there is no EXIF data at all and `options.orientation_from_orNT` is 
+/// set, the TIFF data of an `Orientation` tag synthesized from the orNT chunk
+/// is returned instead.
+#[allow(non_snake_case)]
 pub(crate) fn
-parse_png
+read_metadata
 (
-	file_buffer: &Vec<u8>
+	file_buffer: &Vec<u8>,
+	options:     &PngReadOptions,
+	warnings:    &mut Vec<MetadataWarning>
 )
--> Result<Vec<PngChunk>, std::io::Error>
+-> Result<Vec<u8>, std::io::Error>
 {
-	let mut cursor = check_signature(file_buffer)?;
-	let mut chunks = Vec::new();
-
-	loop
+	if let Some(payload) = read_exif_chunk_payload(file_buffer, options, warnings)?
 	{
-		let chunk_descriptor = get_next_chunk_descriptor(&mut cursor)?;
-		chunks.push(chunk_descriptor);
+		return Ok(payload);
+	}
 
-		if chunks.last().unwrap().as_string() == "IEND".to_string()
+	// The orNT chunk is only used if there is no EXIF data at all
+	if options.orientation_from_orNT
+	{
+		if let Some(orientation_exif) = read_orNT_orientation(file_buffer, options)?
 		{
-			break;
+			return Ok(orientation_exif);
 		}
 	}
 
-	return Ok(chunks);
+	return little_exif_error!(NoMetadata, "No metadata found!");
 }
 
-// Clears existing metadata chunk from a png file
-// Gets called before writing any new metadata
+/// Gets the EXIF data stored in the chunks of the PNG file. If the file 
+/// contains an eXIf chunk its payload is returned as is, as it already is a
+/// TIFF/EXIF block. Otherwise, the legacy zTXt (or tEXt) chunk with the "Raw
+/// profile type exif" keyword gets decompressed (if necessary) and decoded. 
+/// If both exist, the eXIf chunk is preferred.
+/// If there are multiple of these text chunks, the first one that can be 
+/// decoded is used. Returns `None` if there is no such chunk at all.
 #[allow(non_snake_case)]
-pub(crate) fn
-clear_metadata
+fn
+read_exif_chunk_payload
 (
-	file_buffer: &mut Vec<u8>
+	file_buffer: &Vec<u8>,
+	options:     &PngReadOptions,
+	warnings:    &mut Vec<MetadataWarning>
 )
--> Result<(), std::io::Error>
+-> Result<Option<Vec<u8>>, std::io::Error>
 {
+	// Parse the PNG - if this fails, the read fails as well
+	let parse_png_result = parse_png_for_reading(file_buffer, options, warnings)?;
 
-	// Parse the PNG - if this fails, the clear operation fails as well
-	let parse_png_result = parse_png(&file_buffer)?;
+	// Store the result of decoding the text chunk in case there is no eXIf
+	// chunk further down the file
+	let mut zTXt_result = None;
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	// let mut file = open_write_file(path)?;
-	let mut cursor = Cursor::new(file_buffer);
-	let mut seek_counter = 8u64;
-
-	for chunk in &parse_png_result
+	let mut cursor = check_signature(file_buffer)?;
+	for chunk in parse_png_result
 	{
-		// If this is not a zTXt chunk, jump to the next chunk
-		if chunk.as_string() != String::from("zTXt")
+		// Wrong chunk? Seek to the next one
+		if chunk.as_string() != String::from("zTXt") && chunk.as_string() != String::from("tEXt") && chunk.as_string() != String::from("eXIf")
 		{
-			seek_counter += chunk.length() as u64 + 12;
 			cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
 			continue;
 		}
 
+		// We now have a zTXt, tEXt or eXIf chunk:
 		// Skip chunk length and type (4+4 Bytes)
 		cursor.seek(std::io::SeekFrom::Current(4+4))?;
 
-		// Read chunk data into buffer for checking that this is the 
-		// correct chunk to delete
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-
-		if cursor.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		// Read chunk data into buffer
+		// No need to verify this using CRC as already done by parse_png(path)
+		let mut chunk_data = vec![0u8; chunk.length() as usize];
+		if cursor.read(&mut chunk_data)? != chunk.length() as usize
 		{
 			return io_error!(Other, "Could not read chunk data");
 		}
 
-		// Compare to the "Raw profile type exif" string constant
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
+		// Skip CRC of the current chunk
+		cursor.seek(std::io::SeekFrom::Current(4))?;
+
+		// The eXIf chunk stores the TIFF/EXIF data directly. An empty eXIf
+		// chunk does not contain any metadata, so it gets ignored in favor
+		// of the other chunks
+		if chunk.as_string() == String::from("eXIf")
 		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
+			if chunk_data.is_empty()
 			{
-				correct_zTXt_chunk = false;
-				break;
+				continue;
 			}
+			return Ok(Some(chunk_data));
 		}
 
-		// Skip the CRC as it is not important at this point
-		cursor.seek(std::io::SeekFrom::Current(4))?;
-
-		// If this is not the correct zTXt chunk, ignore current
-		// (wrong) zTXt chunk and continue with next chunk
-		if !correct_zTXt_chunk
-		{	
+		// Check that this is the correct text chunk and that there is not
+		// already a previous one that got decoded successfully
+		if matches!(zTXt_result, Some(Ok(_))) || !is_raw_profile_exif_chunk(&chunk.as_string(), &chunk_data, options.match_raw_profile_app1)
+		{
 			continue;
 		}
-		
-		// We have now established that this is the correct chunk to delete
-		let remove_start = seek_counter as usize;
-		let remove_end   = cursor.position() as usize;
-		range_remove(cursor.get_mut(), remove_start, remove_end);
+
+		// Inflate zlib data if necessary and perform PNG-specific decoding
+		// Files edited by multiple tools may contain several of these chunks,
+		// some of them stale or corrupt. So if decoding fails, the next one
+		// is tried and the first error is only returned if all of them fail
+		let decode_result = decode_raw_profile_exif_chunk(&chunk.as_string(), &chunk_data, options);
+		if decode_result.is_ok() || zTXt_result.is_none()
+		{
+			zTXt_result = Some(decode_result);
+		}
 	}
 
-	return Ok(());
+	return zTXt_result.transpose();
 }
 
+/// Creates the TIFF data of an `Orientation` tag from the first orNT chunk,
+/// see `encode_orNT_orientation`. Returns `None` if there is no orNT chunk
+/// or if its value is invalid.
 #[allow(non_snake_case)]
-pub(crate) fn
-read_metadata
+fn
+read_orNT_orientation
+(
+	file_buffer: &Vec<u8>,
+	options:     &PngReadOptions
+)
+-> Result<Option<Vec<u8>>, std::io::Error>
+{
+	// A missing IEND chunk got already reported while looking for the EXIF
+	// chunks, so the warnings of this second pass are not needed
+	let chunks = parse_png_for_reading(file_buffer, options, &mut Vec::new())?;
+
+	let mut position = PNG_SIGNATURE.len();
+	for chunk in &chunks
+	{
+		if chunk.as_string() == String::from("orNT")
+		{
+			// Skip chunk length and type (4+4 Bytes)
+			let chunk_data = &file_buffer[position+8..position+8+chunk.length() as usize];
+			return Ok(encode_orNT_orientation(chunk_data));
+		}
+
+		position += chunk.length() as usize + 12;
+	}
+
+	return Ok(None);
+}
+
+
+
+/// Reads the raw EXIF data from the PNG file without decoding its IFDs, e.g.
+/// for inspecting metadata that little_exif fails to decode. The data of a
+/// "Raw profile type exif" text chunk is returned decompressed and decoded 
+/// from its hex representation, starting with the "Exif\0\0" header. The
+/// payload of an eXIf chunk is returned as it is. In contrast to reading the
+/// metadata, nothing gets synthesized from other chunks like orNT.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::read_metadata_raw;
+///
+/// let file_data = std::fs::read("image.png").unwrap();
+/// let raw_exif  = read_metadata_raw(&file_data).unwrap();
+/// std::fs::write("exif.bin", raw_exif).unwrap();
+/// ```
+pub fn
+read_metadata_raw
 (
 	file_buffer: &Vec<u8>
 )
 -> Result<Vec<u8>, std::io::Error>
+{
+	match read_exif_chunk_payload(file_buffer, &PngReadOptions::default(), &mut Vec::new())?
+	{
+		Some(payload) => return Ok(payload),
+		None          => return little_exif_error!(NoMetadata, "No EXIF chunk found!"),
+	}
+}
+
+
+
+/// Reads the XMP packet stored in an iTXt chunk with the keyword 
+/// "XML:com.adobe.xmp" and returns it as UTF-8 string
+#[allow(non_snake_case)]
+pub(crate) fn
+read_xmp
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<String, std::io::Error>
 {
 	// Parse the PNG - if this fails, the read fails as well
-	let parse_png_result = parse_png(file_buffer)?;
+	// There is no way of reporting warnings here, so a missing IEND chunk is
+	// tolerated silently like with the default options
+	let parse_png_result = parse_png_for_reading(file_buffer, &PngReadOptions::default(), &mut Vec::new())?;
 
-	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	let mut cursor = check_signature(file_buffer).unwrap();
-	for chunk in &parse_png_result
+	let mut cursor = check_signature(file_buffer)?;
+	for chunk in parse_png_result
 	{
 		// Wrong chunk? Seek to the next one
-		if chunk.as_string() != String::from("zTXt")
+		if chunk.as_string() != String::from("iTXt")
 		{
 			cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
 			continue;
 		}
 
-		// We now have a zTXt chunk:
 		// Skip chunk length and type (4+4 Bytes)
 		cursor.seek(std::io::SeekFrom::Current(4+4))?;
 
-		// Read chunk data into buffer
-		// No need to verify this using CRC as already done by parse_png(path)
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if cursor.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		let mut iTXt_chunk_data = vec![0u8; chunk.length() as usize];
+		cursor.read_exact(&mut iTXt_chunk_data)?;
+
+		// Skip the CRC as this got already checked by parse_png
+		cursor.seek(std::io::SeekFrom::Current(4))?;
+
+		let (keyword, text) = decode_iTXt_chunk_data(&iTXt_chunk_data)?;
+		if keyword == XMP_KEYWORD
 		{
-			return io_error!(Other, "Could not read chunk data");
+			return Ok(text);
 		}
+	}
+
+	return little_exif_error!(NoMetadata, "No XMP data found!");
+}
 
-		// Check that this is the correct zTXt chunk...
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
+/// Reads the ICC profile stored in the iCCP chunk, which consists of the
+/// profile name followed by a NUL separator, the compression method (0 for
+/// zlib) and the compressed profile, and returns the inflated profile
+#[allow(non_snake_case)]
+pub(crate) fn
+read_icc_profile
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	// Same as for the XMP data, a missing IEND chunk is tolerated silently
+	let parse_png_result = parse_png_for_reading(file_buffer, &PngReadOptions::default(), &mut Vec::new())?;
+
+	let mut cursor = check_signature(file_buffer)?;
+	for chunk in parse_png_result
+	{
+		// Wrong chunk? Seek to the next one
+		if chunk.as_string() != String::from("iCCP")
 		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
+			cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
+			continue;
 		}
 
-		if !correct_zTXt_chunk
+		// Skip chunk length and type (4+4 Bytes)
+		cursor.seek(std::io::SeekFrom::Current(4+4))?;
+
+		let mut iCCP_chunk_data = vec![0u8; chunk.length() as usize];
+		cursor.read_exact(&mut iCCP_chunk_data)?;
+
+		let nul_position = match iCCP_chunk_data.iter().position(|byte| *byte == 0x00)
 		{
-			// Skip CRC from current (wrong) zTXt chunk and continue
-			cursor.seek(std::io::SeekFrom::Current(4))?;
-			continue;
+			Some(nul_position) => nul_position,
+			None               => return io_error!(InvalidData, "Missing NUL separator in iCCP chunk!"),
+		};
+
+		match iCCP_chunk_data.get(nul_position + 1)
+		{
+			Some(0x00) => return decompress_zlib(&iCCP_chunk_data[nul_position + 2..], DEFAULT_MAX_DECOMPRESSED_SIZE),
+			_          => return io_error!(InvalidData, "Unknown compression method in iCCP chunk!"),
 		}
+	}
+
+	return little_exif_error!(NoMetadata, "No ICC profile found!");
+}
+
+/// Writes the metadata to the PNG file buffer, either as zTXt chunk using the
+/// "Raw profile type exif" keyword (if `options.as_zTXt_chunk` is true) or as eXIf
+/// chunk that stores the TIFF/EXIF data directly.
+/// Any previously stored EXIF metadata gets removed first, i.e. all eXIf and
+/// "Raw profile type exif" text chunks, so that the file contains exactly one
+/// EXIF chunk afterwards.
+#[allow(non_snake_case)]
+pub(crate) fn
+write_metadata
+(
+	file_buffer:   &mut Vec<u8>,
+	metadata:      &Metadata,
+	options:       &PngWriteOptions,
+	warnings:      &mut Vec<MetadataWarning>
+)
+-> Result<(), std::io::Error>
+{
+	// Parse the PNG once and check its validity, so it is safe to assume 
+	// that is, in fact, a usable PNG file. The result is used for clearing
+	// the existing metadata as well as for determining where to insert the
+	// new chunk
+	let chunks = parse_png_for_writing(file_buffer, options.lenient_crc)?;
+
+	// Everything that may fail is done before modifying the file buffer, so
+	// that it is left untouched in case of an error
+	let encoded_metadata = metadata.encode_with_warnings(warnings)?;
 
-		// Decode zlib data...
-		if let Ok(decompressed_data) = decompress_to_vec_zlib(&zTXt_chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
+	// Without an offset to UTC the time in the tIME chunk would be wrong
+	let tIME_chunk = match options.mirror_datetime_to_tIME
+	{
+		true  => metadata.get_datetime()
+			.filter(|datetime| datetime.offset_minutes.is_some())
+			.map(|datetime| encode_tIME_chunk(&datetime)),
+		false => None,
+	};
+
+	let pHYs_chunk = match options.sync_pHYs_from_exif
+	{
+		true  => encode_pHYs_chunk(metadata)?,
+		false => None,
+	};
+
+	if tIME_chunk.is_some() || pHYs_chunk.is_some()
+	{
+		get_position_after_IHDR(&chunks)?;
+	}
+
+	let remaining_chunks = clear_metadata_chunks(file_buffer, &chunks, options.match_raw_profile_app1)?;
+
+	// A zTXt chunk gets inserted directly after the IHDR chunk, an eXIf
+	// chunk after PLTE and iCCP but before the first IDAT chunk. This is
+	// allowed as the ordering constraints of other chunks (e.g. iCCP before
+	// PLTE) only concern their position relative to the critical chunks 
+	// PLTE and IDAT, which stays unchanged. As nothing else gets moved, the
+	// relative order of all other chunks is preserved as well.
+	let seek_start = get_metadata_insert_position(remaining_chunks, options.as_zTXt_chunk);
+
+	let mut chunk_data: Vec<u8>;
+	if options.as_zTXt_chunk
+	{
+		// Encode the data specifically for PNG
+		let encoded_metadata = encode_metadata_png(&encoded_metadata);
+
+		match options.text_mode
 		{
-			// ...and perform PNG-specific decoding & return the result
-			return Ok(decode_metadata_png(&decompressed_data).unwrap());
+			PngTextMode::ZTxtCompressed => {
+				// Build data of new chunk using zlib compression with the given level
+				chunk_data = vec![0x7a, 0x54, 0x58, 0x74];
+				chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
+				chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, options.zlib_level).iter());
+			},
+			PngTextMode::TextUncompressed => {
+				// The tEXt chunk stores the encoded data as it is
+				chunk_data = vec![0x74, 0x45, 0x58, 0x74];
+				chunk_data.extend(RAW_PROFILE_TYPE_EXIF_TEXT.iter());
+				chunk_data.extend(encoded_metadata.iter());
+			},
 		}
-		else
+	}
+	else
+	{
+		// The eXIf chunk simply contains the encoded metadata
+		chunk_data = vec![0x65, 0x58, 0x49, 0x66];
+		chunk_data.extend(encoded_metadata.iter());
+	}
+
+	// Write the complete chunk with a single insertion, so that the tail of
+	// the file buffer only needs to be moved once
+	insert_multiple_at(file_buffer, seek_start, &mut encode_png_chunk(&chunk_data));
+
+	if let Some(tIME_chunk) = tIME_chunk
+	{
+		replace_or_insert_chunk(file_buffer, "tIME", tIME_chunk)?;
+	}
+
+	if let Some(pHYs_chunk) = pHYs_chunk
+	{
+		replace_or_insert_chunk(file_buffer, "pHYs", pHYs_chunk)?;
+	}
+
+	return Ok(());
+}
+
+/// Gets the position directly after the IHDR chunk, which has to be the
+/// first chunk of the PNG
+#[allow(non_snake_case)]
+pub(super) fn
+get_position_after_IHDR
+(
+	chunks: &[PngChunk]
+)
+-> Result<usize, std::io::Error>
+{
+	match chunks.first()
+	{
+		Some(chunk) if chunk.as_string() == "IHDR"
+			=> return Ok(PNG_SIGNATURE.len() + chunk.length() as usize + 12),
+		_
+			=> return io_error!(InvalidData, "PNG does not start with an IHDR chunk!"),
+	}
+}
+
+/// Reads the last-modification time of the image from the tIME chunk of the
+/// PNG file buffer. As the tIME chunk is supposed to store the time in UTC,
+/// the offset of the result is set to zero.
+/// Returns `None` if there is no tIME chunk or if its contents are invalid.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::read_png_time;
+///
+/// let file_data = std::fs::read("image.png").unwrap();
+/// if let Some(datetime) = read_png_time(&file_data)
+/// {
+///     println!("Last modified in {}", datetime.year);
+/// }
+/// ```
+pub fn
+read_png_time
+(
+	file_buffer: &Vec<u8>
+)
+-> Option<ExifDateTime>
+{
+	let (chunks, _) = parse_png_tolerant(file_buffer).ok()?;
+
+	let mut position = PNG_SIGNATURE.len();
+	for chunk in &chunks
+	{
+		if chunk.as_string() == "tIME" && chunk.length() == 7
 		{
-			return io_error!(Other, "Could not inflate compressed chunk data!");
+			// Skip chunk length and type (4+4 Bytes)
+			let data = &file_buffer[position+8..position+15];
+
+			// Reuse the validation of the EXIF datetime format
+			let mut datetime = ExifDateTime::from_exif_string(&format!(
+				"{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+				u16::from_be_bytes([data[0], data[1]]),
+				data[2], data[3], data[4], data[5], data[6]
+			))?;
+			datetime.offset_minutes = Some(0);
+			return Some(datetime);
 		}
+
+		position += chunk.length() as usize + 12;
 	}
 
-	return io_error!(Other, "No metadata found!");
+	return None;
+}
 
+/// Sets the last-modification time of the image stored in the tIME chunk of
+/// the PNG file buffer. An existing tIME chunk gets replaced, otherwise a new
+/// one is inserted after the IHDR chunk. 
+/// As the tIME chunk is supposed to store the time in UTC, the given datetime
+/// gets converted to UTC using its offset. If the offset is unknown, it is
+/// written as it is.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::exif_datetime::ExifDateTime;
+/// use little_exif::png::vec::write_png_time;
+///
+/// let mut file_data = std::fs::read("image.png").unwrap();
+/// let datetime = ExifDateTime::from_exif_string("2024:02:29 13:37:00").unwrap();
+/// write_png_time(&mut file_data, &datetime).unwrap();
+/// ```
+#[allow(non_snake_case)]
+pub fn
+write_png_time
+(
+	file_buffer: &mut Vec<u8>,
+	datetime:    &ExifDateTime
+)
+-> Result<(), std::io::Error>
+{
+	return replace_or_insert_chunk(file_buffer, "tIME", encode_tIME_chunk(datetime));
 }
 
+/// Builds the complete tIME chunk (length, type, data and CRC) storing the
+/// given datetime, converted to UTC if its offset is known
+#[allow(non_snake_case)]
+pub(super) fn
+encode_tIME_chunk
+(
+	datetime: &ExifDateTime
+)
+-> Vec<u8>
+{
+	let datetime = datetime.to_utc().unwrap_or(*datetime);
+
+	let mut tIME_chunk = vec![0x74, 0x49, 0x4d, 0x45];
+	tIME_chunk.extend(datetime.year.to_be_bytes().iter());
+	tIME_chunk.extend([datetime.month, datetime.day, datetime.hour, datetime.minute, datetime.second].iter());
 
+	return encode_png_chunk(&tIME_chunk);
+}
 
+/// Replaces the first chunk with the given name by the given complete chunk.
+/// If there is no such chunk, the new one is inserted after the IHDR chunk,
+/// which is always before the first IDAT chunk as required e.g. for pHYs.
+pub(super) fn
+replace_or_insert_chunk
+(
+	file_buffer:   &mut Vec<u8>,
+	chunk_name:    &str,
+	mut new_chunk: Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	let (chunks, _) = parse_png_lenient(file_buffer)?;
+
+	// Remove an existing chunk and insert the new one at its position
+	let mut position = PNG_SIGNATURE.len();
+	for chunk in &chunks
+	{
+		if chunk.as_string() == chunk_name
+		{
+			range_remove(file_buffer, position, position + chunk.length() as usize + 12);
+			insert_multiple_at(file_buffer, position, &mut new_chunk);
+			return Ok(());
+		}
+
+		position += chunk.length() as usize + 12;
+	}
+
+	// Otherwise insert it after the IHDR chunk
+	let insert_position = get_position_after_IHDR(&chunks)?;
+	insert_multiple_at(file_buffer, insert_position, &mut new_chunk);
+
+	return Ok(());
+}
+
+/// Sets the physical pixel dimensions stored in the pHYs chunk of the PNG
+/// file buffer to the resolution given by the `XResolution`, `YResolution` 
+/// and `ResolutionUnit` tags of the metadata. As the pHYs chunk stores the
+/// number of pixels per meter, resolutions in pixels per inch (the default
+/// unit) or centimeter get converted and rounded to the nearest integer. If
+/// no unit is given (`ResolutionUnit` of 1), only the aspect ratio is stored.
+/// An existing pHYs chunk gets replaced, otherwise a new one is inserted 
+/// after the IHDR chunk. If one of the resolution tags is missing, the file
+/// buffer is left untouched.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::metadata::Metadata;
+/// use little_exif::png::vec::sync_phys_from_exif;
+///
+/// let mut file_data = std::fs::read("image.png").unwrap();
+/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+/// sync_phys_from_exif(&mut file_data, &metadata).unwrap();
+/// ```
 #[allow(non_snake_case)]
-pub(crate) fn
-write_metadata
+pub fn
+sync_phys_from_exif
 (
 	file_buffer: &mut Vec<u8>,
 	metadata:    &Metadata
 )
 -> Result<(), std::io::Error>
 {
-	// First clear the existing metadata
-	// This also parses the PNG and checks its validity, so it is safe to
-	// assume that is, in fact, a usable PNG file
-	let _ = clear_metadata(file_buffer)?;
+	match encode_pHYs_chunk(metadata)?
+	{
+		Some(pHYs_chunk) => return replace_or_insert_chunk(file_buffer, "pHYs", pHYs_chunk),
+		None             => return Ok(()),
+	}
+}
+
+/// Builds the complete pHYs chunk (length, type, data and CRC) storing the
+/// resolution given by the metadata, see `sync_phys_from_exif`. Returns
+/// `None` if one of the resolution tags is missing.
+#[allow(non_snake_case)]
+pub(super) fn
+encode_pHYs_chunk
+(
+	metadata: &Metadata
+)
+-> Result<Option<Vec<u8>>, std::io::Error>
+{
+	let get_resolution = |tag: ExifTag| -> Option<f64> {
+		return metadata.get_tag(&tag).next()?.as_f64_vec(false)?.first().copied();
+	};
+
+	let (x_resolution, y_resolution) = match (
+		get_resolution(ExifTag::XResolution(Vec::new())),
+		get_resolution(ExifTag::YResolution(Vec::new()))
+	)
+	{
+		(Some(x_resolution), Some(y_resolution)) => (x_resolution, y_resolution),
+		_                                        => return Ok(None),
+	};
+
+	let resolution_unit = match metadata.get_tag(&ExifTag::ResolutionUnit(Vec::new())).next()
+	{
+		Some(ExifTag::ResolutionUnit(unit)) => unit.first().copied().unwrap_or(2),
+		_                                   => 2,
+	};
 
-	let mut IHDR_length = 0u32;
-	if let Ok(chunks) = parse_png(file_buffer)
+	// Determine the factor for converting to pixels per meter as well as the
+	// unit specifier of the pHYs chunk (1 for meter, 0 for unknown)
+	let (factor, unit_specifier) = match resolution_unit
 	{
-		IHDR_length = chunks[0].length();
+		1 => (1.0,            0u8),
+		2 => (1.0 / 0.0254,   1u8),
+		3 => (100.0,          1u8),
+		_ => return io_error!(InvalidData, format!("Unknown ResolutionUnit value {}", resolution_unit)),
+	};
+
+	let to_pixels_per_unit = |resolution: f64| -> Result<u32, std::io::Error> {
+		let pixels_per_unit = (resolution * factor).round();
+		if !(1.0..=u32::MAX as f64).contains(&pixels_per_unit)
+		{
+			return io_error!(InvalidData, format!("Resolution {} can't be stored in pHYs chunk", resolution));
+		}
+		return Ok(pixels_per_unit as u32);
+	};
+
+	// Build the new pHYs chunk, consisting of length, type, data and CRC
+	let mut pHYs_chunk = vec![0x70, 0x48, 0x59, 0x73];
+	pHYs_chunk.extend(to_pixels_per_unit(x_resolution)?.to_be_bytes().iter());
+	pHYs_chunk.extend(to_pixels_per_unit(y_resolution)?.to_be_bytes().iter());
+	pHYs_chunk.push(unit_specifier);
+
+	return Ok(Some(encode_png_chunk(&pHYs_chunk)));
+}
+
+/// Reads the keyword/text pairs of all tEXt, zTXt and iTXt chunks of the PNG
+/// file buffer in the order they are stored in the file, e.g. for reading
+/// the "Title", "Author" or "Software" text. zTXt and compressed iTXt chunks
+/// get decompressed. Note that this includes the chunks used for storing 
+/// EXIF ("Raw profile type exif") and XMP ("XML:com.adobe.xmp") data.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::read_png_text_chunks;
+///
+/// let file_data = std::fs::read("image.png").unwrap();
+/// for (keyword, text) in read_png_text_chunks(&file_data).unwrap()
+/// {
+///     println!("{}: {}", keyword, text);
+/// }
+/// ```
+pub fn
+read_png_text_chunks
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<(String, String)>, std::io::Error>
+{
+	let (chunks, _) = parse_png_tolerant(file_buffer)?;
+
+	let mut text_chunks = Vec::new();
+	let mut position    = PNG_SIGNATURE.len();
+	for chunk in &chunks
+	{
+		// Skip chunk length and type (4+4 Bytes)
+		let chunk_name = chunk.as_string();
+		let chunk_data = &file_buffer[position+8..position+8+chunk.length() as usize];
+
+		match chunk_name.as_str()
+		{
+			"tEXt" | "zTXt" => text_chunks.push(decode_tEXt_zTXt_chunk_data(&chunk_name, chunk_data)?),
+			"iTXt"          => text_chunks.push(decode_iTXt_chunk_data(chunk_data)?),
+			_               => (),
+		}
+
+		position += chunk.length() as usize + 12;
 	}
 
-	// Encode the data specifically for PNG and open the image file
-	let encoded_metadata = encode_metadata_png(&metadata.encode()?);
-	let seek_start = 0u64         // Skip ...
-	+ PNG_SIGNATURE.len() as u64  // PNG Signature
-	+ IHDR_length         as u64  // IHDR data section
-	+ 12                  as u64; // rest of IHDR chunk (length, type, CRC)
+	return Ok(text_chunks);
+}
+
+/// Sets the text for the given keyword by writing a tEXt chunk (or a zTXt 
+/// chunk if `compress` is true) to the PNG file buffer. Existing text chunks
+/// with the same keyword get removed, the new chunk is inserted after the
+/// IHDR chunk. 
+/// The keyword must consist of 1-79 characters and, like the text, must be
+/// representable using Latin-1 as required by the PNG specification.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::set_png_text_chunk;
+///
+/// let mut file_data = std::fs::read("image.png").unwrap();
+/// set_png_text_chunk(&mut file_data, "Author", "Jane Doe", false).unwrap();
+/// ```
+pub fn
+set_png_text_chunk
+(
+	file_buffer: &mut Vec<u8>,
+	keyword:     &str,
+	text:        &str,
+	compress:    bool
+)
+-> Result<(), std::io::Error>
+{
+	let to_latin1 = |string: &str| -> Option<Vec<u8>> {
+		string.chars().map(|character| u8::try_from(character).ok()).collect()
+	};
+
+	let keyword_data = match to_latin1(keyword)
+	{
+		Some(data) if !data.is_empty() && data.len() <= 79 && !data.contains(&0x00) => data,
+		_ => return io_error!(InvalidInput, format!("Invalid PNG text chunk keyword: {:?}", keyword)),
+	};
 
-	// Build data of new chunk using zlib compression (level=8 -> default)
-	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
-	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
-	zTXt_chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, 8).iter());
+	let text_data = match to_latin1(text)
+	{
+		Some(data) => data,
+		None       => return io_error!(InvalidInput, "PNG text chunks can only store Latin-1 text!"),
+	};
 
-	// Compute CRC and append it to the chunk data
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&zTXt_chunk_data) as u32;
-	for i in 0..4
+	// Build the new chunk, consisting of length, type, data and CRC
+	let mut text_chunk;
+	if compress
+	{
+		text_chunk = vec![0x7a, 0x54, 0x58, 0x74];
+		text_chunk.extend(keyword_data.iter());
+		text_chunk.extend([0x00, 0x00].iter());
+		text_chunk.extend(compress_to_vec_zlib(&text_data, 8).iter());
+	}
+	else
+	{
+		text_chunk = vec![0x74, 0x45, 0x58, 0x74];
+		text_chunk.extend(keyword_data.iter());
+		text_chunk.push(0x00);
+		text_chunk.extend(text_data.iter());
+	}
+
+	let mut text_chunk = encode_png_chunk(&text_chunk);
+
+	// Remove existing text chunks with the same keyword, starting with the
+	// last one so that the positions of the other chunks stay valid
+	let (chunks, _) = parse_png_lenient(file_buffer)?;
+
+	let mut chunk_ranges = Vec::new();
+	let mut position     = PNG_SIGNATURE.len();
+	for chunk in &chunks
 	{
-		zTXt_chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
+		let end = position + chunk.length() as usize + 12;
+		if ["tEXt", "zTXt", "iTXt"].contains(&chunk.as_string().as_str())
+		&& file_buffer[position+8..end-4].starts_with(&keyword_data)
+		&& file_buffer.get(position + 8 + keyword_data.len()) == Some(&0x00)
+		{
+			chunk_ranges.push((position, end));
+		}
+		position = end;
 	}
 
-	// Prepare the length of the new chunk (subtracting 8 for type and CRC) for
-	// inserting prior to the new chunk
-	let     chunk_data_len        = zTXt_chunk_data.len() as u32 - 8;
-	let mut chunk_data_len_buffer = [0u8; 4];
-	for i in 0..4
+	for (start, end) in chunk_ranges.into_iter().rev()
 	{
-		chunk_data_len_buffer[i] = (chunk_data_len >> (8 * (3-i))) as u8;
+		range_remove(file_buffer, start, end);
 	}
-	
-	// Write data of new chunk length and chunk itself
-	let insert_position = seek_start as usize;
-	insert_multiple_at(file_buffer, insert_position,   &mut chunk_data_len_buffer.to_vec());
-	insert_multiple_at(file_buffer, insert_position+4, &mut zTXt_chunk_data);
+
+	// Insert the new chunk after the IHDR chunk
+	let insert_position = get_position_after_IHDR(&chunks)?;
+	insert_multiple_at(file_buffer, insert_position, &mut text_chunk);
 
 	return Ok(());
 }
 
+/// Recomputes the CRC of every chunk of the PNG file buffer and replaces
+/// the stored CRCs that don't match, e.g. for salvaging a file that got 
+/// mangled by another tool. Returns the number of repaired chunks.
+/// Note that this only fixes the checksums, the chunk data stays untouched.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::png::vec::fix_png_crcs;
+///
+/// let mut file_data = std::fs::read("image.png").unwrap();
+/// let fixed = fix_png_crcs(&mut file_data).unwrap();
+/// println!("Repaired {} chunks", fixed);
+/// ```
+pub fn
+fix_png_crcs
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<usize, std::io::Error>
+{
+	let (chunks, _) = parse_png_lenient(file_buffer)?;
+
+	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+	let mut fixed  = 0;
+
+	let mut position = PNG_SIGNATURE.len();
+	for chunk in &chunks
+	{
+		// The CRC is computed over the chunk type and data, but not the length
+		let crc_position = position + 8 + chunk.length() as usize;
+		let checksum     = crc_struct.checksum(&file_buffer[position+4..crc_position]).to_be_bytes();
+
+		if file_buffer[crc_position..crc_position+4] != checksum
+		{
+			file_buffer[crc_position..crc_position+4].copy_from_slice(&checksum);
+			fixed += 1;
+		}
+
+		position = crc_position + 4;
+	}
+
+	return Ok(fixed);
+}
+
 #[cfg(test)]
 mod tests 
 {
@@ -358,5 +1172,283 @@ mod tests
 		).unwrap();
 		assert_eq!(chunks.len(), 3);
 	}
-	
+
+	#[test]
+	fn
+	truncated_chunk_length_test()
+	-> Result<(), std::io::Error>
+	{
+		// Keep the signature and the IHDR chunk, followed by only two of
+		// the four bytes storing the length of the next chunk
+		let mut file_buffer = std::fs::read("tests/sample2.png")?;
+		file_buffer.truncate(33 + 2);
+
+		assert!(super::parse_png(&file_buffer).is_err());
+		assert!(super::read_metadata(&file_buffer, &super::PngReadOptions::default(), &mut Vec::new()).is_err());
+		assert!(super::read_metadata(&file_buffer[0..4].to_vec(), &super::PngReadOptions::default(), &mut Vec::new()).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	oversized_chunk_length_test()
+	-> Result<(), std::io::Error>
+	{
+		// Keep the signature and the IHDR chunk, followed by a chunk that 
+		// claims to be 0xFFFFFFFF and 2^31-1 bytes long, respectively
+		for length in [[0xff, 0xff, 0xff, 0xff], [0x7f, 0xff, 0xff, 0xff]]
+		{
+			let mut file_buffer = std::fs::read("tests/sample2.png")?;
+			file_buffer.truncate(33);
+			file_buffer.extend(length);
+			file_buffer.extend(b"tEXtsome data");
+
+			assert!(super::parse_png(&file_buffer).is_err());
+			assert!(super::read_metadata(&file_buffer, &super::PngReadOptions::default(), &mut Vec::new()).is_err());
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	lenient_crc_test()
+	-> Result<(), std::io::Error>
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::metadata::Metadata;
+
+		// Break the CRC of the IHDR chunk
+		let mut file_buffer = std::fs::read("tests/sample2.png")?;
+		file_buffer[29] ^= 0xff;
+
+		assert!(super::parse_png(&file_buffer).is_err());
+
+		let (chunks, crc_mismatches) = super::parse_png_lenient(&file_buffer)?;
+		assert_eq!(chunks.last().unwrap().as_string(), "IEND");
+		assert_eq!(crc_mismatches.len(), 1);
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Lenient".to_string()))?;
+
+		// Strict writing fails, lenient writing succeeds
+		assert!(super::write_metadata(
+			&mut file_buffer, 
+			&metadata, 
+			&Default::default(),
+			&mut Vec::new()
+		).is_err());
+
+		metadata.write_to_png_vec(
+			&mut file_buffer, 
+			&super::PngWriteOptions { lenient_crc: true, ..Default::default() }
+		)?;
+
+		let (chunks, crc_mismatches) = super::parse_png_lenient(&file_buffer)?;
+		assert_eq!(chunks[1].as_string(), "zTXt");
+		assert_eq!(crc_mismatches.len(), 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	fix_png_crcs_test()
+	-> Result<(), std::io::Error>
+	{
+		let original_buffer = std::fs::read("tests/sample2.png")?;
+		let mut file_buffer = original_buffer.clone();
+
+		// Nothing to fix for an intact file
+		assert_eq!(super::fix_png_crcs(&mut file_buffer)?, 0);
+		assert_eq!(file_buffer, original_buffer);
+
+		// Break the CRC of the IHDR and the IEND chunk
+		let length = file_buffer.len();
+		file_buffer[29]         ^= 0xff;
+		file_buffer[length - 1] ^= 0xff;
+		assert!(super::parse_png(&file_buffer).is_err());
+
+		assert_eq!(super::fix_png_crcs(&mut file_buffer)?, 2);
+		assert_eq!(file_buffer, original_buffer);
+		assert!(super::parse_png(&file_buffer).is_ok());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	write_metadata_output_test()
+	-> Result<(), std::io::Error>
+	{
+		use crc::Crc;
+		use crc::CRC_32_ISO_HDLC;
+
+		use crate::exif_tag::ExifTag;
+		use crate::metadata::Metadata;
+		use super::PngTextMode;
+		use super::PngWriteOptions;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Single parse".to_string()))?;
+		metadata.set_tag(ExifTag::ISO(vec![1600]))?;
+
+		// Length and CRC of the results produced before the PNG got parsed
+		// only once while writing, for checking that the output stays the same
+		let expected = [
+			(PngWriteOptions { ..Default::default() },                                           64180, 0x603b21ce),
+			(PngWriteOptions { text_mode: PngTextMode::TextUncompressed, ..Default::default() }, 64261, 0xee650f86),
+			(PngWriteOptions { as_zTXt_chunk: false, ..Default::default() },                     64133, 0x47961cb6),
+			(PngWriteOptions { lenient_crc: true, ..Default::default() },                        64180, 0x603b21ce),
+		];
+
+		let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+		for (options, expected_length, expected_checksum) in expected
+		{
+			// Write twice so that the removal of the existing metadata is
+			// covered as well
+			let mut file_buffer = std::fs::read("tests/sample2.png")?;
+			super::write_metadata(&mut file_buffer, &metadata, &options, &mut Vec::new())?;
+			super::write_metadata(&mut file_buffer, &metadata, &options, &mut Vec::new())?;
+
+			assert_eq!(file_buffer.len(),                  expected_length);
+			assert_eq!(crc_struct.checksum(&file_buffer), expected_checksum);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	parsing_truncated_test()
+	-> Result<(), std::io::Error>
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::metadata::Metadata;
+		use crate::metadata::warning::MetadataWarning;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Truncated".to_string()))?;
+
+		let mut file_buffer = std::fs::read("tests/sample2.png")?;
+		super::write_metadata(&mut file_buffer, &metadata, &Default::default(), &mut Vec::new())?;
+
+		// Cut off the image in the middle of the last chunks
+		file_buffer.truncate(file_buffer.len() - 20);
+
+		assert!(super::parse_png(&file_buffer).is_err());
+
+		let (chunks, iend_found) = super::parse_png_tolerant(&file_buffer)?;
+		assert!(!iend_found);
+		assert_eq!(chunks.first().unwrap().as_string(), "IHDR");
+		assert_ne!(chunks.last().unwrap().as_string(),  "IEND");
+
+		// The metadata before the truncation can still be read
+		let read_metadata = Metadata::new_from_vec(
+			&file_buffer, 
+			crate::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+		)?;
+		assert_eq!(
+			read_metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Truncated".to_string()))
+		);
+
+		// This is reported as warning, or as error if not tolerated
+		let (_, warnings) = Metadata::new_from_png_vec_with_warnings(&file_buffer, &Default::default())?;
+		assert_eq!(warnings, vec![MetadataWarning::MissingIend]);
+
+		let strict_options = super::PngReadOptions { tolerate_missing_IEND: false, ..Default::default() };
+		assert!(Metadata::new_from_png_vec(&file_buffer, &strict_options).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	encode_png_chunk_matches_two_inserts_test()
+	-> Result<(), std::io::Error>
+	{
+		use crc::Crc;
+		use crc::CRC_32_ISO_HDLC;
+		use crate::util::insert_multiple_at;
+
+		let original    = std::fs::read("tests/sample2.png")?;
+		let insert_at   = 33;
+		let mut payload = vec![0x65, 0x58, 0x49, 0x66];
+		payload.extend(crate::metadata::Metadata::new().encode()?);
+
+		// Old approach: Append the CRC, then insert the length and the chunk
+		// separately
+		let mut chunk_data = payload.clone();
+		let checksum       = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&chunk_data);
+		chunk_data.extend(checksum.to_be_bytes());
+		let mut length     = (chunk_data.len() as u32 - 8).to_be_bytes().to_vec();
+
+		let mut two_inserts = original.clone();
+		insert_multiple_at(&mut two_inserts, insert_at,   &mut length);
+		insert_multiple_at(&mut two_inserts, insert_at+4, &mut chunk_data);
+
+		let mut single_insert = original.clone();
+		insert_multiple_at(&mut single_insert, insert_at, &mut super::encode_png_chunk(&payload));
+
+		assert_eq!(single_insert, two_inserts);
+		assert!(super::parse_png(&single_insert).is_ok());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	zero_length_chunk_test()
+	-> Result<(), std::io::Error>
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::metadata::Metadata;
+
+		// The CRC of a chunk without data only covers the chunk type, e.g.
+		// the well known CRC of the IEND chunk
+		let iend_chunk = super::encode_png_chunk(b"IEND");
+		assert_eq!(iend_chunk, [0, 0, 0, 0, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82]);
+
+		let original_buffer = std::fs::read("tests/sample2.png")?;
+		assert!(original_buffer.ends_with(&iend_chunk));
+
+		// Insert an empty private and an empty eXIf chunk right after IHDR
+		let mut file_buffer = original_buffer.clone();
+		let mut empty_chunks = super::encode_png_chunk(b"prVt");
+		empty_chunks.extend(super::encode_png_chunk(b"eXIf"));
+		file_buffer.splice(33..33, empty_chunks);
+
+		let chunks = super::parse_png(&file_buffer)?;
+		assert_eq!(chunks[1].as_string(), "prVt");
+		assert_eq!(chunks[1].length(), 0);
+		assert_eq!(chunks[2].as_string(), "eXIf");
+		assert_eq!(chunks[2].length(), 0);
+
+		// The empty eXIf chunk does not count as metadata
+		assert!(super::read_metadata(&file_buffer, &super::PngReadOptions::default(), &mut Vec::new()).is_err());
+
+		// Writing inserts the zTXt chunk right after IHDR, removes the empty
+		// eXIf chunk and keeps the private one
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Zero".to_string()))?;
+		super::write_metadata(&mut file_buffer, &metadata, &Default::default(), &mut Vec::new())?;
+
+		let chunks = super::parse_png(&file_buffer)?;
+		assert_eq!(chunks[1].as_string(), "zTXt");
+		assert_eq!(chunks[2].as_string(), "prVt");
+		assert_eq!(chunks[3].as_string(), "IDAT");
+		assert!(super::read_metadata(&file_buffer, &super::PngReadOptions::default(), &mut Vec::new()).is_ok());
+
+		// An empty eXIf chunk in front of the zTXt chunk is ignored as well
+		file_buffer.splice(33..33, super::encode_png_chunk(b"eXIf"));
+		assert_eq!(
+			Metadata::new_from_vec(&file_buffer, crate::filetype::FileExtension::PNG { as_zTXt_chunk: true })?
+				.get_tag_value_string(&ExifTag::ImageDescription(String::new())),
+			Some("Zero".to_string())
+		);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,271 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Photoshop files (PSD, as well as PSB for large documents) consist of the
+//! following sections:
+//! - The file header, starting with the "8BPS" signature and the version (1
+//!   for PSD, 2 for PSB)
+//! - The color mode data, e.g. the palette of indexed color images
+//! - The image resources, a sequence of blocks that are identified by their
+//!   resource ID. The EXIF data is stored in resource 0x0422 (with IPTC data
+//!   in 0x0404 and XMP data in 0x0424)
+//! - The layer and mask information as well as the image data
+//!
+//! The length fields of the first three sections are 4 bytes long for both
+//! PSD and PSB, only the ones of the last sections use 8 bytes for PSB.
+//! Currently, only reading the metadata is supported.
+
+use std::path::Path;
+
+use crate::general_file_io::io_error;
+use crate::general_file_io::little_exif_error;
+
+pub(crate) const PSD_SIGNATURE: [u8; 4] = [0x38, 0x42, 0x50, 0x53]; // "8BPS"
+
+const PSD_HEADER_LENGTH: usize = 26;
+
+// Image resource blocks are usually identified by "8BIM", but other
+// signatures are used by some applications as well
+const RESOURCE_SIGNATURES: [[u8; 4]; 5] = [
+	*b"8BIM", *b"MeSa", *b"AgHg", *b"PHUT", *b"DCSR"
+];
+
+#[non_exhaustive]
+struct PsdResourceId;
+
+impl PsdResourceId {
+	pub const EXIF: u16 = 0x0422;
+}
+
+/// Reads a big endian u16 at the given position
+fn
+read_u16
+(
+	buffer:   &[u8],
+	position: usize
+)
+-> Result<u16, std::io::Error>
+{
+	match buffer.get(position..position + 2)
+	{
+		Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+		None        => io_error!(UnexpectedEof, "Unexpected end of PSD data!"),
+	}
+}
+
+/// Reads a big endian u32 at the given position
+fn
+read_u32
+(
+	buffer:   &[u8],
+	position: usize
+)
+-> Result<u32, std::io::Error>
+{
+	match buffer.get(position..position + 4)
+	{
+		Some(bytes) => Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+		None        => io_error!(UnexpectedEof, "Unexpected end of PSD data!"),
+	}
+}
+
+/// Checks the signature as well as the version of the file header
+fn
+check_signature
+(
+	file_buffer: &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	if !file_buffer.starts_with(&PSD_SIGNATURE)
+	{
+		return little_exif_error!(InvalidSignature, "Can't open PSD file - Wrong signature!");
+	}
+
+	match read_u16(file_buffer, 4)?
+	{
+		1 | 2   => return Ok(()),
+		version => return io_error!(InvalidData, format!("Unknown PSD version {}!", version)),
+	}
+}
+
+/// Gets the data of the image resource with the given ID, or `None` if there
+/// is no such resource
+fn
+find_image_resource
+(
+	file_buffer: &[u8],
+	resource_id: u16
+)
+-> Result<Option<&[u8]>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	// Skip the color mode data section
+	let color_mode_data_length = read_u32(file_buffer, PSD_HEADER_LENGTH)? as usize;
+	let resources_start        = PSD_HEADER_LENGTH + 4 + color_mode_data_length;
+
+	let resources_length = read_u32(file_buffer, resources_start)? as usize;
+	let mut position     = resources_start + 4;
+	let resources_end    = position.saturating_add(resources_length);
+
+	if resources_end > file_buffer.len()
+	{
+		return io_error!(InvalidData, "Image resources section extends beyond the PSD data!");
+	}
+
+	while position < resources_end
+	{
+		if !RESOURCE_SIGNATURES.iter().any(|signature| file_buffer[position..].starts_with(signature))
+		{
+			return io_error!(InvalidData, "Invalid signature of PSD image resource block!");
+		}
+
+		let id = read_u16(file_buffer, position + 4)?;
+
+		// The name is a Pascal string (length byte followed by the text)
+		// that is padded to an even size
+		let name_length = match file_buffer.get(position + 6)
+		{
+			Some(name_length) => 1 + *name_length as usize,
+			None              => return io_error!(UnexpectedEof, "Unexpected end of PSD data!"),
+		};
+		position += 6 + name_length + name_length % 2;
+
+		// The data is padded to an even size as well
+		let data_length = read_u32(file_buffer, position)? as usize;
+		let data_start  = position + 4;
+		let data_end    = data_start.saturating_add(data_length);
+
+		if data_end > resources_end
+		{
+			return io_error!(InvalidData, "Image resource block extends beyond the image resources section!");
+		}
+
+		if id == resource_id
+		{
+			return Ok(Some(&file_buffer[data_start..data_end]));
+		}
+
+		position = data_end + data_length % 2;
+	}
+
+	return Ok(None);
+}
+
+/// Reads the raw EXIF data from the PSD or PSB file buffer, i.e. the data
+/// of image resource 0x0422, which starts with the TIFF header
+pub(crate) fn
+read_metadata
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	match find_image_resource(file_buffer, PsdResourceId::EXIF)?
+	{
+		Some(exif_data) => return Ok(exif_data.to_vec()),
+		None            => return little_exif_error!(NoMetadata, "No EXIF image resource found in PSD file!"),
+	}
+}
+
+/// Reads the raw EXIF data from the PSD or PSB file at the given path
+pub(crate) fn
+file_read_metadata
+(
+	path: &Path
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return read_metadata(&std::fs::read(path)?);
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// Builds an image resource block with the given ID, name and data
+	fn
+	make_resource
+	(
+		id:   u16,
+		name: &[u8],
+		data: &[u8]
+	)
+	-> Vec<u8>
+	{
+		let mut result = b"8BIM".to_vec();
+		result.extend(id.to_be_bytes());
+		result.push(name.len() as u8);
+		result.extend(name);
+		if (name.len() + 1) % 2 != 0
+		{
+			result.push(0);
+		}
+		result.extend((data.len() as u32).to_be_bytes());
+		result.extend(data);
+		if data.len() % 2 != 0
+		{
+			result.push(0);
+		}
+		return result;
+	}
+
+	/// Builds a minimal PSD (or PSB) file with color mode data and the given
+	/// image resource blocks, without any layers or image data
+	fn
+	get_test_psd
+	(
+		version:   u16,
+		resources: &[Vec<u8>]
+	)
+	-> Vec<u8>
+	{
+		let mut result = PSD_SIGNATURE.to_vec();
+		result.extend(version.to_be_bytes());
+		result.extend([0u8; 6]);
+		result.extend(3u16.to_be_bytes());
+		result.extend(1u32.to_be_bytes());
+		result.extend(1u32.to_be_bytes());
+		result.extend(8u16.to_be_bytes());
+		result.extend(3u16.to_be_bytes());
+
+		// Color mode data
+		result.extend(2u32.to_be_bytes());
+		result.extend([0xab, 0xcd]);
+
+		let resources = resources.concat();
+		result.extend((resources.len() as u32).to_be_bytes());
+		result.extend(resources);
+
+		return result;
+	}
+
+	#[test]
+	fn
+	read_exif_resource()
+	{
+		let tiff_data = b"II*\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+
+		for version in [1, 2]
+		{
+			let psd = get_test_psd(version, &[
+				make_resource(0x03ed, b"",      &[0x00; 16]),
+				make_resource(0x0404, b"IPTC",  &[0x1c, 0x02, 0x00]),
+				make_resource(0x0422, b"",      &tiff_data),
+			]);
+			assert_eq!(read_metadata(&psd).unwrap(), tiff_data);
+		}
+
+		let psd = get_test_psd(1, &[make_resource(0x03ed, b"", &[0x00; 16])]);
+		assert!(read_metadata(&psd).is_err());
+
+		let psd = get_test_psd(3, &[make_resource(0x0422, b"", &tiff_data)]);
+		assert!(read_metadata(&psd).is_err());
+
+		// Truncated image resources section
+		let psd = get_test_psd(1, &[make_resource(0x0422, b"", &tiff_data)]);
+		assert!(read_metadata(&psd[..psd.len() - 4]).is_err());
+	}
+}
@@ -8,9 +8,12 @@ use std::path::Path;
 use crate::general_file_io::open_read_file;
 use crate::general_file_io::open_write_file;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
 
+use super::check_write_support;
 use super::generic_read_metadata;
 use super::generic_write_metadata;
+use super::generic_write_metadata_in_place;
 
 pub(crate) fn
 read_metadata
@@ -30,9 +33,11 @@ clear_metadata
 )
 -> Result<(), std::io::Error>
 {
+	check_write_support(&mut BufReader::new(open_read_file(path)?))?;
+
 	// Read in the data
-	let     raw_data = generic_read_metadata(&mut BufReader::new(open_read_file(path)?));
-	let mut data     = Metadata::general_decoding_wrapper(raw_data)?;
+	let     raw_data = generic_read_metadata(&mut BufReader::new(open_read_file(path)?))?;
+	let mut data     = Metadata::decode_from_vec(&raw_data, &mut Vec::new())?;
 
 	// Remove all IFDs that aren't required
 	data.reduce_to_a_minimum();
@@ -47,10 +52,12 @@ pub(crate) fn
 write_metadata
 (
 	path:     &Path,
-	metadata: &Metadata
+	metadata: &Metadata,
+	warnings: &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
-	let mut buffered_file = BufWriter::new(open_write_file(path)?);
-	return generic_write_metadata(&mut buffered_file, metadata);
+	check_write_support(&mut BufReader::new(open_read_file(path)?))?;
+
+	return generic_write_metadata_in_place(&mut open_write_file(path)?, metadata, warnings);
 }
\ No newline at end of file
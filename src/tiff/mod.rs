@@ -1,16 +1,67 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use std::io::Cursor;
 use std::io::Seek;
 use std::io::Read;
 use std::io::Write;
 
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::general_file_io::little_exif_error;
 use crate::general_file_io::EXIF_HEADER;
+use crate::ifd::ExifTagGroup;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
+use crate::u8conversion::from_u8_vec_macro;
+use crate::u8conversion::to_u8_vec_macro;
+use crate::u8conversion::U8conversion;
 
 pub mod file;
 pub mod vec;
 
+// The byte order marker ("II" for little, "MM" for big endian) followed by 
+// the magic number 42 in the respective byte order
+pub(crate) const TIFF_SIGNATURE_LITTLE_ENDIAN: [u8; 4] = [0x49, 0x49, 0x2a, 0x00];
+pub(crate) const TIFF_SIGNATURE_BIG_ENDIAN:    [u8; 4] = [0x4d, 0x4d, 0x00, 0x2a];
+
+// Same for BigTIFF files, which use the magic number 43 and 8 byte offsets
+pub(crate) const BIG_TIFF_SIGNATURE_LITTLE_ENDIAN: [u8; 4] = [0x49, 0x49, 0x2b, 0x00];
+pub(crate) const BIG_TIFF_SIGNATURE_BIG_ENDIAN:    [u8; 4] = [0x4d, 0x4d, 0x00, 0x2b];
+
+fn
+is_big_tiff_signature
+(
+	signature: &[u8; 4]
+)
+-> bool
+{
+	return *signature == BIG_TIFF_SIGNATURE_LITTLE_ENDIAN 
+		|| *signature == BIG_TIFF_SIGNATURE_BIG_ENDIAN;
+}
+
+/// Writing BigTIFF files is not supported (yet), as the encoder only 
+/// produces regular TIFF data with 4 byte offsets
+fn
+check_write_support
+<T: Seek + Read>
+(
+	cursor: &mut T
+)
+-> Result<(), std::io::Error>
+{
+	let start_position = cursor.stream_position()?;
+	let mut signature_buffer = [0u8; 4];
+	if cursor.read_exact(&mut signature_buffer).is_ok() && is_big_tiff_signature(&signature_buffer)
+	{
+		return io_error!(Unsupported, "Writing metadata to BigTIFF files is not supported yet!");
+	}
+	cursor.seek(std::io::SeekFrom::Start(start_position))?;
+
+	return Ok(());
+}
+
 pub(crate) fn
 generic_write_metadata
 <T: Seek + Write>
@@ -27,6 +78,173 @@ generic_write_metadata
 	return Ok(());
 }
 
+/// Writes the metadata without touching the image data of the TIFF: The
+/// new IFDs get appended to the end of the data and the header is updated
+/// to link to them. The IFDs of the previous metadata stay in the data, but
+/// are no longer referenced.
+/// The tags describing the image data (e.g. `StripOffsets`, `ImageWidth`,
+/// `Compression`, see `reduce_to_a_minimum`) are taken from the current
+/// IFDs, so that they match the image data in the file. Resolution and
+/// thumbnail tags are only taken over if they are not set in the metadata.
+/// Note that SubIFDs of the current IFDs are not taken over.
+pub(crate) fn
+generic_write_metadata_in_place
+<T: Seek + Read + Write>
+(
+	cursor:   &mut T,
+	metadata: &Metadata,
+	warnings: &mut Vec<MetadataWarning>
+)
+-> Result<(), std::io::Error>
+{
+	let start_position = cursor.stream_position()?;
+	let mut tiff_data  = Vec::new();
+	cursor.read_to_end(&mut tiff_data)?;
+
+	if tiff_data.len() > u32::MAX as usize - 3
+	{
+		return io_error!(Unsupported, "TIFF data is too large for appending new IFDs!");
+	}
+
+	// Decode the current metadata for getting the tags that describe the
+	// image data - the signature check lets invalid data fail early. If the
+	// IFDs can't be decoded, writing fails as well, as the new IFDs would not
+	// reference the image data otherwise
+	let raw_data = generic_read_metadata(&mut Cursor::new(&tiff_data))?;
+	let mut current_metadata = Metadata::decode_from_vec(&raw_data, &mut Vec::new())?;
+	current_metadata.reduce_to_a_minimum();
+
+	let endian = match tiff_data.starts_with(&TIFF_SIGNATURE_BIG_ENDIAN)
+	{
+		true  => Endian::Big,
+		false => Endian::Little,
+	};
+	let strip_offsets = read_strip_offsets(&tiff_data, &endian)?;
+
+	// The metadata needs to use the byte order of the file, as the image 
+	// data (e.g. 16 bit samples) is stored using that as well
+	let mut new_metadata = metadata.clone();
+	new_metadata.set_endian(endian.clone());
+
+	for ifd in current_metadata.get_ifds()
+	{
+		let generic_ifd_nr = ifd.get_generic_ifd_nr();
+		let target_ifd     = new_metadata.get_ifd_mut(ExifTagGroup::GENERIC, generic_ifd_nr);
+
+		for tag in ifd.get_tags()
+		{
+			let is_set = target_ifd.get_tags().iter().any(|target_tag| target_tag.as_u16() == tag.as_u16());
+
+			match tag
+			{
+				// The strips stay where they are
+				ExifTag::StripOffsets(_, _) => {
+					match strip_offsets.get(generic_ifd_nr as usize)
+					{
+						Some(Some(offsets)) => target_ifd.set_tag_unchecked(ExifTag::StripOffsets(offsets.clone(), Vec::new())),
+						_                   => return io_error!(InvalidData, format!("Could not determine the strip offsets of IFD {}!", generic_ifd_nr)),
+					}
+				},
+
+				ExifTag::ThumbnailOffset(_, _)
+				| ExifTag::ThumbnailLength(_)
+				| ExifTag::XResolution(_)
+				| ExifTag::YResolution(_)
+				| ExifTag::ResolutionUnit(_)
+				=> if !is_set { target_ifd.set_tag_unchecked(tag.clone()); },
+
+				_ 
+				=> target_ifd.set_tag_unchecked(tag.clone()),
+			}
+		}
+	}
+
+	// Append the new IFDs, aligned to a multiple of 4, and link to them
+	let padding    = (4 - tiff_data.len() % 4) % 4;
+	let ifd_offset = (tiff_data.len() + padding) as u32;
+
+	cursor.seek(std::io::SeekFrom::Start(start_position + tiff_data.len() as u64))?;
+	cursor.write_all(&vec![0u8; padding])?;
+	cursor.write_all(&new_metadata.encode_ifds(ifd_offset, warnings)?)?;
+
+	cursor.seek(std::io::SeekFrom::Start(start_position + 4))?;
+	cursor.write_all(&to_u8_vec_macro!(u32, &ifd_offset, &endian))?;
+
+	return Ok(());
+}
+
+/// Gets the values of the `StripOffsets` tags of the generic IFDs, indexed
+/// by their generic IFD number. This is necessary as the decoder replaces 
+/// the offsets with the strip data.
+fn
+read_strip_offsets
+(
+	tiff_data: &[u8],
+	endian:    &Endian
+)
+-> Result<Vec<Option<Vec<u32>>>, std::io::Error>
+{
+	let read_u16 = |position: usize| -> Result<u16, std::io::Error> {
+		match tiff_data.get(position..position+2)
+		{
+			Some(bytes) => Ok(from_u8_vec_macro!(u16, &bytes.to_vec(), endian)),
+			None        => io_error!(UnexpectedEof, "Could not read IFD entry!"),
+		}
+	};
+	let read_u32 = |position: usize| -> Result<u32, std::io::Error> {
+		match tiff_data.get(position..position+4)
+		{
+			Some(bytes) => Ok(from_u8_vec_macro!(u32, &bytes.to_vec(), endian)),
+			None        => io_error!(UnexpectedEof, "Could not read IFD entry!"),
+		}
+	};
+
+	let mut strip_offsets   = Vec::new();
+	let mut visited_offsets = Vec::new();
+	let mut ifd_offset      = read_u32(4)? as usize;
+
+	while ifd_offset != 0 && !visited_offsets.contains(&ifd_offset)
+	{
+		visited_offsets.push(ifd_offset);
+
+		let entry_count = read_u16(ifd_offset)? as usize;
+		let mut offsets = None;
+
+		for index in 0..entry_count
+		{
+			let entry_position = ifd_offset + 2 + 12 * index;
+			if read_u16(entry_position)? != 0x0111
+			{
+				continue;
+			}
+
+			// The offsets are either stored as INT16U or INT32U values, 
+			// located in the entry itself if they fit into 4 bytes
+			let is_int16u      = read_u16(entry_position + 2)? == 3;
+			let count          = read_u32(entry_position + 4)? as usize;
+			let value_size     = if is_int16u { 2 } else { 4 };
+			let value_position = match count.saturating_mul(value_size) > 4
+			{
+				true  => read_u32(entry_position + 8)? as usize,
+				false => entry_position + 8,
+			};
+
+			offsets = Some((0..count).map(|component| 
+				match is_int16u
+				{
+					true  => read_u16(value_position + 2 * component).map(u32::from),
+					false => read_u32(value_position + 4 * component),
+				}
+			).collect::<Result<Vec<u32>, std::io::Error>>()?);
+		}
+
+		strip_offsets.push(offsets);
+		ifd_offset = read_u32(ifd_offset + 2 + 12 * entry_count)? as usize;
+	}
+
+	return Ok(strip_offsets);
+}
+
 fn
 generic_read_metadata
 <T: Seek + Read>
@@ -35,11 +253,35 @@ generic_read_metadata
 )
 -> Result<Vec<u8>, std::io::Error>
 {
+	// Check the byte order marker and the magic number before handing the
+	// data to the IFD decoder
+	let start_position = cursor.stream_position()?;
+	let mut signature_buffer = [0u8; 4];
+	cursor.read_exact(&mut signature_buffer)?;
+
+	let big_tiff = is_big_tiff_signature(&signature_buffer);
+
+	if signature_buffer != TIFF_SIGNATURE_LITTLE_ENDIAN 
+	&& signature_buffer != TIFF_SIGNATURE_BIG_ENDIAN
+	&& !big_tiff
+	{
+		return little_exif_error!(InvalidSignature, "Can't open TIFF file - Wrong signature!");
+	}
+	cursor.seek(std::io::SeekFrom::Start(start_position))?;
+
 	let mut tiff_with_exif_header = Vec::new();
 	tiff_with_exif_header.extend(EXIF_HEADER);
 
 	let mut buffer = Vec::new();
 	cursor.read_to_end(&mut buffer)?;
+
+	// BigTIFF data is decoded here and handed on as regular TIFF data, as 
+	// the magic number 43 is invalid in any other (i.e. EXIF) context
+	if big_tiff
+	{
+		buffer = Metadata::decode_big_tiff(&buffer)?.encode()?;
+	}
+
 	tiff_with_exif_header.append(&mut buffer);
 	
 	return Ok(tiff_with_exif_header);
@@ -3,10 +3,14 @@
 
 use std::io::Cursor;
 
+use crate::general_file_io::EXIF_HEADER;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
 
+use super::check_write_support;
 use super::generic_read_metadata;
 use super::generic_write_metadata;
+use super::generic_write_metadata_in_place;
 
 pub(crate) fn
 read_metadata
@@ -16,6 +20,15 @@ read_metadata
 -> Result<Vec<u8>, std::io::Error>
 {
 	let mut cursor = Cursor::new(file_buffer);
+
+	// Raw EXIF blocks (e.g. extracted from a JPEG APP1 segment by another
+	// tool) may still start with the "Exif\0\0" header, followed by the
+	// actual TIFF data
+	if file_buffer.starts_with(&EXIF_HEADER)
+	{
+		cursor.set_position(EXIF_HEADER.len() as u64);
+	}
+
 	return generic_read_metadata(&mut cursor);
 }
 
@@ -30,9 +43,11 @@ clear_metadata
 	let mut cursor           = Cursor::new(file_buffer);
 	let     cursor_start_pos = cursor.position();
 
+	check_write_support(&mut cursor)?;
+
 	// Read in the data
-	let     raw_data = generic_read_metadata(&mut cursor);
-	let mut data     = Metadata::general_decoding_wrapper(raw_data)?;
+	let     raw_data = generic_read_metadata(&mut cursor)?;
+	let mut data     = Metadata::decode_from_vec(&raw_data, &mut Vec::new())?;
 
 	// Remove all IFDs that aren't required
 	data.reduce_to_a_minimum();
@@ -46,10 +61,12 @@ pub(crate) fn
 write_metadata
 (
 	file_buffer: &mut Vec<u8>,
-	metadata:    &Metadata
+	metadata:    &Metadata,
+	warnings:    &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
 	let mut cursor = Cursor::new(file_buffer);
-	return generic_write_metadata(&mut cursor, metadata);
+	check_write_support(&mut cursor)?;
+	return generic_write_metadata_in_place(&mut cursor, metadata, warnings);
 }
\ No newline at end of file
@@ -115,17 +115,19 @@ impl U8conversion<String> for String
 			panic!("from_u8_vec (String): Mangled EXIF data encountered!")
 		}
 
-		let mut result = String::new();
-
-		for byte in u8_vec
+		let bytes = u8_vec.iter()
+			.filter(|byte| **byte > 0)
+			.cloned()
+			.collect::<Vec<u8>>();
+
+		// Keep valid UTF-8 as it is so that the string gets encoded into the
+		// same bytes again, otherwise interpret each byte as a character
+		if let Ok(result) = String::from_utf8(bytes.clone())
 		{
-			if *byte > 0
-			{
-				result.push(*byte as char);
-			}
+			return result;
 		}
 
-		return result;
+		return bytes.into_iter().map(|byte| byte as char).collect::<String>();
 	}
 }
 
@@ -1,6 +1,8 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use crate::endian::Endian;
+
 /// Inserts a slice into a vector at a given offset, shifting elements 
 /// starting at the offset towards the end.
 /// Returns 0 (zero) if the operation was successful, non-zero if the offset 
@@ -71,6 +73,53 @@ where T: Copy
     vec.truncate(new_vec_len);
 }
 
+/// Decodes the UTF-16 bytes using the given endianness. Returns `None` if
+/// the number of bytes is odd or the data is not valid UTF-16.
+pub(crate) fn
+decode_utf16
+(
+    bytes:  &[u8],
+    endian: &Endian
+)
+-> Option<String>
+{
+    if bytes.len() % 2 != 0
+    {
+        return None;
+    }
+
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| match endian
+        {
+            Endian::Little => u16::from_le_bytes([pair[0], pair[1]]),
+            Endian::Big    => u16::from_be_bytes([pair[0], pair[1]]),
+        })
+        .collect::<Vec<u16>>();
+
+    return String::from_utf16(&units).ok();
+}
+
+/// Encodes the string as UTF-16 bytes using the given endianness, without
+/// any terminating null character
+pub(crate) fn
+encode_utf16
+(
+    value:  &str,
+    endian: &Endian
+)
+-> Vec<u8>
+{
+    return value
+        .encode_utf16()
+        .flat_map(|unit| match endian
+        {
+            Endian::Little => unit.to_le_bytes(),
+            Endian::Big    => unit.to_be_bytes(),
+        })
+        .collect::<Vec<u8>>();
+}
+
 /*
 /// Inserts a slice into a vector at a given offset, shifting elements 
 /// starting at the offset towards the end.
@@ -10,6 +10,7 @@ use std::path::Path;
 
 use crate::endian::*;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
 use crate::u8conversion::*;
 use crate::general_file_io::*;
 use super::riff_chunk::RiffChunk;
@@ -245,7 +246,7 @@ check_exif_in_file
 	// https://developers.google.com/speed/webp/docs/riff_container#extended_file_format
 	if flag_buffer[0] & 0x08 != 0x08
 	{
-		return io_error!(Other, "No EXIF chunk according to VP8X flags!");
+		return little_exif_error!(NoMetadata, "No EXIF chunk according to VP8X flags!");
 	}
 
 	return Ok((file, parsed_webp_result.unwrap()));
@@ -264,7 +265,7 @@ read_metadata
 {
 	// Check the file signature, parse it, check that it has a VP8X chunk and
 	// the EXIF flag is set there
-	let (mut file, parse_webp_result) = check_exif_in_file(path).unwrap();
+	let (mut file, parse_webp_result) = check_exif_in_file(path)?;
 
 	// At this point we have established that the file has to contain an EXIF
 	// chunk at some point. So, now we need to find & return it
@@ -393,8 +394,8 @@ convert_to_extended_format
 	// Find out what simple type of WebP file we are dealing with
 	let (width, height) = match first_chunk.descriptor().header().as_str()
 	{
-		"VP8" 
-			=> {println!("VP8 !"); todo!()},
+		"VP8 " 
+			=> get_dimension_info_from_vp8_chunk(first_chunk.payload()),
 		"VP8L"
 			=> get_dimension_info_from_vp8l_chunk(first_chunk.payload()),
 		_ 
@@ -435,42 +436,6 @@ convert_to_extended_format
 
 
 
-fn
-get_dimension_info_from_vp8l_chunk
-(
-	payload: &Vec<u8>
-)
--> Result<(u32, u32), std::io::Error>
-{
-	// Get the 4 bytes containing the dimension information
-	// (although we only need 28 bits)
-	// Starting at byte 1 instead of 0 due to the 0x2F byte
-	// See: https://developers.google.com/speed/webp/docs/webp_lossless_bitstream_specification#3_riff_header
-	let width_height_info_buffer = payload[1..5].to_vec();
-	
-	// Convert to a single u32 number for bit-mask operations
-	let width_height_info = from_u8_vec_macro!(u32, &width_height_info_buffer, &Endian::Little);
-	
-	let mut width  = 0;
-	let mut height = 0;
-
-	// Get the first 14 bit to construct the width
-	for bit_index in 0..14
-	{
-		width  |= ((width_height_info >> (27 - bit_index)) & 0x01) << (13 - (bit_index % 14));
-	}
-
-	// Get the next 14 bit to construct the height
-	for bit_index in 14..28
-	{
-		height |= ((width_height_info >> (27 - bit_index)) & 0x01) << (13 - (bit_index % 14));
-	}
-
-	return Ok((width, height));
-}
-
-
-
 fn
 set_exif_flag
 (
@@ -553,6 +518,8 @@ clear_metadata
 				=> return Ok(()),
 			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8L!"
 				=> return Ok(()),
+			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8 !"
+				=> return Ok(()),
 			_
 				=> return Err(exif_check_result.err().unwrap())
 		}
@@ -632,7 +599,8 @@ pub(crate) fn
 write_metadata
 (
 	path:     &Path,
-	metadata: &Metadata
+	metadata: &Metadata,
+	warnings: &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
@@ -640,7 +608,7 @@ write_metadata
 	clear_metadata(path)?;
 
 	// Encode the general metadata format to WebP specifications
-	let encoded_metadata = encode_metadata_webp(&metadata.encode()?);
+	let encoded_metadata = encode_metadata_webp(&metadata.encode_with_warnings(warnings)?);
 
 	// Open the file...
 	let mut file = check_signature(path)?;
@@ -650,16 +618,19 @@ write_metadata
 	// that is both known and should be located *before* the EXIF chunk
 	let pre_exif_chunks = [
 		"VP8X",
-		"VP8",
+		"VP8 ",
 		"VP8L",
 		"ICCP",
-		"ANIM"
+		"ANIM",
+		"ANMF",
+		"ALPH"
 	];
 
 	loop
 	{
 		// Request a chunk descriptor. If this fails, check the error 
 		// Depending on its type, either continue normally or return it
+		let chunk_start_position    = file.stream_position()?;
 		let chunk_descriptor_result = get_next_chunk_descriptor(&mut file);
 
 		if let Ok(chunk_descriptor) = chunk_descriptor_result
@@ -673,8 +644,11 @@ write_metadata
 				chunk_type_found_in_pre_exif_chunks |= pre_exif_chunk.to_lowercase() == chunk_descriptor.header().to_lowercase();
 			}
 
+			// Place the EXIF chunk *before* the first chunk that is not 
+			// supposed to come before it
 			if !chunk_type_found_in_pre_exif_chunks
 			{
+				perform_file_action!(file.seek(SeekFrom::Start(chunk_start_position)));
 				break;
 			}
 		}
@@ -7,6 +7,11 @@ pub(crate) const RIFF_SIGNATURE:       [u8; 4] = [0x52, 0x49, 0x46, 0x46];
 pub(crate) const WEBP_SIGNATURE:       [u8; 4] = [0x57, 0x45, 0x42, 0x50];
 pub(crate) const VP8X_HEADER:          &str    = "VP8X";
 pub(crate) const EXIF_CHUNK_HEADER:    &str    = "EXIF";
+pub(crate) const XMP_CHUNK_HEADER:     &str    = "XMP ";
+
+// The flags stored in the first byte of the VP8X chunk's payload
+pub(crate) const VP8X_EXIF_FLAG:       u8      = 0x08;
+pub(crate) const VP8X_XMP_FLAG:        u8      = 0x04;
 
 use std::fs::File;
 
@@ -16,6 +21,63 @@ use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::to_u8_vec_macro;
 use crate::u8conversion::U8conversion;
 
+/// Gets the canvas dimensions from the payload of a "VP8 " chunk (lossy WebP)
+/// in the format expected by the VP8X chunk, i.e. width and height minus one.
+/// The payload starts with a 3 byte frame tag, followed by the start code
+/// 0x9d 0x01 0x2a and two 16 bit values for width and height, of which the
+/// upper 2 bits each are used for scaling information.
+/// See: https://datatracker.ietf.org/doc/html/rfc6386#section-9.1
+pub(crate) fn
+get_dimension_info_from_vp8_chunk
+(
+	payload: &Vec<u8>
+)
+-> Result<(u32, u32), std::io::Error>
+{
+	if payload.len() < 10 || payload[3..6] != [0x9d, 0x01, 0x2a]
+	{
+		return io_error!(InvalidData, "Could not find start code in 'VP8 ' chunk!");
+	}
+
+	let width  = from_u8_vec_macro!(u16, &payload[6.. 8].to_vec(), &Endian::Little) & 0x3fff;
+	let height = from_u8_vec_macro!(u16, &payload[8..10].to_vec(), &Endian::Little) & 0x3fff;
+
+	if width == 0 || height == 0
+	{
+		return io_error!(InvalidData, "Invalid dimensions in 'VP8 ' chunk!");
+	}
+
+	return Ok(((width - 1) as u32, (height - 1) as u32));
+}
+
+/// Gets the canvas dimensions from the payload of a "VP8L" chunk (lossless 
+/// WebP) in the format expected by the VP8X chunk. The payload starts with
+/// the signature byte 0x2f, followed by 14 bits for the width minus one and
+/// 14 bits for the height minus one (least significant bits first), so these
+/// can be used directly.
+/// See: https://developers.google.com/speed/webp/docs/webp_lossless_bitstream_specification#3_riff_header
+pub(crate) fn
+get_dimension_info_from_vp8l_chunk
+(
+	payload: &Vec<u8>
+)
+-> Result<(u32, u32), std::io::Error>
+{
+	if payload.len() < 5 || payload[0] != 0x2f
+	{
+		return io_error!(InvalidData, "Could not find signature in 'VP8L' chunk!");
+	}
+
+	// Convert the 4 bytes following the signature to a single u32 number for
+	// bit-mask operations (although we only need 28 bits)
+	let width_height_info = from_u8_vec_macro!(u32, &payload[1..5].to_vec(), &Endian::Little);
+
+	let width  =  width_height_info        & 0x3fff;
+	let height = (width_height_info >> 14) & 0x3fff;
+
+	return Ok((width, height));
+}
+
 fn
 check_riff_signature
 (
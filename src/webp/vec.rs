@@ -7,7 +7,10 @@ use std::io::Seek;
 use std::io::Write;
 
 use crate::general_file_io::EXIF_HEADER;
+use crate::general_file_io::little_exif_error;
 use crate::metadata::Metadata;
+use crate::metadata::warning::MetadataWarning;
+use crate::metadata::removal_plan::RemovalPlan;
 use crate::util::insert_multiple_at;
 use crate::util::range_remove;
 
@@ -179,6 +182,54 @@ parse_webp
 
 
 
+/// Gets the width and height of the image, using the canvas size stored in
+/// the VP8X chunk for the extended format or the dimensions of the image
+/// data itself for the simple formats (lossy and lossless).
+pub(crate) fn
+read_dimensions
+(
+	file_buffer: &[u8]
+)
+-> Result<(u32, u32), std::io::Error>
+{
+	if file_buffer.len() < 12
+	|| file_buffer[0.. 4] != RIFF_SIGNATURE
+	|| file_buffer[8..12] != WEBP_SIGNATURE
+	{
+		return io_error!(InvalidData, "Can't open WebP file - Expected RIFF and WEBP signature!");
+	}
+
+	// The first chunk starts after the 12 bytes of the RIFF header, its 
+	// payload after the 4 bytes of the chunk header and the 4 bytes of the 
+	// chunk size. At most 10 bytes of the payload are needed.
+	if file_buffer.len() < 20
+	{
+		return io_error!(UnexpectedEof, "Could not read first chunk of WebP data!");
+	}
+	let header  = String::from_utf8_lossy(&file_buffer[12..16]).to_string();
+	let payload = file_buffer[20..std::cmp::min(30, file_buffer.len())].to_vec();
+
+	// All three variants store the width and height minus one
+	let (width, height) = match header.as_str()
+	{
+		VP8X_HEADER if payload.len() == 10
+			=> (
+				from_u8_vec_macro!(u32, &[&payload[4..7], &[0x00]].concat(), &Endian::Little),
+				from_u8_vec_macro!(u32, &[&payload[7..10], &[0x00]].concat(), &Endian::Little),
+			),
+		"VP8 "
+			=> get_dimension_info_from_vp8_chunk(&payload)?,
+		"VP8L"
+			=> get_dimension_info_from_vp8l_chunk(&payload)?,
+		_
+			=> return io_error!(InvalidData, format!("Could not read dimensions from {:?} chunk!", header)),
+	};
+
+	return Ok((width + 1, height + 1));
+}
+
+
+
 fn
 check_exif_in_file
 (
@@ -232,7 +283,7 @@ check_exif_in_file
 	// https://developers.google.com/speed/webp/docs/riff_container#extended_file_format
 	if flag_buffer[0] & 0x08 != 0x08
 	{
-		return io_error!(Other, "No EXIF chunk according to VP8X flags!");
+		return little_exif_error!(NoMetadata, "No EXIF chunk according to VP8X flags!");
 	}
 
 	return Ok((cursor, parsed_webp_result.unwrap()));
@@ -251,7 +302,7 @@ read_metadata
 {
 	// Check the signature, parse it, check that it has a VP8X chunk and the
 	// EXIF flag is set there
-	let (mut cursor, parse_webp_result) = check_exif_in_file(file_buffer).unwrap();
+	let (mut cursor, parse_webp_result) = check_exif_in_file(file_buffer)?;
 
 	// At this point we have established that the file has to contain an EXIF
 	// chunk at some point. So, now we need to find & return it
@@ -379,8 +430,8 @@ convert_to_extended_format
 	// Find out what simple type of WebP file we are dealing with
 	let (width, height) = match first_chunk.descriptor().header().as_str()
 	{
-		"VP8" 
-			=> {println!("VP8 !"); todo!()},
+		"VP8 " 
+			=> get_dimension_info_from_vp8_chunk(first_chunk.payload()),
 		"VP8L"
 			=> get_dimension_info_from_vp8l_chunk(first_chunk.payload()),
 		_ 
@@ -412,46 +463,27 @@ convert_to_extended_format
 
 
 fn
-get_dimension_info_from_vp8l_chunk
+set_exif_flag
 (
-	payload: &Vec<u8>
+	cursor: &mut Cursor<&mut Vec<u8>>,
+	exif_flag_value: bool
 )
--> Result<(u32, u32), std::io::Error>
+-> Result<(), std::io::Error>
 {
-	// Get the 4 bytes containing the dimension information
-	// (although we only need 28 bits)
-	// Starting at byte 1 instead of 0 due to the 0x2F byte
-	// See: https://developers.google.com/speed/webp/docs/webp_lossless_bitstream_specification#3_riff_header
-	let width_height_info_buffer = payload[1..5].to_vec();
-	
-	// Convert to a single u32 number for bit-mask operations
-	let width_height_info = from_u8_vec_macro!(u32, &width_height_info_buffer, &Endian::Little);
-	
-	let mut width  = 0;
-	let mut height = 0;
-
-	// Get the first 14 bit to construct the width
-	for bit_index in 0..14
-	{
-		width  |= ((width_height_info >> (27 - bit_index)) & 0x01) << (13 - (bit_index % 14));
-	}
-
-	// Get the next 14 bit to construct the height
-	for bit_index in 14..28
-	{
-		height |= ((width_height_info >> (27 - bit_index)) & 0x01) << (13 - (bit_index % 14));
-	}
-
-	return Ok((width, height));
+	return set_vp8x_flag(cursor, VP8X_EXIF_FLAG, exif_flag_value);
 }
 
 
 
+/// Sets or unsets the given flag (e.g. `VP8X_XMP_FLAG`) of the VP8X chunk. 
+/// If the file is in the simple format, it gets converted to the extended
+/// format first.
 fn
-set_exif_flag
+set_vp8x_flag
 (
-	cursor: &mut Cursor<&mut Vec<u8>>,
-	exif_flag_value: bool
+	cursor:     &mut Cursor<&mut Vec<u8>>,
+	flag:       u8,
+	flag_value: bool
 )
 -> Result<(), std::io::Error>
 {
@@ -475,16 +507,16 @@ set_exif_flag
 	}	
 
 	// At this point we know that we have a VP8X chunk at the expected location
-	// Mask the old flag by either or-ing with 1 at the EXIF flag position for
-	// setting it to true, or and-ing with 1 everywhere but the EXIF flag pos
+	// Mask the old flag by either or-ing with 1 at the flag position for
+	// setting it to true, or and-ing with 1 everywhere but the flag position
 	// to set it to false
-	cursor.get_mut()[20] = if exif_flag_value
+	cursor.get_mut()[20] = if flag_value
 	{
-		cursor.get_ref()[20] | 0x08
+		cursor.get_ref()[20] | flag
 	}
 	else
 	{
-		cursor.get_ref()[20] & 0b11110111
+		cursor.get_ref()[20] & !flag
 	};
 
 	Ok(())
@@ -510,6 +542,8 @@ clear_metadata
 				=> return Ok(()),
 			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8L!"
 				=> return Ok(()),
+			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8 !"
+				=> return Ok(()),
 			_
 				=> return Err(exif_check_result.err().unwrap())
 		}
@@ -565,6 +599,56 @@ clear_metadata
 
 
 
+/// Determines the EXIF chunks that `clear_metadata` would remove, without
+/// modifying the file. Note that `clear_metadata` additionally updates the
+/// file size information and the flags of the VP8X chunk.
+pub(crate) fn
+plan_clear_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<RemovalPlan>, std::io::Error>
+{
+	// Without a VP8X chunk stating that there is EXIF data, nothing gets
+	// removed, see `clear_metadata`
+	let parse_webp_result = match check_exif_in_file(file_buffer)
+	{
+		Ok((_, parse_webp_result)) => parse_webp_result,
+		Err(error) => match error.to_string().as_str()
+		{
+			"No EXIF chunk according to VP8X flags!"
+				=> return Ok(Vec::new()),
+			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8L!"
+				=> return Ok(Vec::new()),
+			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8 !"
+				=> return Ok(Vec::new()),
+			_
+				=> return Err(error),
+		}
+	};
+
+	let mut plans    = Vec::new();
+	let mut position = 12;
+
+	for parsed_chunk in parse_webp_result
+	{
+		// fourCC section, size information and the data, including the
+		// padding byte (already accounted for in the length of the chunk)
+		let chunk_byte_count = 4 + 4 + parsed_chunk.len();
+
+		if parsed_chunk.header().to_lowercase() == EXIF_CHUNK_HEADER.to_lowercase()
+		{
+			plans.push(RemovalPlan::new(&parsed_chunk.header(), position..position + chunk_byte_count));
+		}
+
+		position += chunk_byte_count;
+	}
+
+	return Ok(plans);
+}
+
+
+
 /// Writes the given generally encoded metadata to the WebP image file at 
 /// the specified path. 
 /// Note that *all* previously stored EXIF metadata gets removed first before
@@ -573,7 +657,8 @@ pub(crate) fn
 write_metadata
 (
 	file_buffer: &mut Vec<u8>,
-	metadata:    &Metadata
+	metadata:    &Metadata,
+	warnings:    &mut Vec<MetadataWarning>
 )
 -> Result<(), std::io::Error>
 {
@@ -581,7 +666,7 @@ write_metadata
 	clear_metadata(file_buffer)?;
 
 	// Encode the general metadata format to WebP specifications
-	let mut encoded_metadata = encode_metadata_webp(&metadata.encode()?);
+	let mut encoded_metadata = encode_metadata_webp(&metadata.encode_with_warnings(warnings)?);
 	let encoded_metadata_len = encoded_metadata.len() as i32;
 
 	// Find a location where to put the EXIF chunk
@@ -589,18 +674,23 @@ write_metadata
 	// that is both known and should be located *before* the EXIF chunk
 	let pre_exif_chunks = [
 		"VP8X",
-		"VP8",
+		"VP8 ",
 		"VP8L",
 		"ICCP",
-		"ANIM"
+		"ANIM",
+		"ANMF",
+		"ALPH"
 	];
 
+	// Skip the RIFF and WEBP signatures as well as the file size information
 	let mut read_cursor = Cursor::new(file_buffer.as_ref());
+	read_cursor.set_position(12);
 
 	loop
 	{
 		// Request a chunk descriptor. If this fails, check the error 
 		// Depending on its type, either continue normally or return it
+		let chunk_start_position    = read_cursor.position();
 		let chunk_descriptor_result = get_next_chunk_descriptor(&mut read_cursor);
 
 		if let Ok(chunk_descriptor) = chunk_descriptor_result
@@ -614,8 +704,11 @@ write_metadata
 				chunk_type_found_in_pre_exif_chunks |= pre_exif_chunk.to_lowercase() == chunk_descriptor.header().to_lowercase();
 			}
 
+			// Place the EXIF chunk *before* the first chunk that is not 
+			// supposed to come before it
 			if !chunk_type_found_in_pre_exif_chunks
 			{
+				read_cursor.set_position(chunk_start_position);
 				break;
 			}
 		}
@@ -647,4 +740,153 @@ write_metadata
 	set_exif_flag(&mut write_cursor, true)?;
 
 	return Ok(());
-}
\ No newline at end of file
+}
+
+
+
+/// Gets the chunk descriptors of the WebP file, together with the position
+/// of each chunk (i.e. its fourCC section) in the file
+fn
+get_chunks_with_positions
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<(RiffChunkDescriptor, usize)>, std::io::Error>
+{
+	let mut chunks   = Vec::new();
+	let mut position = 12;
+
+	for parsed_chunk in parse_webp(file_buffer)?
+	{
+		// fourCC section, size information and the data, including the
+		// padding byte (already accounted for in the length of the chunk)
+		let chunk_byte_count = 4 + 4 + parsed_chunk.len();
+		chunks.push((parsed_chunk, position));
+		position += chunk_byte_count;
+	}
+
+	return Ok(chunks);
+}
+
+
+
+/// Reads the XMP packet stored in the "XMP " chunk of the WebP file. If there
+/// are multiple such chunks, the first one is used.
+pub(crate) fn
+read_xmp
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<String, std::io::Error>
+{
+	for (chunk, position) in get_chunks_with_positions(file_buffer)?
+	{
+		if chunk.header() != XMP_CHUNK_HEADER
+		{
+			continue;
+		}
+
+		// The length of the descriptor includes the padding byte, so use the
+		// size information stored in the file instead
+		let size = from_u8_vec_macro!(u32, &file_buffer[position+4..position+8].to_vec(), &Endian::Little) as usize;
+		let data = file_buffer[position+8..position+8+size].to_vec();
+
+		return match String::from_utf8(data)
+		{
+			Ok(xmp) => Ok(xmp),
+			Err(_)  => io_error!(InvalidData, "WebP XMP data is not valid UTF-8!"),
+		};
+	}
+
+	return little_exif_error!(NoMetadata, "No XMP data found!");
+}
+
+
+
+/// Removes all "XMP " chunks from the WebP file and unsets the XMP flag of
+/// the VP8X chunk. The EXIF data is not affected by this.
+pub(crate) fn
+clear_xmp
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	let chunks = get_chunks_with_positions(file_buffer)?;
+
+	// Remove in reverse order so that the positions of the remaining chunks
+	// stay valid
+	let mut delta = 0i32;
+	for (chunk, position) in chunks.iter().rev()
+	{
+		if chunk.header() == XMP_CHUNK_HEADER
+		{
+			range_remove(file_buffer, *position, position + 8 + chunk.len());
+			delta -= 8 + chunk.len() as i32;
+		}
+	}
+
+	let mut cursor = Cursor::new(file_buffer);
+	update_file_size_information(&mut cursor, delta)?;
+
+	// Only files in the extended format have flags that need to be updated
+	if chunks.first().map(|(chunk, _)| chunk.header() == VP8X_HEADER).unwrap_or(false)
+	{
+		set_vp8x_flag(&mut cursor, VP8X_XMP_FLAG, false)?;
+	}
+
+	return Ok(());
+}
+
+
+
+/// Writes the XMP packet into an "XMP " chunk, replacing any existing XMP
+/// data. The chunk is placed after the image data and the EXIF chunk, as
+/// required by the specification. If the file is in the simple format, it
+/// gets converted to the extended format in order to set the XMP flag.
+/// The EXIF data is not affected by this.
+pub(crate) fn
+write_xmp
+(
+	file_buffer: &mut Vec<u8>,
+	xmp:         &str
+)
+-> Result<(), std::io::Error>
+{
+	clear_xmp(file_buffer)?;
+
+	// The chunks that are located before the XMP chunk
+	let pre_xmp_chunks = [
+		"VP8X",
+		"ICCP",
+		"ANIM",
+		"ANMF",
+		"ALPH",
+		"VP8 ",
+		"VP8L",
+		"EXIF"
+	];
+
+	let chunks = get_chunks_with_positions(file_buffer)?;
+	let insert_position = chunks.iter()
+		.find(|(chunk, _)| !pre_xmp_chunks.iter().any(|header| header.to_lowercase() == chunk.header().to_lowercase()))
+		.map(|(_, position)| *position)
+		.unwrap_or(file_buffer.len());
+
+	let mut xmp_chunk = XMP_CHUNK_HEADER.as_bytes().to_vec();
+	xmp_chunk.extend(to_u8_vec_macro!(u32, &(xmp.len() as u32), &Endian::Little));
+	xmp_chunk.extend(xmp.as_bytes());
+	if xmp.len() % 2 == 1
+	{
+		xmp_chunk.push(0x00);
+	}
+	let xmp_chunk_len = xmp_chunk.len() as i32;
+
+	insert_multiple_at(file_buffer, insert_position, &mut xmp_chunk);
+
+	let mut cursor = Cursor::new(file_buffer);
+	update_file_size_information(&mut cursor, xmp_chunk_len)?;
+	set_vp8x_flag(&mut cursor, VP8X_XMP_FLAG, true)?;
+
+	return Ok(());
+}
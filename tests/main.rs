@@ -73,6 +73,77 @@ new_from_vec()
 	let _ = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG).unwrap();
 }
 
+#[test]
+fn
+new_from_vec_autodetect()
+-> Result<(), std::io::Error>
+{
+	for (path, file_type) in [
+		("tests/read_sample.jpg",  little_exif::filetype::FileExtension::JPEG),
+		("tests/read_sample.tif",  little_exif::filetype::FileExtension::TIFF),
+		("tests/read_sample.webp", little_exif::filetype::FileExtension::WEBP),
+		("tests/with_exif.jxl",    little_exif::filetype::FileExtension::JXL),
+	]
+	{
+		let image_data = read(path).unwrap();
+		assert_eq!(
+			Metadata::new_from_vec_autodetect(&image_data)?.encode()?,
+			Metadata::new_from_vec(&image_data, file_type)?.encode()?
+		);
+	}
+
+	assert!(Metadata::new_from_vec_autodetect(&read("tests/sample1.txt").unwrap()).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+detect_file_type()
+{
+	use little_exif::filetype::detect_file_type;
+	use little_exif::filetype::FileExtension;
+
+	assert_eq!(detect_file_type(&read("tests/sample2.png").unwrap()),               Some(FileExtension::PNG { as_zTXt_chunk: true }));
+	assert_eq!(detect_file_type(&read("tests/sample2.jpg").unwrap()),               Some(FileExtension::JPEG));
+	assert_eq!(detect_file_type(&read("tests/read_sample.tif").unwrap()),           Some(FileExtension::TIFF));
+	assert_eq!(detect_file_type(&read("tests/sample2_simple_lossy.webp").unwrap()), Some(FileExtension::WEBP));
+	assert_eq!(detect_file_type(&read("tests/no_exif.jxl").unwrap()),               Some(FileExtension::JXL));
+	assert_eq!(detect_file_type(&read("tests/iso_no_exif.jxl").unwrap()),           Some(FileExtension::JXL));
+	assert_eq!(detect_file_type(&read("tests/sample2.psd").unwrap()),               Some(FileExtension::PSD));
+	assert_eq!(detect_file_type(b"GIF87a\x01\x00\x01\x00"),                     Some(FileExtension::GIF));
+
+	assert_eq!(detect_file_type(b"\x00\x00\x00\x18ftypheic"),                   Some(FileExtension::HEIF));
+	assert_eq!(detect_file_type(b"\x00\x00\x00\x18ftypavif"),                   Some(FileExtension::HEIF));
+
+	// Other RIFF and ISO BMFF based formats are not supported
+	assert_eq!(detect_file_type(b"RIFF\x24\x00\x00\x00AVI LIST"),               None);
+	assert_eq!(detect_file_type(b"RIFF\x24\x00\x00\x00WAVEfmt "),               None);
+	assert_eq!(detect_file_type(b"\x00\x00\x00\x18ftypisom"),                   None);
+	assert_eq!(detect_file_type(b"RIFF"),                                           None);
+	assert_eq!(detect_file_type(&[]),                                               None);
+}
+
+#[test]
+fn
+new_from_path_wrong_extension()
+-> Result<(), std::io::Error>
+{
+	// A JPEG with an extension that can't be used for determining the type
+	let path = Path::new("tests/read_sample_copy.dat");
+	copy("tests/read_sample.jpg", path)?;
+
+	let metadata = Metadata::new_from_path(path);
+	remove_file(path)?;
+
+	assert_eq!(
+		metadata?.encode()?,
+		Metadata::new_from_path(Path::new("tests/read_sample.jpg"))?.encode()?
+	);
+
+	Ok(())
+}
+
 
 
 fn
@@ -254,16 +325,16 @@ get_test_metadata()
 
 	metadata.set_tag(
 		ExifTag::ImageDescription("Hello World!".to_string())
-	);
+	)?;
 	metadata.set_tag(
 		ExifTag::ExposureProgram(vec![1])
-	);
+	)?;
 	metadata.set_tag(
 		ExifTag::ISO(vec![2706])
-	);
+	)?;
 	metadata.set_tag(
 		ExifTag::Model("Testcam(1)".to_string())
-	);
+	)?;
 	assert_eq!(metadata.into_iter().count(), 4);
 
 	return Ok(metadata);
@@ -272,18 +343,28 @@ get_test_metadata()
 #[test]
 fn 
 as_u8_vec_png()
+-> Result<(), std::io::Error>
 {
-	println!(
-		"as_u8_vec_png: {}", 
-		get_test_metadata()
-			.unwrap()
-			.as_u8_vec(little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })
-			.unwrap()
-			.iter()
-			.map(|char_value| *char_value as char)
-			.into_iter()
-			.collect::<String>()
-	);
+	let metadata = get_test_metadata()?;
+	let as_u8_vec = metadata
+		.as_u8_vec(little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })
+		.unwrap();
+
+	// Without the zTXt chunk, the result is the raw TIFF payload that also 
+	// ends up in the eXIf chunk when writing to a PNG
+	assert_eq!(as_u8_vec, metadata.encode()?);
+	assert!(as_u8_vec.starts_with(&[0x49, 0x49, 0x2a, 0x00]));
+
+	let mut image_data = read("tests/sample2.png")?;
+	metadata.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false }
+	)?;
+	assert_eq!(&image_data[37..41], "eXIf".as_bytes());
+	assert_eq!(&image_data[33..37], (as_u8_vec.len() as u32).to_be_bytes());
+	assert_eq!(&image_data[41..41+as_u8_vec.len()], &as_u8_vec[..]);
+
+	return Ok(());
 }
 
 #[test]
@@ -454,14 +535,11 @@ write_to_file_png()
 	Ok(())
 }
 
-#[ignore]
 #[test]
 fn 
 write_to_file_webp_simple_lossy() 
 -> Result<(), std::io::Error>
 {
-	// Currently not active as the "VP8 " converter does not exist yet!
-
 	// Remove file from previous run and replace it with fresh copy
 	if let Err(error) = remove_file("tests/sample2_simple_lossy_copy.webp")
 	{
@@ -628,8 +706,89 @@ compare_write_to_png()
 		"tests/sample2.png",
 		"tests/sample2_copy1.png",
 		"tests/sample2_copy2.png",
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+write_to_vec_png_eXIf()
+-> Result<(), std::io::Error>
+{
+	let mut image_data = read("tests/sample2.png").unwrap();
+
+	// Write metadata as eXIf chunk and read it back in
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false }
+	)?;
+	assert_eq!(&image_data[37..41], "eXIf".as_bytes());
+
+	let metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false }
+	)?;
+	assert_eq!(metadata.into_iter().count(), 4);
+
+	// Writing again must replace the eXIf chunk instead of adding a second one
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(&image_data[37..41], "zTXt".as_bytes());
+	assert!(!image_data.windows(4).any(|window| window == "eXIf".as_bytes()));
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+read_from_vec_png_prefers_eXIf()
+-> Result<(), std::io::Error>
+{
+	// Get an eXIf chunk with a single tag...
+	let mut eXIf_data = read("tests/sample2.png").unwrap();
+	let mut metadata  = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("eXIf".to_string()))?;
+	metadata.write_to_vec(
+		&mut eXIf_data, 
 		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false }
+	)?;
+	let eXIf_length = u32::from_be_bytes(eXIf_data[33..37].try_into().unwrap()) as usize;
+	let mut eXIf_chunk = eXIf_data[33..(33 + 12 + eXIf_length)].to_vec();
+
+	// ...and put it after the zTXt chunk of an image with different metadata
+	let mut image_data = read("tests/sample2.png").unwrap();
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	let zTXt_length = u32::from_be_bytes(image_data[33..37].try_into().unwrap()) as usize;
+	let insert_position = 33 + 12 + zTXt_length;
+	let mut tail = image_data.split_off(insert_position);
+	image_data.append(&mut eXIf_chunk);
+	image_data.append(&mut tail);
+
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.into_iter().count(), 1);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		Some(&ExifTag::ImageDescription("eXIf".to_string()))
 	);
+
+	// Clearing the metadata removes both chunks
+	Metadata::clear_metadata(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(image_data, read("tests/sample2.png").unwrap());
+
+	Ok(())
 }
 
 #[test]
@@ -656,4 +815,3471 @@ compare_write_to_webp_extended()
 		"tests/sample2_extended_copy2.webp",
 		little_exif::filetype::FileExtension::WEBP
 	);
-}
\ No newline at end of file
+}
+fn
+get_png_chunk
+(
+	chunk_type: &str,
+	data:       &[u8]
+)
+-> Vec<u8>
+{
+	let mut chunk_data = chunk_type.as_bytes().to_vec();
+	chunk_data.extend(data);
+
+	let crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&chunk_data);
+
+	let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+	chunk.extend(chunk_data);
+	chunk.extend(crc.to_be_bytes());
+	return chunk;
+}
+
+#[allow(non_snake_case)]
+fn
+get_XMP_iTXt_chunk
+(
+	xmp:        &str,
+	compressed: bool
+)
+-> Vec<u8>
+{
+	let mut chunk_data = "XML:com.adobe.xmp".as_bytes().to_vec();
+	chunk_data.extend([0x00, compressed as u8, 0x00]);
+	chunk_data.extend("en".as_bytes());
+	chunk_data.push(0x00);
+	chunk_data.push(0x00);
+	if compressed
+	{
+		chunk_data.extend(miniz_oxide::deflate::compress_to_vec_zlib(xmp.as_bytes(), 8));
+	}
+	else
+	{
+		chunk_data.extend(xmp.as_bytes());
+	}
+
+	return get_png_chunk("iTXt", &chunk_data);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+write_to_vec_png_preserves_chunk_order()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::png_chunk_iter::PngChunkIter;
+
+	// Add some ancillary chunks that have ordering constraints between the
+	// IHDR and the IDAT chunks
+	let mut image_data = read("tests/sample2.png").unwrap();
+	let mut tail       = image_data.split_off(33);
+	image_data.append(&mut get_png_chunk("iCCP", b"icc\x00\x00\x78\x9c\x03\x00\x00\x00\x00\x01"));
+	image_data.append(&mut get_png_chunk("gAMA", &45455u32.to_be_bytes()));
+	image_data.append(&mut get_png_chunk("cHRM", &[0u8; 32]));
+	image_data.append(&mut get_png_chunk("tRNS", &[0x00, 0x00]));
+	image_data.append(&mut get_png_chunk("pHYs", &[0, 0, 0x0b, 0x13, 0, 0, 0x0b, 0x13, 1]));
+	image_data.append(&mut get_png_chunk("tEXt", b"Software\x00little_exif"));
+	image_data.append(&mut tail);
+
+	let get_chunk_names = |image_data: &Vec<u8>| PngChunkIter::new(std::io::Cursor::new(image_data))
+		.unwrap()
+		.map(|chunk| chunk.unwrap().as_string())
+		.collect::<Vec<String>>();
+
+	let original_chunk_names = get_chunk_names(&image_data);
+
+	for as_zTXt_chunk in [true, false]
+	{
+		let mut image_data = image_data.clone();
+
+		// Write twice to also check that replacing the metadata works
+		for _ in 0..2
+		{
+			get_test_metadata()?.write_to_vec(
+				&mut image_data, 
+				little_exif::filetype::FileExtension::PNG { as_zTXt_chunk }
+			)?;
+		}
+
+		// Removing the new EXIF chunk gives the original order
+		// zTXt is inserted after IHDR, eXIf after iCCP
+		let new_chunk_index = if as_zTXt_chunk { 1 } else { 2 };
+		let mut chunk_names = get_chunk_names(&image_data);
+		assert_eq!(chunk_names[new_chunk_index], if as_zTXt_chunk { "zTXt" } else { "eXIf" });
+		chunk_names.remove(new_chunk_index);
+		assert_eq!(chunk_names, original_chunk_names);
+
+		let metadata = Metadata::new_from_vec(
+			&image_data, 
+			little_exif::filetype::FileExtension::PNG { as_zTXt_chunk }
+		)?;
+		assert_eq!(metadata.encode()?, get_test_metadata()?.encode()?);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+read_xmp_png()
+-> Result<(), std::io::Error>
+{
+	let xmp = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>";
+
+	for compressed in [false, true]
+	{
+		let mut image_data = read("tests/sample2.png").unwrap();
+		let mut tail       = image_data.split_off(33);
+		image_data.append(&mut get_XMP_iTXt_chunk(xmp, compressed));
+		image_data.append(&mut tail);
+
+		assert_eq!(
+			Metadata::read_xmp(
+				&image_data, 
+				little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+			)?, 
+			xmp
+		);
+	}
+
+	// No iTXt chunk with XMP data available
+	assert!(Metadata::read_xmp(
+		&read("tests/sample2.png").unwrap(), 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+png_chunk_iter()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::png_chunk_iter::PngChunkIter;
+
+	let image_data = read("tests/sample2.png").unwrap();
+
+	// Iterating over the whole file ends with the IEND chunk
+	let chunk_names = PngChunkIter::new(std::io::Cursor::new(&image_data))?
+		.map(|chunk| chunk.map(|chunk| chunk.as_string()))
+		.collect::<Result<Vec<String>, std::io::Error>>()?;
+	assert_eq!(chunk_names.first().unwrap(), "IHDR");
+	assert_eq!(chunk_names.last().unwrap(),  "IEND");
+
+	// Stopping early after the first chunk
+	let first_chunk = PngChunkIter::new(std::fs::File::open("tests/sample2.png")?)?
+		.next()
+		.unwrap()?;
+	assert_eq!(first_chunk.as_string(), "IHDR");
+	assert_eq!(first_chunk.length(),    13);
+
+	// A corrupted CRC is reported as an error, after which the iteration stops
+	let mut corrupted_data = image_data.clone();
+	corrupted_data[29] ^= 0xff;
+	let mut iterator = PngChunkIter::new(std::io::Cursor::new(&corrupted_data))?;
+	assert!(iterator.next().unwrap().is_err());
+	assert!(iterator.next().is_none());
+
+	Ok(())
+}
+
+#[test]
+fn
+get_tag_value_string()
+-> Result<(), std::io::Error>
+{
+	let mut image_data = read("tests/sample2.png").unwrap();
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+
+	let metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+
+	assert_eq!(
+		metadata.get_tag_value_string(&ExifTag::ImageDescription(String::new())),
+		Some("Hello World!".to_string())
+	);
+	assert_eq!(
+		metadata.get_tag_value_string(&ExifTag::Model(String::new())),
+		Some("Testcam(1)".to_string())
+	);
+
+	// Not a string tag or not present at all
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::ISO(Vec::new())),    None);
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::Make(String::new())), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+remove_tag()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+
+	// Removing a tag returns it, removing it again returns None
+	assert_eq!(
+		metadata.remove_tag(&ExifTag::ImageDescription(String::new()), false),
+		Some(ExifTag::ImageDescription("Hello World!".to_string()))
+	);
+	assert_eq!(metadata.remove_tag(&ExifTag::ImageDescription(String::new()), false), None);
+	assert_eq!(metadata.into_iter().count(), 3);
+
+	// The ExifIFD still contains the ISO tag after this, so it is kept
+	assert!(metadata.remove_tag_by_hex(0x8822, ExifTagGroup::EXIF, 0, false).is_some());
+	assert!(metadata.get_ifd(ExifTagGroup::EXIF, 0).is_some());
+
+	// Keep the now empty ExifIFD...
+	assert!(metadata.remove_tag_by_hex(0x8827, ExifTagGroup::EXIF, 0, true).is_some());
+	assert!(metadata.get_ifd(ExifTagGroup::EXIF, 0).is_some());
+
+	// ...which requires IFD0 to be kept as well
+	assert!(metadata.remove_tag(&ExifTag::Model(String::new()), false).is_some());
+	assert!(metadata.get_ifd(ExifTagGroup::GENERIC, 0).is_some());
+	assert_eq!(metadata.into_iter().count(), 0);
+
+	// Without any dependent IFDs, the empty IFD0 gets dropped
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Model("Testcam(1)".to_string()))?;
+	assert!(metadata.remove_tag(&ExifTag::Model(String::new()), false).is_some());
+	assert!(metadata.get_ifd(ExifTagGroup::GENERIC, 0).is_none());
+
+	Ok(())
+}
+
+#[test]
+fn
+get_all_tags()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let metadata = get_test_metadata()?;
+	assert_eq!(metadata.get_all_tags().count(), 4);
+
+	let exif_tags = metadata.get_all_tags()
+		.filter(|(ifd, _)| ifd.get_ifd_type() == ExifTagGroup::EXIF)
+		.map(|(_, tag)| tag.as_u16())
+		.collect::<Vec<u16>>();
+	assert_eq!(exif_tags, vec![0x8822, 0x8827]);
+
+	let (first_ifd, first_tag) = metadata.get_all_tags().next().unwrap();
+	assert_eq!(first_ifd.get_ifd_type(), first_tag.get_group());
+
+	Ok(())
+}
+
+#[test]
+fn
+ifd_summary()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	assert_eq!(metadata.ifd_summary(), vec![
+		(ExifTagGroup::GENERIC, 0, 2),
+		(ExifTagGroup::EXIF,    0, 2),
+	]);
+
+	metadata.set_gps_position(48.2082, 16.3738);
+	assert!(metadata.ifd_summary().contains(&(ExifTagGroup::GPS, 0, 5)));
+
+	assert!(Metadata::new().ifd_summary().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+gps_position()
+-> Result<(), std::io::Error>
+{
+	use little_exif::rational::uR64;
+
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_gps_position(), None);
+
+	// Write, encode and read back in
+	metadata.set_gps_position(-33.8568, 151.2153);
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	metadata.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	let metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+
+	let (latitude, longitude) = metadata.get_gps_position().unwrap();
+	assert!((latitude  - -33.8568).abs() < 1e-7);
+	assert!((longitude - 151.2153).abs() < 1e-7);
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::GPSLatitudeRef( String::new())), Some("S".to_string()));
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::GPSLongitudeRef(String::new())), Some("E".to_string()));
+
+	// Negative zero is written as North/East
+	let mut metadata = Metadata::new();
+	metadata.set_gps_position(-0.0, -0.0);
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::GPSLatitudeRef(String::new())), Some("N".to_string()));
+	assert_eq!(metadata.get_gps_position(), Some((0.0, 0.0)));
+
+	// Missing ref tags are assumed to be positive, degrees with decimal 
+	// minutes are supported, zero denominators are not
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::GPSLatitude(vec![
+		uR64 { nominator: 10,   denominator: 1  },
+		uR64 { nominator: 3045, denominator: 100},
+	]))?;
+	metadata.set_tag(ExifTag::GPSLongitude(vec![
+		uR64 { nominator: 20,   denominator: 1  },
+	]))?;
+	assert_eq!(metadata.get_gps_position(), Some((10.5075, 20.0)));
+
+	metadata.set_tag(ExifTag::GPSLongitude(vec![
+		uR64 { nominator: 20,   denominator: 0  },
+	]))?;
+	assert_eq!(metadata.get_gps_position(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+get_datetime_original()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_datetime_original(), None);
+
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024:02:29 13:37:05".to_string()))?;
+	let datetime = metadata.get_datetime_original().unwrap();
+	assert_eq!(
+		(datetime.year, datetime.month, datetime.day, datetime.hour, datetime.minute, datetime.second),
+		(2024, 2, 29, 13, 37, 5)
+	);
+	assert_eq!(datetime.subsec_nanos,   None);
+	assert_eq!(datetime.offset_minutes, None);
+
+	// Combine with sub seconds and offset
+	metadata.set_tag(ExifTag::SubSecTimeOriginal("042".to_string()))?;
+	metadata.set_tag(ExifTag::OffsetTimeOriginal("-05:30".to_string()))?;
+	let datetime = metadata.get_datetime_original().unwrap();
+	assert_eq!(datetime.subsec_nanos,   Some(42_000_000));
+	assert_eq!(datetime.offset_minutes, Some(-330));
+
+	// Malformed strings
+	for malformed in ["2024:02:29 13:37", "2024-02-29 13:37:05", "2024:13:29 13:37:05", "    :  :     :  :  "]
+	{
+		metadata.set_tag(ExifTag::DateTimeOriginal(malformed.to_string()))?;
+		assert_eq!(metadata.get_datetime_original(), None);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+get_datetime_original_utc()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024:02:29 13:37:05".to_string()))?;
+
+	// No offset tag - don't assume UTC
+	assert_eq!(metadata.get_datetime_original_utc(), None);
+
+	for (offset, expected_minutes) in [("+02:00", 120), ("-05:30", -330), ("Z", 0), ("+00:00", 0)]
+	{
+		metadata.set_tag(ExifTag::OffsetTimeOriginal(offset.to_string()))?;
+		let (datetime, offset_minutes) = metadata.get_datetime_original_utc().unwrap();
+		assert_eq!((datetime.hour, datetime.minute), (13, 37));
+		assert_eq!(offset_minutes, expected_minutes);
+	}
+
+	for malformed in ["02:00", "+2:00", "+24:00", "    "]
+	{
+		metadata.set_tag(ExifTag::OffsetTimeOriginal(malformed.to_string()))?;
+		assert_eq!(metadata.get_datetime_original_utc(), None);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_vec_webp_simple_lossy()
+-> Result<(), std::io::Error>
+{
+	let original_data  = read("tests/sample2_simple_lossy.webp").unwrap();
+	let mut image_data = original_data.clone();
+
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::WEBP
+	)?;
+
+	// The simple file format got converted into the extended one with the 
+	// EXIF flag set and the canvas size taken from the "VP8 " chunk
+	assert_eq!(&image_data[12..16], "VP8X".as_bytes());
+	assert_eq!(image_data[20] & 0x08, 0x08);
+	assert_eq!(&image_data[24..27], &[
+		(((original_data[26] as u16 | (original_data[27] as u16) << 8) & 0x3fff) - 1).to_le_bytes()[0],
+		(((original_data[26] as u16 | (original_data[27] as u16) << 8) & 0x3fff) - 1).to_le_bytes()[1],
+		0x00
+	]);
+	assert_eq!(&image_data[30..34], "VP8 ".as_bytes());
+
+	// RIFF file size is updated and still even
+	let file_size = u32::from_le_bytes(image_data[4..8].try_into().unwrap());
+	assert_eq!(file_size as usize + 8, image_data.len());
+	assert_eq!(file_size % 2, 0);
+
+	let metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::WEBP
+	)?;
+	assert_eq!(metadata.into_iter().count(), 4);
+
+	Ok(())
+}
+
+#[test]
+fn
+to_file_buffer()
+-> Result<(), std::io::Error>
+{
+	let mut expected_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(
+		&mut expected_data, 
+		little_exif::filetype::FileExtension::JPEG
+	)?;
+
+	let image_data = get_test_metadata()?.to_file_buffer(
+		read("tests/sample2.jpg").unwrap(), 
+		little_exif::filetype::FileExtension::JPEG
+	)?;
+	assert_eq!(image_data, expected_data);
+
+	// Errors of the format specific writers are passed on
+	assert!(get_test_metadata()?.to_file_buffer(
+		read("tests/sample2.jpg").unwrap(), 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_from_vec_tiff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut image_data = read("tests/read_sample.tif").unwrap();
+
+	let metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::TIFF
+	)?;
+	assert!(metadata.get_ifd(ExifTagGroup::GENERIC, 0).is_some());
+	assert!(metadata.get_ifd(ExifTagGroup::EXIF,    0).is_some());
+
+	// Break the magic number 42, resulting in an error
+	image_data[2] = 0x2b;
+	let result = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::TIFF
+	);
+	assert!(matches!(result, Err(little_exif::error::LittleExifError::InvalidSignature(_))));
+
+	Ok(())
+}
+
+#[test]
+fn
+set_endian()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::rational::uR64;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 300, denominator: 1 }]))?;
+	assert_eq!(&metadata.encode()?[0..2], "II".as_bytes());
+
+	metadata.set_endian(Endian::Big);
+	let encoded = metadata.encode()?;
+	assert_eq!(&encoded[0..4], &[0x4d, 0x4d, 0x00, 0x2a]);
+
+	// Decoding the big endian data results in the same tags
+	let mut image_data = read("tests/sample2.png").unwrap();
+	metadata.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.get_endian(), Endian::Big);
+	assert_eq!(
+		read_metadata.into_iter().collect::<Vec<&ExifTag>>(),
+		metadata.into_iter().collect::<Vec<&ExifTag>>()
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+thumbnail()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	assert_eq!(metadata.get_thumbnail(), None);
+
+	// Use a real JPEG as thumbnail and write everything to a PNG
+	let thumbnail_data = read("tests/no_exif.jpeg").unwrap();
+	metadata.set_thumbnail(&thumbnail_data);
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	metadata.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.get_thumbnail(), Some(thumbnail_data));
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::Compression(Vec::new())).next(),
+		Some(&ExifTag::Compression(vec![6]))
+	);
+	assert_eq!(read_metadata.get_tag_value_string(&ExifTag::Model(String::new())), Some("Testcam(1)".to_string()));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_read_xmp_gif()
+-> Result<(), std::io::Error>
+{
+	// Minimal 1x1 GIF87a without global color table
+	let mut image_data = b"GIF87a".to_vec();
+	image_data.extend([0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+	image_data.extend([0x2c, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00]);
+	image_data.extend([0x02, 0x02, 0x44, 0x01, 0x00, 0x3b]);
+
+	let xmp = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>";
+	Metadata::write_xmp(&mut image_data, little_exif::filetype::FileExtension::GIF, xmp)?;
+
+	assert_eq!(&image_data[0..6], b"GIF89a");
+	assert_eq!(Metadata::read_xmp(&image_data, little_exif::filetype::FileExtension::GIF)?, xmp);
+
+	Metadata::clear_metadata(&mut image_data, little_exif::filetype::FileExtension::GIF)?;
+	assert!(Metadata::read_xmp(&image_data, little_exif::filetype::FileExtension::GIF).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+makernote_relocation()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::makernote::MakerNoteHandling;
+	use little_exif::metadata::warning::MetadataWarning;
+
+	fn
+	find(haystack: &[u8], needle: &[u8]) -> usize
+	{
+		haystack.windows(needle.len()).position(|window| window == needle).unwrap()
+	}
+
+	// Canon style MakerNote: An IFD with a single entry that points to an
+	// ASCII string located directly after the IFD, relative to the start of
+	// the TIFF data
+	fn
+	get_makernote(offset: u32) -> Vec<u8>
+	{
+		let mut makernote = vec![0x01, 0x00, 0x06, 0x00, 0x02, 0x00, 0x08, 0x00, 0x00, 0x00];
+		makernote.extend((offset + 18).to_le_bytes());
+		makernote.extend([0x00; 4]);
+		makernote.extend(b"CanonXX\0");
+		return makernote;
+	}
+
+	fn
+	get_string_via_makernote(encoded: &[u8]) -> Vec<u8>
+	{
+		let makernote_position = find(encoded, &[0x01, 0x00, 0x06, 0x00, 0x02, 0x00, 0x08, 0x00]);
+		let string_offset      = u32::from_le_bytes(encoded[makernote_position+10..makernote_position+14].try_into().unwrap()) as usize;
+		return encoded[string_offset..string_offset+8].to_vec();
+	}
+
+	// Determine where the MakerNote gets written and create a file with a 
+	// valid MakerNote at that position
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Make("Canon".to_string()))?;
+	metadata.set_tag(ExifTag::MakerNote(get_makernote(0)))?;
+	let offset = find(&metadata.encode()?, &get_makernote(0)) as u32;
+
+	metadata.set_tag(ExifTag::MakerNote(get_makernote(offset)))?;
+	let encoded = metadata.encode()?;
+	assert_eq!(get_string_via_makernote(&encoded), b"CanonXX\0");
+
+	// Adding a tag moves the MakerNote to a different position
+	for handling in [MakerNoteHandling::FixOffsets, MakerNoteHandling::Opaque]
+	{
+		let mut metadata = Metadata::new_from_vec(&encoded, little_exif::filetype::FileExtension::TIFF)?;
+		metadata.set_makernote_handling(handling);
+		metadata.set_tag(ExifTag::ImageDescription("Moving the MakerNote".to_string()))?;
+
+		let reencoded = metadata.encode()?;
+		assert_ne!(find(&reencoded, &[0x01, 0x00, 0x06, 0x00, 0x02, 0x00, 0x08, 0x00]) as u32, offset);
+		assert_eq!(
+			get_string_via_makernote(&reencoded) == b"CanonXX\0",
+			handling == MakerNoteHandling::FixOffsets
+		);
+
+		// Only the opaque MakerNote is reported as having stale offsets
+		let mut image_data = read("tests/sample2.jpg").unwrap();
+		let warnings = metadata.write_to_vec_with_warnings(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+		assert_eq!(
+			warnings.iter().any(|warning| matches!(warning, MetadataWarning::StaleMakerNoteOffsets { .. })),
+			handling == MakerNoteHandling::Opaque
+		);
+	}
+
+	// The offsets are adjusted using the byte order the MakerNote has been
+	// decoded with, even if the metadata gets encoded using a different one
+	let mut metadata = Metadata::new_from_vec(&encoded, little_exif::filetype::FileExtension::TIFF)?;
+	metadata.set_makernote_handling(MakerNoteHandling::FixOffsets);
+	metadata.set_endian(little_exif::endian::Endian::Big);
+	metadata.set_tag(ExifTag::ImageDescription("Moving the MakerNote".to_string()))?;
+
+	let reencoded = metadata.encode()?;
+	assert_eq!(&reencoded[0..2], b"MM");
+	assert_eq!(get_string_via_makernote(&reencoded), b"CanonXX\0");
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_xmp_app1_before_exif_app1()
+-> Result<(), std::io::Error>
+{
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	// Put an APP1 segment with XMP data in front of the EXIF APP1 segment
+	let mut xmp_segment = b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>".to_vec();
+	let mut xmp_app1    = vec![0xff, 0xe1];
+	xmp_app1.extend(((xmp_segment.len() + 2) as u16).to_be_bytes());
+	xmp_app1.append(&mut xmp_segment);
+	let mut tail = image_data.split_off(2);
+	image_data.extend(xmp_app1.iter());
+	image_data.append(&mut tail);
+
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(metadata.encode()?, get_test_metadata()?.encode()?);
+
+	// Clearing the EXIF data keeps the XMP segment
+	Metadata::clear_metadata(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(image_data[2..2+xmp_app1.len()], xmp_app1[..]);
+	assert_eq!(
+		Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?.get_ifds().len(),
+		0
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_non_standard_app_segment_order()
+-> Result<(), std::io::Error>
+{
+	let segment = |marker: u8, payload: &[u8]| -> Vec<u8>
+	{
+		let mut segment = vec![0xff, marker];
+		segment.extend(((payload.len() + 2) as u16).to_be_bytes());
+		segment.extend(payload.iter());
+		return segment;
+	};
+
+	let mut exif_payload = b"Exif\0\0".to_vec();
+	exif_payload.extend(get_test_metadata()?.encode()?.iter());
+
+	let app0     = segment(0xe0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+	let app1_xmp = segment(0xe1, b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>");
+	let app1     = segment(0xe1, &exif_payload);
+	let app2     = segment(0xe2, b"ICC_PROFILE\0\x01\x01dummy profile");
+
+	let orders = [
+		vec![&app0,     &app1                   ],
+		vec![&app1,     &app0                   ],
+		vec![&app0,     &app2,     &app1        ],
+		vec![&app1_xmp, &app2,     &app0, &app1 ],
+		vec![&app0,     &app1_xmp, &app1, &app2 ],
+	];
+
+	for order in orders
+	{
+		let mut image_data = vec![0xff, 0xd8];
+		for (index, segment) in order.iter().enumerate()
+		{
+			// Some encoders pad markers with additional 0xFF fill bytes
+			if index == 1
+			{
+				image_data.extend([0xff, 0xff]);
+			}
+			image_data.extend(segment.iter());
+		}
+		image_data.extend([0xff, 0xd9]);
+
+		let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+		assert_eq!(metadata.encode()?, get_test_metadata()?.encode()?);
+	}
+
+	// A segment length below 2 is invalid and must not cause a panic
+	let mut image_data = vec![0xff, 0xd8];
+	image_data.extend(app0.iter());
+	image_data.extend([0xff, 0xe2, 0x00, 0x01]);
+	image_data.extend(app1.iter());
+	assert!(Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_fill_bytes_when_writing_and_clearing()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Insert a single 0xFF fill byte before the APP1 marker
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	assert_eq!(image_data[0..4], [0xff, 0xd8, 0xff, 0xe1]);
+	image_data.insert(2, 0xff);
+
+	let mut cleared_data = image_data.clone();
+	Metadata::clear_metadata(&mut cleared_data, FileExtension::JPEG)?;
+	assert_eq!(cleared_data.windows(6).filter(|window| window == b"Exif\0\0").count(), 0);
+	assert_eq!(Metadata::new_from_vec(&cleared_data, FileExtension::JPEG)?.get_ifds().len(), 0);
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::ImageDescription("Written despite fill bytes".to_string()))?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	assert_eq!(image_data.windows(6).filter(|window| window == b"Exif\0\0").count(), 1);
+	assert_eq!(Metadata::new_from_vec(&image_data, FileExtension::JPEG)?.encode()?, metadata.encode()?);
+
+	// Same for the segments read and written besides the EXIF data
+	Metadata::set_jpeg_comment(&mut image_data, b"First comment")?;
+	let comment_position = image_data.windows(2).position(|window| window == [0xff, 0xfe]).unwrap();
+	image_data.insert(comment_position, 0xff);
+	Metadata::set_jpeg_comment(&mut image_data, b"Second comment")?;
+	assert_eq!(Metadata::get_jpeg_comment(&image_data)?, Some(b"Second comment".to_vec()));
+	assert_eq!(image_data.windows(13).filter(|window| window == b"First comment").count(), 0);
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_exif_too_large_for_app1()
+{
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("X".repeat(70000))).unwrap();
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	let original_data  = image_data.clone();
+
+	let error = metadata.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::JPEG
+	).unwrap_err();
+	assert!(error.to_string().contains("65533"));
+	assert!(metadata.as_u8_vec(little_exif::filetype::FileExtension::JPEG).is_err());
+
+	// The image itself is not modified
+	assert_eq!(image_data, original_data);
+}
+
+#[test]
+fn
+new_from_path_with_warnings()
+-> Result<(), std::io::Error>
+{
+	use std::fs::write;
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+	use little_exif::metadata::warning::MetadataWarning;
+
+	// Unknown tag & tag with an unexpected format (ISO as INT32U)
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![42], 0xbeef, ExifTagGroup::GENERIC))?;
+	let mut encoded = metadata.encode()?;
+	let iso_entry   = encoded.windows(4).position(|window| window == [0x27, 0x88, 0x03, 0x00]).unwrap();
+	encoded[iso_entry+2] = 0x04;
+	encoded[iso_entry+8..iso_entry+12].copy_from_slice(&2706u32.to_le_bytes());
+
+	let path = Path::new("tests/warnings_copy.tif");
+	write(path, &encoded)?;
+	let (metadata, warnings) = Metadata::new_from_path_with_warnings(path)?;
+	remove_file(path)?;
+
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+	// The ExifOffset tag (0x8769) precedes the unknown tag in IFD0, so the
+	// ExifIFD gets decoded first
+	assert_eq!(warnings, vec![
+		MetadataWarning::UnexpectedFormat { 
+			hex:      0x8827, 
+			group:    ExifTagGroup::EXIF, 
+			expected: ExifTagFormat::INT16U, 
+			found:    ExifTagFormat::INT32U 
+		},
+		MetadataWarning::UnknownTag { hex: 0xbeef, group: ExifTagGroup::GENERIC },
+	]);
+
+	// ExifIFD located past the end of the data: IFD0 is still readable
+	let mut encoded = get_test_metadata()?.encode()?;
+	let exif_offset_entry = encoded.windows(2).position(|window| window == [0x69, 0x87]).unwrap();
+	encoded[exif_offset_entry+8..exif_offset_entry+12].copy_from_slice(&0x1000u32.to_le_bytes());
+
+	let path = Path::new("tests/warnings_skipped_ifd_copy.tif");
+	write(path, &encoded)?;
+	let (metadata, warnings) = Metadata::new_from_path_with_warnings(path)?;
+	remove_file(path)?;
+
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::Model(String::new())), Some("Testcam(1)".to_string()));
+	assert_eq!(warnings, vec![MetadataWarning::SkippedIfd { group: ExifTagGroup::EXIF, offset: 0x1000 }]);
+
+	// PNG with a broken CRC of an IDAT chunk can still be read
+	let mut image_data = read("tests/sample2.png").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })?;
+	let last_idat_crc_position = image_data.len() - 12 - 4;
+	image_data[last_idat_crc_position] ^= 0xff;
+
+	let path = Path::new("tests/warnings_crc_copy.png");
+	write(path, &image_data)?;
+	let (metadata, warnings) = Metadata::new_from_path_with_warnings(path)?;
+	let strict_result        = Metadata::new_from_path(path);
+	remove_file(path)?;
+
+	assert_eq!(metadata.encode()?, get_test_metadata()?.encode()?);
+	assert_eq!(warnings, vec![MetadataWarning::CrcMismatch("Checksum check failed for IDAT chunk".to_string())]);
+	assert!(matches!(strict_result, Err(little_exif::error::LittleExifError::ChecksumMismatch(_))));
+
+	Ok(())
+}
+
+#[test]
+fn
+get_tag_by_hex_with_group()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	// 0x0001 is used in both the GPS and the Interoperability IFD
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::GPSLatitudeRef("N".to_string()))?;
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()))?;
+
+	assert_eq!(metadata.get_tag_by_hex(0x0001, None).count(), 2);
+	assert_eq!(
+		metadata.get_tag_by_hex(0x0001, Some(ExifTagGroup::GPS)).collect::<Vec<&ExifTag>>(),
+		vec![&ExifTag::GPSLatitudeRef("N".to_string())]
+	);
+	assert_eq!(
+		metadata.get_tag_by_hex(0x0001, Some(ExifTagGroup::INTEROP)).collect::<Vec<&ExifTag>>(),
+		vec![&ExifTag::InteroperabilityIndex("R98".to_string())]
+	);
+	assert_eq!(metadata.get_tag_by_hex(0x0001, Some(ExifTagGroup::GENERIC)).count(), 0);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_png_vec_zlib_level()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::PngWriteOptions;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::UserComment("Compress me! ".repeat(100).into_bytes()))?;
+
+	let mut sizes = Vec::new();
+	for zlib_level in [1, 9]
+	{
+		let mut image_data = read("tests/sample2.png").unwrap();
+		metadata.write_to_png_vec(
+			&mut image_data, 
+			&PngWriteOptions { zlib_level, ..Default::default() }
+		)?;
+		sizes.push(image_data.len());
+
+		let read_metadata = Metadata::new_from_vec(
+			&image_data, 
+			little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+		)?;
+		assert_eq!(read_metadata.encode()?, metadata.encode()?);
+	}
+
+	assert!(sizes[0] >= sizes[1]);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_png_vec_text_mode()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::PngTextMode;
+	use little_exif::png::PngWriteOptions;
+
+	let metadata = get_test_metadata()?;
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	metadata.write_to_png_vec(
+		&mut image_data, 
+		&PngWriteOptions { text_mode: PngTextMode::TextUncompressed, ..Default::default() }
+	)?;
+
+	// The new chunk is placed directly after the IHDR chunk
+	let ihdr_length = u32::from_be_bytes(image_data[8..12].try_into().unwrap()) as usize;
+	let chunk_start = 8 + 12 + ihdr_length;
+	assert_eq!(&image_data[chunk_start+4..chunk_start+8], b"tEXt");
+	assert!(image_data[chunk_start+8..].starts_with(b"Raw profile type exif\0\n"));
+
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.encode()?, metadata.encode()?);
+
+	// Writing again using the default mode replaces the tEXt chunk
+	metadata.write_to_png_vec(&mut image_data, &PngWriteOptions::default())?;
+	assert_eq!(&image_data[chunk_start+4..chunk_start+8], b"zTXt");
+	assert!(!image_data.windows(4).any(|window| window == b"tEXt"));
+
+	Ok(())
+}
+
+#[test]
+fn
+set_tag_validates_format()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = Metadata::new();
+
+	// ExposureTime must be stored as RATIONAL64U, not as INT16U
+	let error = metadata.set_tag(ExifTag::UnknownINT16U(vec![1], 0x829a, ExifTagGroup::EXIF)).unwrap_err();
+	assert!(error.to_string().contains("ExposureTime"));
+	assert!(error.to_string().contains("RATIONAL64U"));
+	assert!(error.to_string().contains("INT16U"));
+	assert!(metadata.get_tag_by_hex(0x829a, Some(ExifTagGroup::EXIF)).next().is_none());
+
+	// Unknown tags with a matching format or an unknown hex value are fine
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![1], 0xbeef, ExifTagGroup::EXIF))?;
+	metadata.set_tag(ExifTag::UnknownSTRING("Testcam".to_string(), 0x0110, ExifTagGroup::GENERIC))?;
+
+	// The unchecked variant allows non-standard formats
+	metadata.set_tag_unchecked(ExifTag::UnknownINT16U(vec![1], 0x829a, ExifTagGroup::EXIF));
+	assert!(metadata.get_tag_by_hex(0x829a, Some(ExifTagGroup::EXIF)).next().is_some());
+
+	Ok(())
+}
+
+#[test]
+fn
+clear_all_and_clear_group()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::GPSLatitudeRef("N".to_string()))?;
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()))?;
+	metadata.set_tag(ExifTag::GPSInfo(vec![0]))?;
+
+	// Removes the GPS IFD's tag as well as the manually set GPSInfo tag
+	assert_eq!(metadata.clear_group(ExifTagGroup::GPS), 2);
+	assert!(metadata.get_ifd(ExifTagGroup::GPS, 0).is_none());
+	assert!(metadata.get_tag(&ExifTag::GPSInfo(Vec::new())).next().is_none());
+
+	// Removing the ExifIFD also removes the dependent Interop IFD
+	assert_eq!(metadata.clear_group(ExifTagGroup::EXIF), 3);
+	assert!(metadata.get_ifd(ExifTagGroup::INTEROP, 0).is_none());
+	assert!(metadata.encode().is_ok());
+
+	// Only ImageDescription and Model are left
+	assert_eq!(metadata.clear_all(), 2);
+	assert!(metadata.get_ifds().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+clear_metadata_strips_exif()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	for (path, file_type) in [
+		("tests/sample2.jpg",                  FileExtension::JPEG),
+		("tests/sample2.png",                  FileExtension::PNG { as_zTXt_chunk: true }),
+		("tests/sample2_simple_lossless.webp", FileExtension::WEBP),
+	]
+	{
+		let mut image_data = read(path).unwrap();
+		get_test_metadata()?.write_to_vec(&mut image_data, file_type)?;
+		assert!(!Metadata::new_from_vec(&image_data, file_type)?.get_ifds().is_empty());
+
+		Metadata::clear_metadata(&mut image_data, file_type)?;
+		assert!(Metadata::new_from_vec(&image_data, file_type)?.get_ifds().is_empty());
+	}
+
+	// For JPEGs, the JFIF segment and an ICC profile are kept
+	let mut icc_segment = vec![0xff, 0xe2, 0x00, 0x10];
+	icc_segment.extend(b"ICC_PROFILE\0\x01\x01");
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	image_data.splice(2..2, icc_segment.clone());
+
+	Metadata::clear_metadata(&mut image_data, FileExtension::JPEG)?;
+	assert!(Metadata::new_from_vec(&image_data, FileExtension::JPEG)?.get_ifds().is_empty());
+	assert!(image_data.windows(icc_segment.len()).any(|window| window == icc_segment));
+	assert!(image_data.windows(5).any(|window| window == b"JFIF\0"));
+
+	Ok(())
+}
+
+#[test]
+fn
+rational_value_helpers()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::rational::iR64;
+	use little_exif::rational::uR64;
+
+	let latitude = ExifTag::GPSLatitude(vec![
+		uR64 { nominator: 48, denominator: 1 },
+		uR64 { nominator: 30, denominator: 1 },
+		uR64 { nominator: 15, denominator: 2 },
+	]);
+	assert_eq!(latitude.as_rational_vec(), Some(vec![(48, 1), (30, 1), (15, 2)]));
+	assert_eq!(latitude.as_f64_vec(false), Some(vec![48.0, 30.0, 7.5]));
+
+	// Signed rationals are not returned as unsigned ones
+	let bias = ExifTag::UnknownRATIONAL64S(
+		vec![iR64 { nominator: -1, denominator: 3 }, iR64 { nominator: -1, denominator: 0 }], 
+		0xbeef, 
+		ExifTagGroup::EXIF
+	);
+	assert_eq!(bias.as_rational_vec(), None);
+	assert_eq!(bias.as_srational_vec(), Some(vec![(-1, 3), (-1, 0)]));
+	assert_eq!(bias.as_f64_vec(false), None);
+	assert_eq!(bias.as_f64_vec(true), Some(vec![-1.0 / 3.0, f64::NEG_INFINITY]));
+
+	// Not a rational at all
+	assert_eq!(ExifTag::Model("Testcam".to_string()).as_f64_vec(true), None);
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_tIME_chunk()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_datetime::ExifDateTime;
+	use little_exif::png::PngWriteOptions;
+	use little_exif::png::vec::read_png_time;
+	use little_exif::png::vec::write_png_time;
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	assert!(read_png_time(&image_data).is_none());
+
+	let datetime = ExifDateTime::from_exif_string("2024:02:29 13:37:05").unwrap();
+	write_png_time(&mut image_data, &datetime)?;
+	assert_eq!(read_png_time(&image_data), Some(ExifDateTime { offset_minutes: Some(0), ..datetime }));
+
+	// Without an offset to UTC the DateTime tag does not get mirrored
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::ModifyDate("2025:12:31 23:59:59".to_string()))?;
+	metadata.write_to_png_vec(
+		&mut image_data, 
+		&PngWriteOptions { mirror_datetime_to_tIME: true, ..Default::default() }
+	)?;
+	assert_eq!(read_png_time(&image_data), Some(ExifDateTime { offset_minutes: Some(0), ..datetime }));
+
+	// Mirror the DateTime tag converted to UTC, replacing the existing tIME
+	// chunk
+	metadata.set_tag(ExifTag::OffsetTime("-01:00".to_string()))?;
+	metadata.write_to_png_vec(
+		&mut image_data, 
+		&PngWriteOptions { mirror_datetime_to_tIME: true, ..Default::default() }
+	)?;
+
+	let mirrored = read_png_time(&image_data).unwrap();
+	assert_eq!((mirrored.year, mirrored.month, mirrored.day), (2026, 1, 1));
+	assert_eq!((mirrored.hour, mirrored.minute, mirrored.second), (0, 59, 59));
+	assert_eq!(image_data.windows(4).filter(|window| window == b"tIME").count(), 1);
+
+	// The PNG is still valid and the metadata can be read
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.encode()?, metadata.encode()?);
+
+	// A PNG without IHDR chunk is rejected instead of causing a panic
+	let mut no_IHDR = image_data[..8].to_vec();
+	no_IHDR.extend(get_png_chunk("IEND", &[]));
+	let error = write_png_time(&mut no_IHDR, &datetime).unwrap_err();
+	assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+	Ok(())
+}
+
+#[test]
+fn
+metadata_builder()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::builder::MetadataBuilder;
+
+	let metadata = MetadataBuilder::new()
+		.endian(Endian::Big)
+		.tag(ExifTag::ImageDescription("Hello World!".to_string()))
+		.tag(ExifTag::ExposureProgram(vec![1]))
+		.tag(ExifTag::ISO(vec![2706]))
+		.tag(ExifTag::Model("Testcam(1)".to_string()))
+		.build()?;
+
+	// Tags are routed to the IFD of their group
+	assert!(metadata.get_ifd(ExifTagGroup::EXIF, 0).unwrap().get_tags().len() == 2);
+
+	let mut expected = get_test_metadata()?;
+	expected.set_endian(Endian::Big);
+	assert_eq!(metadata.encode()?, expected.encode()?);
+
+	// Duplicates are rejected unless they should replace the previous tag
+	let duplicate_builder = MetadataBuilder::new()
+		.tag(ExifTag::Model("First".to_string()))
+		.tag(ExifTag::Model("Second".to_string()));
+
+	assert!(duplicate_builder.clone().build().is_err());
+
+	let metadata = duplicate_builder.replace_duplicates(true).build()?;
+	assert_eq!(metadata.get_tag_value_string(&ExifTag::Model(String::new())), Some("Second".to_string()));
+
+	Ok(())
+}
+
+#[test]
+fn
+orientation()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::orientation::Orientation;
+
+	let mut metadata = get_test_metadata()?;
+	assert_eq!(metadata.get_orientation(), None);
+
+	metadata.set_orientation(Orientation::Rotate270CW);
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(Vec::new())).next(), Some(&ExifTag::Orientation(vec![8])));
+	assert_eq!(metadata.get_orientation(), Some(Orientation::Rotate270CW));
+
+	for value in 1..=8
+	{
+		assert_eq!(Orientation::from_u16(value).unwrap().as_u16(), value);
+	}
+
+	// Out of range values are ignored
+	metadata.set_tag(ExifTag::Orientation(vec![9]))?;
+	assert_eq!(metadata.get_orientation(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+merge_metadata()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::metadata::merge::MergeStrategy;
+
+	let template = get_test_metadata()?;
+
+	let mut other = Metadata::new();
+	other.set_endian(Endian::Big);
+	other.set_tag(ExifTag::ISO(vec![100]))?;
+	other.set_tag(ExifTag::Model("Testcam(1)".to_string()))?;
+	other.set_tag(ExifTag::GPSLatitudeRef("N".to_string()))?;
+
+	// ISO is in conflict, Model is identical and thus no conflict
+	let mut merged = template.clone();
+	assert!(merged.merge(&other, MergeStrategy::Error).is_err());
+	assert_eq!(merged.encode()?, template.encode()?);
+
+	let mut merged = template.clone();
+	merged.merge(&other, MergeStrategy::PreferSelf)?;
+	assert_eq!(merged.get_endian(), Endian::Little);
+	assert_eq!(merged.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(merged.get_tag_value_string(&ExifTag::GPSLatitudeRef(String::new())), Some("N".to_string()));
+
+	let mut merged = template.clone();
+	merged.merge(&other, MergeStrategy::PreferOther)?;
+	assert_eq!(merged.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![100])));
+	assert_eq!(merged.get_tag_value_string(&ExifTag::ImageDescription(String::new())), Some("Hello World!".to_string()));
+
+	// The merged struct can be encoded and decoded again
+	let mut image_data = read("tests/sample2.png").unwrap();
+	merged.write_to_png_vec(&mut image_data, &Default::default())?;
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.encode()?, merged.encode()?);
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_from_raw_bytes()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::exif_tag_format::ExifTagFormat;
+	use little_exif::ifd::ExifTagGroup;
+
+	let metadata = get_test_metadata()?;
+	for endian in [Endian::Little, Endian::Big]
+	{
+		for (ifd, tag) in metadata.get_all_tags()
+		{
+			let raw_data = tag.value_as_u8_vec(&endian);
+			let count    = raw_data.len() as u32 / tag.format().bytes_per_component();
+
+			let reconstructed = ExifTag::from_raw(tag.as_u16(), ifd.get_ifd_type(), tag.format(), count, &raw_data, &endian)?;
+			assert_eq!(&reconstructed, tag);
+		}
+	}
+
+	// Values get converted to the format of the known tag
+	let width = ExifTag::from_raw(0x0100, ExifTagGroup::GENERIC, ExifTagFormat::INT16U, 1, &[0x01, 0x02, 0x00, 0x00], &Endian::Big)?;
+	assert_eq!(width, ExifTag::ImageWidth(vec![0x0102]));
+
+	// Unknown tags and too little data
+	let unknown = ExifTag::from_raw(0xbeef, ExifTagGroup::GENERIC, ExifTagFormat::INT8U, 2, &[1, 2, 3, 4], &Endian::Big)?;
+	assert_eq!(unknown, ExifTag::UnknownINT8U(vec![1, 2], 0xbeef, ExifTagGroup::GENERIC));
+	assert!(ExifTag::from_raw(0x8827, ExifTagGroup::EXIF, ExifTagFormat::INT16U, 3, &[0, 1, 0, 2], &Endian::Big).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+sub_ifds_round_trip()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Little endian TIFF data with IFD0 referencing two SubIFDs that contain
+	// a single ImageWidth tag each
+	let entry = |hex: u16, format: u16, count: u32, value: u32| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let tiff_data = [
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],
+		vec![0x02, 0x00], entry(0x0100, 4, 1, 1), entry(0x014a, 4, 2, 38), vec![0; 4],
+		46u32.to_le_bytes().to_vec(), 64u32.to_le_bytes().to_vec(),
+		vec![0x01, 0x00], entry(0x0100, 4, 1, 100), vec![0; 4],
+		vec![0x01, 0x00], entry(0x0100, 4, 1, 200), vec![0; 4],
+	].concat();
+
+	let check = |metadata: &Metadata| {
+		let ifd0     = metadata.get_ifd(little_exif::ifd::ExifTagGroup::GENERIC, 0).unwrap();
+		let sub_ifds = ifd0.get_sub_ifds();
+		assert_eq!(ifd0.get_tags(), &vec![ExifTag::ImageWidth(vec![1])]);
+		assert_eq!(sub_ifds.len(), 2);
+		assert_eq!(sub_ifds[0].get_tags(), &vec![ExifTag::ImageWidth(vec![100])]);
+		assert_eq!(sub_ifds[1].get_tags(), &vec![ExifTag::ImageWidth(vec![200])]);
+	};
+
+	let metadata = Metadata::new_from_vec(&tiff_data, FileExtension::TIFF)?;
+	check(&metadata);
+
+	let encoded = metadata.encode()?;
+	check(&Metadata::new_from_vec(&encoded, FileExtension::TIFF)?);
+
+	Ok(())
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn
+async_read_and_write()
+-> Result<(), std::io::Error>
+{
+	let path = Path::new("tests/read_sample_async_copy.jpg");
+	copy("tests/read_sample.jpg", path)?;
+
+	let metadata = get_test_metadata()?;
+	metadata.write_to_file_async(path).await?;
+	let read_metadata = Metadata::new_from_path_async(path).await;
+	remove_file(path)?;
+
+	assert_eq!(read_metadata?.encode()?, metadata.encode()?);
+
+	Ok(())
+}
+
+#[test]
+fn
+png_chunk_properties()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::png_chunk_iter::PngChunkIter;
+
+	// Add a private chunk that is unknown to little_exif
+	let mut image_data = read("tests/sample2.png").unwrap();
+	let mut tail       = image_data.split_off(33);
+	image_data.append(&mut get_png_chunk("prVt", b"private data"));
+	image_data.append(&mut tail);
+
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+
+	let chunks = PngChunkIter::new(std::io::Cursor::new(&image_data))?
+		.collect::<Result<Vec<_>, std::io::Error>>()?;
+
+	let private_chunk = chunks.iter().find(|chunk| chunk.as_string() == "prVt").unwrap();
+	assert_eq!(private_chunk.length(), 12);
+	assert!(private_chunk.is_ancillary());
+	assert!(private_chunk.is_safe_to_copy());
+
+	let header_chunk = chunks.first().unwrap();
+	assert_eq!(header_chunk.as_string(), "IHDR");
+	assert!(header_chunk.is_critical());
+	assert!(!header_chunk.is_ancillary());
+	assert!(!header_chunk.is_safe_to_copy());
+
+	let text_chunk = chunks.iter().find(|chunk| chunk.as_string() == "zTXt").unwrap();
+	assert!(text_chunk.is_ancillary());
+	assert!(text_chunk.is_safe_to_copy());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_vec_webp_lossless_canvas_size()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	let mut image_data = read("tests/sample2_simple_lossless.webp").unwrap();
+	assert_eq!(&image_data[12..16], "VP8L".as_bytes());
+
+	let metadata = get_test_metadata()?;
+	metadata.write_to_vec(&mut image_data, FileExtension::WEBP)?;
+
+	// The new VP8X chunk has the EXIF flag set and contains the canvas size
+	// of 1000x1000 pixels, stored as width and height minus one
+	assert_eq!(&image_data[12..16], "VP8X".as_bytes());
+	assert_eq!(image_data[20] & 0x08, 0x08);
+	assert_eq!(&image_data[24..30], &[0xe7, 0x03, 0x00, 0xe7, 0x03, 0x00]);
+
+	let read_metadata = Metadata::new_from_vec(&image_data, FileExtension::WEBP)?;
+	assert_eq!(read_metadata.encode()?, metadata.encode()?);
+
+	Ok(())
+}
+
+#[test]
+fn
+get_icc_profile()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	let icc_profile = (0..=255u8).cycle().take(1000).collect::<Vec<u8>>();
+
+	// JPEG: Profile split into two APP2 segments that are stored out of order
+	let get_icc_segment = |sequence_number: u8, part: &[u8]| -> Vec<u8> {
+		let mut segment = vec![0xff, 0xe2];
+		segment.extend((2 + 12 + 2 + part.len() as u16).to_be_bytes());
+		segment.extend(b"ICC_PROFILE\0");
+		segment.extend([sequence_number, 2]);
+		segment.extend(part);
+		return segment;
+	};
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	assert_eq!(Metadata::get_icc_profile(&image_data, FileExtension::JPEG), None);
+
+	image_data.splice(2..2, [get_icc_segment(2, &icc_profile[600..]), get_icc_segment(1, &icc_profile[..600])].concat());
+	assert_eq!(Metadata::get_icc_profile(&image_data, FileExtension::JPEG), Some(icc_profile.clone()));
+
+	// PNG: Compressed profile in the iCCP chunk
+	let mut iccp_data = b"Test profile\0\0".to_vec();
+	iccp_data.extend(miniz_oxide::deflate::compress_to_vec_zlib(&icc_profile, 8));
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	assert_eq!(Metadata::get_icc_profile(&image_data, FileExtension::PNG { as_zTXt_chunk: true }), None);
+
+	image_data.splice(33..33, get_png_chunk("iCCP", &iccp_data));
+	assert_eq!(Metadata::get_icc_profile(&image_data, FileExtension::PNG { as_zTXt_chunk: true }), Some(icc_profile));
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_names_and_descriptions()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let tag = ExifTag::FNumber(vec![]);
+	assert_eq!(tag.name(),        "FNumber");
+	assert_eq!(tag.description(), "F number of the lens");
+	assert_eq!(tag.get_group(),   ExifTagGroup::EXIF);
+
+	assert_eq!(ExifTag::StripOffsets(vec![], vec![]).name(), "StripOffsets");
+	assert_eq!(ExifTag::UnknownINT8U(vec![], 0xbeef, ExifTagGroup::GENERIC).name(),        "Unknown");
+	assert_eq!(ExifTag::UnknownINT8U(vec![], 0xbeef, ExifTagGroup::GENERIC).description(), "");
+
+	// Names match the variant names as used by the Debug output
+	for (_, tag) in get_test_metadata()?.get_all_tags()
+	{
+		assert!(format!("{:?}", tag).starts_with(tag.name()));
+		assert!(!tag.description().is_empty());
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+read_big_tiff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Little endian BigTIFF data with IFD0 containing an ImageWidth tag stored
+	// as LONG8, an ImageDescription located via an 8 byte offset and an
+	// Orientation tag
+	let entry = |hex: u16, format: u16, count: u64, value: u64| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let big_tiff_data = [
+		vec![0x49, 0x49, 0x2b, 0x00, 0x08, 0x00, 0x00, 0x00], 16u64.to_le_bytes().to_vec(),
+		3u64.to_le_bytes().to_vec(), entry(0x0100, 16, 1, 640), entry(0x010e, 2, 13, 92), entry(0x0112, 3, 1, 1), vec![0; 8],
+		b"BigTIFF test\0".to_vec(),
+	].concat();
+
+	let metadata = Metadata::new_from_vec(&big_tiff_data, FileExtension::TIFF)?;
+	let ifd0     = metadata.get_ifd(little_exif::ifd::ExifTagGroup::GENERIC, 0).unwrap();
+	assert_eq!(ifd0.get_tags(), &vec![
+		ExifTag::ImageWidth(vec![640]),
+		ExifTag::ImageDescription("BigTIFF test".to_string()),
+		ExifTag::Orientation(vec![1]),
+	]);
+
+	// Writing is not supported
+	let mut file_buffer = big_tiff_data.clone();
+	assert!(metadata.write_to_vec(&mut file_buffer, FileExtension::TIFF).is_err());
+	assert_eq!(file_buffer, big_tiff_data);
+
+	Ok(())
+}
+
+#[test]
+fn
+exif_tag_group_names()
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	assert_eq!(ExifTagGroup::all().len(), 4);
+	assert_eq!(ExifTagGroup::GENERIC.to_string(), "IFD0");
+	assert_eq!(ExifTagGroup::EXIF.to_string(),    "ExifIFD");
+	assert_eq!(ExifTagGroup::INTEROP.to_string(), "Interop");
+	assert_eq!(ExifTagGroup::GPS.to_string(),     "GPS");
+}
+
+/// Encodes the given metadata and decodes it again, asserting that no tags
+/// got lost or changed along the way
+fn
+assert_round_trip
+(
+	metadata: &Metadata
+)
+-> Result<(), std::io::Error>
+{
+	let round_trip = Metadata::new_from_vec(&metadata.encode()?, little_exif::filetype::FileExtension::TIFF)?;
+
+	let original_tags   = metadata.get_all_tags().map(|(ifd, tag)| (ifd.get_ifd_type(), ifd.get_generic_ifd_nr(), tag.clone())).collect::<Vec<_>>();
+	let round_trip_tags = round_trip.get_all_tags().map(|(ifd, tag)| (ifd.get_ifd_type(), ifd.get_generic_ifd_nr(), tag.clone())).collect::<Vec<_>>();
+
+	assert!(round_trip_tags.len() >= original_tags.len());
+	assert_eq!(round_trip_tags, original_tags);
+
+	Ok(())
+}
+
+#[test]
+fn
+round_trip_sample_files()
+-> Result<(), std::io::Error>
+{
+	for path in [
+		"tests/read_sample.jpg",
+		"tests/sample2.jpg",
+		"tests/read_sample.tif",
+		"tests/read_sample.webp",
+		"tests/with_exif.jxl",
+		"tests/sample2.png",
+	]
+	{
+		assert_round_trip(&Metadata::new_from_path(Path::new(path))?)?;
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+round_trip_tag_with_unexpected_format()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+
+	// Little endian TIFF data with IFD0 containing an ImageDescription stored
+	// as INT16U, which can't be converted to a string, and an Orientation tag
+	let entry = |hex: u16, format: u16, count: u32, value: u32| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let tiff_data = [
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],
+		vec![0x02, 0x00], entry(0x010e, 3, 2, 0x0002_0001), entry(0x0112, 3, 1, 1), vec![0; 4],
+	].concat();
+
+	let metadata = Metadata::new_from_vec(&tiff_data, FileExtension::TIFF)?;
+	assert_eq!(metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap().get_tags(), &vec![
+		ExifTag::UnknownINT16U(vec![1, 2], 0x010e, ExifTagGroup::GENERIC),
+		ExifTag::Orientation(vec![1]),
+	]);
+
+	assert_round_trip(&metadata)?;
+
+	Ok(())
+}
+
+#[test]
+fn
+unknown_tags_are_preserved()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+
+	// Little endian TIFF data with IFD0 containing unknown tags with inline 
+	// and offset values, laid out (and padded) the same way little_exif 
+	// encodes them
+	let entry = |hex: u16, format: u16, count: u32, value: u32| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let tiff_data = [
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],
+		vec![0x03, 0x00], 
+		entry(0xc000, 3, 2,  0x0002_0001), 
+		entry(0xc001, 2, 8,  52), 
+		entry(0xc002, 7, 10, 60), 
+		vec![0; 4],
+		vec![0; 2], "Grüße\0".as_bytes().to_vec(),
+		vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], vec![0; 2],
+	].concat();
+
+	let metadata = Metadata::new_from_vec(&tiff_data, FileExtension::TIFF)?;
+	assert_eq!(
+		metadata.get_tag_by_hex(0xc000, None).next(), 
+		Some(&ExifTag::UnknownINT16U(vec![1, 2], 0xc000, ExifTagGroup::GENERIC))
+	);
+	assert_eq!(
+		metadata.get_tag_by_hex(0xc001, Some(ExifTagGroup::GENERIC)).next(), 
+		Some(&ExifTag::UnknownSTRING("Grüße".to_string(), 0xc001, ExifTagGroup::GENERIC))
+	);
+	assert_eq!(
+		metadata.get_tag_by_hex(0xc002, None).next(), 
+		Some(&ExifTag::UnknownUNDEF(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 0xc002, ExifTagGroup::GENERIC))
+	);
+
+	assert_eq!(metadata.encode()?, tiff_data);
+
+	Ok(())
+}
+
+#[test]
+fn
+jpeg_comment()
+-> Result<(), std::io::Error>
+{
+	let mut image_data = read("tests/read_sample.jpg").unwrap();
+	let original_metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	Metadata::set_jpeg_comment(&mut image_data, "First comment".as_bytes())?;
+	Metadata::set_jpeg_comment(&mut image_data, "Grüße".as_bytes())?;
+	assert_eq!(Metadata::get_jpeg_comment(&image_data)?, Some("Grüße".as_bytes().to_vec()));
+
+	// The old comment got replaced and the EXIF data is still there
+	assert_eq!(image_data.windows(13).filter(|window| window == b"First comment").count(), 0);
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(metadata.encode()?, original_metadata.encode()?);
+
+	// The payload is limited to 65533 bytes
+	assert!(Metadata::set_jpeg_comment(&mut image_data, &vec![0x41; 65534]).is_err());
+	Metadata::set_jpeg_comment(&mut image_data, &vec![0x41; 65533])?;
+	assert_eq!(Metadata::get_jpeg_comment(&image_data)?.unwrap().len(), 65533);
+
+	assert_eq!(Metadata::get_jpeg_comment(&read("tests/sample2.jpg").unwrap())?, None);
+
+	Ok(())
+}
+
+#[test]
+fn
+encoded_size()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	assert_eq!(metadata.encoded_size()?, metadata.encode()?.len());
+
+	let size_before = metadata.encoded_size()?;
+	metadata.set_thumbnail(&[0xff, 0xd8, 0xff, 0xd9]);
+	assert!(metadata.encoded_size()? > size_before);
+
+	assert_eq!(Metadata::new().encoded_size()?, 8);
+
+	Ok(())
+}
+
+#[test]
+fn
+dedup_tags()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::dedup::DedupStrategy;
+
+	// Little endian TIFF data with IFD0 containing two Model tags
+	let entry = |hex: u16, format: u16, count: u32, value: u32| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let tiff_data = [
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],
+		vec![0x02, 0x00], entry(0x0110, 2, 4, 0x0043_4241), entry(0x0110, 2, 8, 38), vec![0; 4],
+		b"Longer\0\0".to_vec(),
+	].concat();
+
+	let metadata = Metadata::new_from_vec(&tiff_data, FileExtension::TIFF)?;
+	assert_eq!(metadata.get_tag_by_hex(0x0110, None).count(), 2);
+
+	for (strategy, expected) in [
+		(DedupStrategy::KeepFirst,        "ABC"),
+		(DedupStrategy::KeepLast,         "Longer"),
+		(DedupStrategy::KeepLargestCount, "Longer"),
+	]
+	{
+		let mut deduped = metadata.clone();
+		assert_eq!(deduped.dedup_tags(strategy), 1);
+		assert_eq!(
+			deduped.get_tag_by_hex(0x0110, None).collect::<Vec<_>>(), 
+			vec![&ExifTag::Model(expected.to_string())]
+		);
+	}
+
+	// Adding a tag that already exists fails, setting it replaces it
+	let mut deduped = metadata.clone();
+	deduped.dedup_tags(DedupStrategy::KeepFirst);
+	let ifd0 = deduped.get_ifd_mut(ExifTagGroup::GENERIC, 0);
+	assert!(ifd0.add_tag(ExifTag::Model("New".to_string())).is_err());
+	ifd0.set_tag(ExifTag::Model("New".to_string()))?;
+	assert_eq!(ifd0.get_tags(), &vec![ExifTag::Model("New".to_string())]);
+
+	Ok(())
+}
+
+#[test]
+fn
+png_text_chunks()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::vec::read_png_text_chunks;
+	use little_exif::png::vec::set_png_text_chunk;
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	assert_eq!(read_png_text_chunks(&image_data)?, vec![]);
+
+	set_png_text_chunk(&mut image_data, "Title",    "Old title", false)?;
+	set_png_text_chunk(&mut image_data, "Author",   "Jürgen",    false)?;
+	set_png_text_chunk(&mut image_data, "Software", "little_exif", true)?;
+	set_png_text_chunk(&mut image_data, "Title",    "New title", true)?;
+
+	assert_eq!(read_png_text_chunks(&image_data)?, vec![
+		("Title".to_string(),    "New title".to_string()),
+		("Software".to_string(), "little_exif".to_string()),
+		("Author".to_string(),   "Jürgen".to_string()),
+	]);
+
+	// The EXIF data gets stored in a text chunk as well
+	get_test_metadata()?.write_to_vec(
+		&mut image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_png_text_chunks(&image_data)?.len(), 4);
+	assert_eq!(Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true })?.encode()?, get_test_metadata()?.encode()?);
+
+	// Invalid keywords and non-Latin-1 text
+	assert!(set_png_text_chunk(&mut image_data, "",           "Text", false).is_err());
+	assert!(set_png_text_chunk(&mut image_data, &"K".repeat(80), "Text", false).is_err());
+	assert!(set_png_text_chunk(&mut image_data, "Title",      "日本", false).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+metadata_equality()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::ifd::ImageFileDirectory;
+
+	let metadata = get_test_metadata()?;
+
+	// A clone is a deep copy that is not affected by editing the original
+	let mut edited = metadata.clone();
+	assert_eq!(edited, metadata);
+	edited.set_tag(ExifTag::ImageDescription("Edited".to_string()))?;
+	assert_ne!(edited, metadata);
+
+	// The endianness does not take part in the comparison
+	let mut big_endian = metadata.clone();
+	big_endian.set_endian(Endian::Big);
+	assert_eq!(big_endian, metadata);
+
+	// The order of the tags within an IFD does not matter
+	let tags = vec![ExifTag::ImageDescription("A".to_string()), ExifTag::Model("B".to_string())];
+	let ifd          = ImageFileDirectory::new_with_tags(tags.clone(),                          ExifTagGroup::GENERIC, 0);
+	let reversed_ifd = ImageFileDirectory::new_with_tags(tags.into_iter().rev().collect(),      ExifTagGroup::GENERIC, 0);
+	let other_ifd    = ImageFileDirectory::new_with_tags(vec![ExifTag::Model("B".to_string())], ExifTagGroup::GENERIC, 0);
+	assert_eq!(ifd, reversed_ifd);
+	assert_ne!(ifd, other_ifd);
+
+	Ok(())
+}
+
+#[test]
+fn
+metadata_diff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::diff::TagDiff;
+
+	let original = get_test_metadata()?;
+	assert!(original.diff(&original.clone()).is_empty());
+
+	let mut edited = original.clone();
+	edited.set_tag(ExifTag::ImageDescription("Edited".to_string()))?;
+	edited.set_tag(ExifTag::Artist("Someone".to_string()))?;
+	edited.remove_tag(&ExifTag::Model(String::new()), false);
+
+	// Move the ISO from the ExifIFD to IFD0
+	let iso = original.get_tag(&ExifTag::ISO(vec![])).next().unwrap().clone();
+	edited.remove_tag(&iso, false);
+	edited.get_ifd_mut(ExifTagGroup::GENERIC, 0).set_tag_unchecked(iso.clone());
+
+	let diff = original.diff(&edited);
+	assert_eq!(diff.changes.len(), 4);
+	assert!(diff.changes.contains(&TagDiff::Changed { 
+		group:          ExifTagGroup::GENERIC, 
+		generic_ifd_nr: 0, 
+		old:            original.get_tag(&ExifTag::ImageDescription(String::new())).next().unwrap().clone(),
+		new:            ExifTag::ImageDescription("Edited".to_string()),
+	}));
+	assert!(diff.changes.contains(&TagDiff::Added { 
+		group:          ExifTagGroup::GENERIC, 
+		generic_ifd_nr: 0, 
+		tag:            ExifTag::Artist("Someone".to_string()),
+	}));
+	assert!(diff.changes.contains(&TagDiff::Removed { 
+		group:          ExifTagGroup::GENERIC, 
+		generic_ifd_nr: 0, 
+		tag:            original.get_tag(&ExifTag::Model(String::new())).next().unwrap().clone(),
+	}));
+	assert!(diff.changes.contains(&TagDiff::Moved { 
+		from: (ExifTagGroup::EXIF,    0), 
+		to:   (ExifTagGroup::GENERIC, 0), 
+		tag:  iso,
+	}));
+
+	let text = diff.to_string();
+	assert_eq!(text.lines().count(), 4);
+	assert!(text.contains("+ IFD0 0: Artist(\"Someone\")"));
+	assert!(text.contains("> ExifIFD 0 -> IFD0 0: ISO("));
+
+	Ok(())
+}
+
+#[test]
+fn
+typed_errors()
+-> Result<(), std::io::Error>
+{
+	use little_exif::error::LittleExifError;
+	use little_exif::filetype::FileExtension;
+
+	// Wrong signature
+	let result = Metadata::clear_metadata(&mut vec![0u8; 32], little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true });
+	assert!(matches!(result, Err(LittleExifError::InvalidSignature(_))));
+
+	// Reading distinguishes a wrong signature from a file without metadata,
+	// which results in an empty struct
+	for file_type in [FileExtension::JPEG, FileExtension::PNG { as_zTXt_chunk: true }, FileExtension::TIFF]
+	{
+		let result = Metadata::new_from_vec(&vec![0u8; 32], file_type);
+		assert!(matches!(result, Err(LittleExifError::InvalidSignature(_))));
+	}
+	assert!(Metadata::new_from_vec(&read("tests/sample2.png")?, FileExtension::PNG { as_zTXt_chunk: true })?.get_ifds().is_empty());
+
+	let mut image_data = read("tests/sample2.png")?;
+	image_data[29] ^= 0xff;
+	let result = Metadata::new_from_vec(&image_data, FileExtension::PNG { as_zTXt_chunk: true });
+	assert!(matches!(result, Err(LittleExifError::ChecksumMismatch(_))));
+
+	// Broken CRC
+	let mut image_data = read("tests/sample2.png").unwrap();
+	image_data[29] ^= 0xff;
+	let result = Metadata::clear_metadata(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true });
+	assert!(matches!(result, Err(LittleExifError::ChecksumMismatch(_))));
+
+	// No metadata
+	let result = Metadata::read_xmp(&read("tests/sample2.png").unwrap(), little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true });
+	assert!(matches!(result, Err(LittleExifError::NoMetadata(_))));
+
+	// Unsupported format
+	let result = Metadata::new_from_vec_autodetect(&vec![0u8; 32]);
+	assert!(matches!(result, Err(LittleExifError::UnsupportedFormat(_))));
+
+	// Truncated data
+	let mut image_data = read("tests/sample2.png").unwrap();
+	image_data.truncate(40);
+	let result = Metadata::clear_metadata(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true });
+	assert!(matches!(result, Err(LittleExifError::Truncated(_))));
+
+	// Converting into a std::io::Error and back keeps the variant
+	let io_error = std::io::Error::from(LittleExifError::NoMetadata("No metadata found!".to_string()));
+	assert_eq!(io_error.kind(), std::io::ErrorKind::Other);
+	assert!(matches!(LittleExifError::from(io_error), LittleExifError::NoMetadata(_)));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_vec_png_exif_before_idat()
+-> Result<(), std::io::Error>
+{
+	// Returns the byte offsets of the chunks with the given type
+	let get_chunk_offsets = |image_data: &Vec<u8>, chunk_type: &[u8]| -> Vec<usize>
+	{
+		let mut offsets  = Vec::new();
+		let mut position = 8;
+		while position + 8 <= image_data.len()
+		{
+			if &image_data[position+4..position+8] == chunk_type
+			{
+				offsets.push(position);
+			}
+			let length = u32::from_be_bytes(image_data[position..position+4].try_into().unwrap()) as usize;
+			position += length + 12;
+		}
+		return offsets;
+	};
+
+	// Add a palette and an ICC profile, after which the eXIf chunk belongs
+	let mut image_data = read("tests/sample2.png").unwrap();
+	let mut tail       = image_data.split_off(33);
+	image_data.append(&mut get_png_chunk("iCCP", b"icc\x00\x00\x78\x9c\x03\x00\x00\x00\x00\x01"));
+	image_data.append(&mut get_png_chunk("PLTE", &[0x00, 0x00, 0x00, 0xff, 0xff, 0xff]));
+	image_data.append(&mut get_png_chunk("tRNS", &[0x00, 0x00]));
+	image_data.append(&mut tail);
+
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })?;
+
+	let exif_offsets = get_chunk_offsets(&image_data, b"eXIf");
+	let plte_offset  = get_chunk_offsets(&image_data, b"PLTE")[0];
+	let idat_offset  = get_chunk_offsets(&image_data, b"IDAT")[0];
+	assert_eq!(exif_offsets.len(), 1);
+	assert!(exif_offsets[0] > plte_offset);
+	assert!(exif_offsets[0] < idat_offset);
+
+	Ok(())
+}
+
+#[test]
+fn
+new_rational_tags()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+
+	let latitude = ExifTag::new_rational(
+		&ExifTag::GPSLatitude(Vec::new()),
+		vec![(48, 1), (12, 1), (3456, 100)]
+	).unwrap();
+	let compensation = ExifTag::new_srational(&ExifTag::ExposureCompensation(Vec::new()), vec![(-1, 3)]).unwrap();
+	let unknown      = ExifTag::new_rational(
+		&ExifTag::UnknownRATIONAL64U(Vec::new(), 0xc0de, little_exif::ifd::ExifTagGroup::GENERIC),
+		vec![(1, 2), (3, 4)]
+	).unwrap();
+
+	// Wrong format or number of components
+	assert!(ExifTag::new_rational( &ExifTag::GPSLatitude(Vec::new()),          vec![(48, 1)]).is_err());
+	assert!(ExifTag::new_rational( &ExifTag::ExposureCompensation(Vec::new()), vec![(1, 3)] ).is_err());
+	assert!(ExifTag::new_srational(&ExifTag::GPSLatitude(Vec::new()),          vec![(1, 3)] ).is_err());
+	assert!(ExifTag::new_rational( &ExifTag::ISO(Vec::new()),                  vec![(1, 1)] ).is_err());
+
+	// The values are the same regardless of the byte order used for encoding
+	for endian in [Endian::Little, Endian::Big]
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(endian);
+		metadata.set_tag(latitude.clone())?;
+		metadata.set_tag(compensation.clone())?;
+		metadata.set_tag(unknown.clone())?;
+
+		let mut image_data = read("tests/sample2.jpg").unwrap();
+		metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+		let read_metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+		assert_eq!(
+			read_metadata.get_tag(&ExifTag::GPSLatitude(Vec::new())).next().unwrap().as_rational_vec(),
+			Some(vec![(48, 1), (12, 1), (3456, 100)])
+		);
+		assert_eq!(
+			read_metadata.get_tag(&ExifTag::ExposureCompensation(Vec::new())).next(),
+			Some(&compensation)
+		);
+		assert_eq!(
+			read_metadata.get_tag(&unknown).next().unwrap().as_rational_vec(),
+			Some(vec![(1, 2), (3, 4)])
+		);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+read_dng_without_image_data()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::warning::MetadataWarning;
+
+	// A DNG-like TIFF with a preview in IFD0, the raw image in a SubIFD and
+	// a GPS IFD. The strips and tiles of the raw image are not part of the
+	// data, as if the data got truncated after the metadata
+	let entry = |hex: u16, format: u16, count: u32, value: u32| -> Vec<u8> {
+		[hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+	};
+	let dng_data = [
+		// Header and IFD0 (offset 8) with 8 entries, followed by the make
+		// (offset 110) and the preview strip (offset 116)
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],
+		8u16.to_le_bytes().to_vec(),
+		entry(0x00fe, 4, 1, 1),
+		entry(0x010f, 2, 6, 110),
+		entry(0x0110, 2, 4, u32::from_le_bytes(*b"EOS\0")),
+		entry(0x0111, 4, 1, 116),
+		entry(0x0117, 4, 1, 4),
+		entry(0x014a, 4, 1, 120),
+		entry(0x8825, 4, 1, 202),
+		entry(0xc612, 1, 4, 0x00000401),
+		vec![0; 4],
+		b"Canon\0".to_vec(),
+		vec![0xaa; 4],
+
+		// Raw image SubIFD (offset 120) with 5 entries, followed by the 
+		// strip offsets (offset 186) and byte counts (offset 194)
+		5u16.to_le_bytes().to_vec(),
+		entry(0x00fe, 4, 1, 0),
+		entry(0x0111, 4, 2, 186),
+		entry(0x0117, 4, 2, 194),
+		entry(0x0144, 4, 1, 0x2000_0000),
+		entry(0x0145, 4, 1, 0x0010_0000),
+		vec![0; 4],
+		[0x1000_0000u32.to_le_bytes(), 0x1010_0000u32.to_le_bytes()].concat(),
+		[0x0010_0000u32.to_le_bytes(), 0x0010_0000u32.to_le_bytes()].concat(),
+
+		// GPS IFD (offset 202) with 2 entries, followed by the latitude
+		2u16.to_le_bytes().to_vec(),
+		entry(0x0001, 2, 2, u32::from_le_bytes(*b"N\0\0\0")),
+		entry(0x0002, 5, 3, 232),
+		vec![0; 4],
+		[48u32, 1, 12, 1, 3456, 100].iter().flat_map(|value| value.to_le_bytes()).collect(),
+	].concat();
+
+	let path = Path::new("tests/read_dng_without_image_data.dng");
+	std::fs::write(path, &dng_data)?;
+	let (metadata, warnings) = Metadata::new_from_path_with_warnings(path)?;
+	remove_file(path)?;
+
+	// Camera and GPS tags
+	assert_eq!(metadata.get_tag(&ExifTag::Make(String::new())).next(),  Some(&ExifTag::Make("Canon".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::Model(String::new())).next(), Some(&ExifTag::Model("EOS".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::GPSLatitudeRef(String::new())).next(), Some(&ExifTag::GPSLatitudeRef("N".to_string())));
+	assert_eq!(
+		metadata.get_tag(&ExifTag::GPSLatitude(Vec::new())).next().unwrap().as_rational_vec(),
+		Some(vec![(48, 1), (12, 1), (3456, 100)])
+	);
+
+	// The preview strip is part of the data and is read
+	let ifd0 = metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap();
+	assert!(ifd0.get_tags().contains(&ExifTag::StripOffsets(Vec::new(), vec![vec![0xaa; 4]])));
+
+	// The strips and tiles of the raw image are only recorded as offsets
+	let raw_ifd = &ifd0.get_sub_ifds()[0];
+	assert!(raw_ifd.get_tags().contains(&ExifTag::StripOffsets(vec![0x1000_0000, 0x1010_0000], Vec::new())));
+	assert!(raw_ifd.get_tags().contains(&ExifTag::StripByteCounts(vec![0x0010_0000, 0x0010_0000])));
+	assert!(raw_ifd.get_tags().contains(&ExifTag::UnknownINT32U(vec![0x2000_0000], 0x0144, ExifTagGroup::GENERIC)));
+	assert!(warnings.contains(&MetadataWarning::UnreadableImageData { hex: 0x0111, group: ExifTagGroup::GENERIC }));
+
+	// Encoding keeps the recorded offsets
+	let round_trip = Metadata::new_from_vec(&metadata.encode()?, little_exif::filetype::FileExtension::TIFF)?;
+	let raw_ifd    = &round_trip.get_ifd(ExifTagGroup::GENERIC, 0).unwrap().get_sub_ifds()[0];
+	assert!(raw_ifd.get_tags().contains(&ExifTag::StripOffsets(vec![0x1000_0000, 0x1010_0000], Vec::new())));
+
+	// Merging takes over the SubIFDs as well
+	let mut merged = Metadata::new();
+	merged.merge(&metadata, little_exif::metadata::merge::MergeStrategy::Error)?;
+	assert_eq!(merged.get_ifd(ExifTagGroup::GENERIC, 0).unwrap().get_sub_ifds(), ifd0.get_sub_ifds());
+	merged.merge(&round_trip, little_exif::metadata::merge::MergeStrategy::Error)?;
+
+	Ok(())
+}
+
+#[test]
+fn
+get_ifd_mut_keeps_ifds_sorted()
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	// Create 100 IFDs in a scrambled order
+	let mut metadata = Metadata::new();
+	for i in 0..100u32
+	{
+		let generic_ifd_nr = (i * 37) % 25;
+		let group          = ExifTagGroup::all()[((i * 7) % 4) as usize];
+		metadata.get_ifd_mut(group, generic_ifd_nr);
+	}
+
+	// Sorted by generic IFD number, then by group, with the parent IFDs 
+	// created along the way, i.e. every combination of number and group
+	let order = metadata.get_ifds().iter()
+		.map(|ifd| (ifd.get_generic_ifd_nr(), ifd.get_ifd_type()))
+		.collect::<Vec<_>>();
+
+	let mut expected_order = Vec::new();
+	for generic_ifd_nr in 0..25u32
+	{
+		for group in [ExifTagGroup::GENERIC, ExifTagGroup::EXIF, ExifTagGroup::INTEROP, ExifTagGroup::GPS]
+		{
+			expected_order.push((generic_ifd_nr, group));
+		}
+	}
+
+	assert_eq!(order, expected_order);
+}
+
+#[test]
+fn
+raw_exif()
+-> Result<(), std::io::Error>
+{
+	assert_eq!(Metadata::new().raw_exif(), None);
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let mut metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let raw_exif     = metadata.raw_exif().unwrap().to_vec();
+
+	// The raw data is the encoded data without the EXIF header
+	assert_eq!(raw_exif, get_test_metadata()?.encode()?);
+	assert!(image_data.windows(raw_exif.len()).any(|window| window == raw_exif));
+
+	// Edits are not reflected in the raw data
+	metadata.set_tag(ExifTag::ImageDescription("Edited".to_string()))?;
+	assert_eq!(metadata.raw_exif().unwrap(), &raw_exif[..]);
+	assert_ne!(metadata.encode()?, raw_exif);
+
+	// Empty metadata due to missing EXIF data
+	let metadata = Metadata::new_from_vec(&read("tests/sample2.png").unwrap(), little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true })?;
+	assert_eq!(metadata.raw_exif(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+interop_ifd_round_trip()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()))?;
+	metadata.set_tag(ExifTag::InteroperabilityVersion(b"0100".to_vec()))?;
+	metadata.set_tag(ExifTag::RelatedImageWidth(vec![640]))?;
+
+	let mut image_data = read("tests/sample2.jpg")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	let read_back = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+
+	// The written ExifIFD points to the Interop IFD via tag 0xA005 (type
+	// LONG), which the decoder follows into a separate IFD
+	let raw_exif = read_back.raw_exif().unwrap();
+	let pointer  = if raw_exif.starts_with(b"II") { [0x05, 0xa0, 0x04, 0x00] } else { [0xa0, 0x05, 0x00, 0x04] };
+	assert!(raw_exif.windows(4).any(|window| window == pointer));
+
+	let interop_ifd = read_back.get_ifd(ExifTagGroup::INTEROP, 0).unwrap();
+	assert_eq!(interop_ifd.get_tags().len(), 3);
+	assert_eq!(
+		read_back.get_tag_by_hex(0x0001, Some(ExifTagGroup::INTEROP)).next(),
+		Some(&ExifTag::InteroperabilityIndex("R98".to_string()))
+	);
+	assert_eq!(
+		read_back.get_tag(&ExifTag::RelatedImageWidth(Vec::new())).next(),
+		Some(&ExifTag::RelatedImageWidth(vec![640]))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+xp_tags()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_xp_title(), None);
+
+	metadata.set_xp_title("Sonnenuntergang über Wien");
+	metadata.set_xp_keywords("sunset;vienna");
+	assert_eq!(
+		metadata.get_tag(&ExifTag::XPKeywords(Vec::new())).next(),
+		Some(&ExifTag::XPKeywords(b"s\0u\0n\0s\0e\0t\0;\0v\0i\0e\0n\0n\0a\0\0\0".to_vec()))
+	);
+
+	let mut image_data = read("tests/sample2.jpg")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	let read_back = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert_eq!(read_back.get_xp_title(),    Some("Sonnenuntergang über Wien".to_string()));
+	assert_eq!(read_back.get_xp_keywords(), Some("sunset;vienna".to_string()));
+	assert_eq!(read_back.get_xp_author(),   None);
+
+	// Odd number of bytes can't be UTF-16
+	metadata.set_tag(ExifTag::XPComment(vec![0x41, 0x00, 0x42]))?;
+	assert_eq!(metadata.get_xp_comment(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+file_extension_mime_types()
+{
+	use little_exif::filetype::FileExtension;
+
+	for (extension, file_type) in [
+		("jpg",  FileExtension::JPEG),
+		("JPEG", FileExtension::JPEG),
+		(".jpe", FileExtension::JPEG),
+		("tif",  FileExtension::TIFF),
+		("tiff", FileExtension::TIFF),
+		("png",  FileExtension::PNG { as_zTXt_chunk: true }),
+		("webp", FileExtension::WEBP),
+		("psd",  FileExtension::PSD),
+		("PSB",  FileExtension::PSD),
+	]
+	{
+		assert_eq!(FileExtension::from_extension_str(extension).unwrap(), file_type);
+
+		// Going through the MIME type leads to the same file type
+		assert_eq!(FileExtension::from_mime(file_type.mime_type()).unwrap(), file_type);
+	}
+
+	assert_eq!(FileExtension::from_mime("Image/PNG; charset=binary").unwrap().mime_type(), "image/png");
+	assert_eq!(FileExtension::HEIF.mime_type(), "image/heif");
+	assert!(FileExtension::from_mime("application/pdf").is_err());
+	assert!(FileExtension::from_extension_str("bmp").is_err());
+}
+
+#[test]
+fn
+write_tiff_in_place()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+
+	let original          = read("tests/read_sample.tif")?;
+	let original_metadata = Metadata::new_from_vec(&original, FileExtension::TIFF)?;
+	let get_strips        = |metadata: &Metadata| {
+		metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap().get_tags().iter()
+			.find(|tag| matches!(tag, ExifTag::StripOffsets(_, _))).cloned()
+	};
+
+	// Write only a description, without any of the image describing tags
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Appended".to_string()))?;
+
+	let mut file_buffer = original.clone();
+	metadata.write_to_vec(&mut file_buffer, FileExtension::TIFF)?;
+	metadata.write_to_vec(&mut file_buffer, FileExtension::TIFF)?;
+
+	// Apart from the link to IFD0 in the header, the data stays untouched
+	assert!(file_buffer.len() > original.len());
+	assert_eq!(file_buffer[..4],                original[..4]);
+	assert_eq!(file_buffer[8..original.len()], original[8..]);
+
+	let read_back = Metadata::new_from_vec(&file_buffer, FileExtension::TIFF)?;
+	assert_eq!(
+		read_back.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		Some(&ExifTag::ImageDescription("Appended".to_string()))
+	);
+	assert_eq!(
+		read_back.get_tag(&ExifTag::ImageWidth(Vec::new())).next(),
+		original_metadata.get_tag(&ExifTag::ImageWidth(Vec::new())).next()
+	);
+	assert!(get_strips(&read_back).is_some());
+	assert_eq!(get_strips(&read_back), get_strips(&original_metadata));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_tiff_in_place_undecodable_ifd()
+{
+	use little_exif::filetype::FileExtension;
+
+	// IFD0 consists of a single entry with the invalid format 99
+	let mut tiff_data = b"II*\x00\x08\x00\x00\x00".to_vec();
+	tiff_data.extend([0x01, 0x00]);
+	tiff_data.extend([0x00, 0x01, 0x63, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	tiff_data.extend([0x00; 4]);
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Appended".to_string())).unwrap();
+
+	// Writing must not replace the IFDs describing the image data
+	let mut file_buffer = tiff_data.clone();
+	assert!(metadata.write_to_vec(&mut file_buffer, FileExtension::TIFF).is_err());
+	assert_eq!(file_buffer, tiff_data);
+}
+
+#[test]
+fn
+png_chunk_offsets()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::vec::parse_png_with_offsets;
+
+	let file_data = read("tests/sample2.png")?;
+	let chunks    = parse_png_with_offsets(&file_data)?;
+
+	assert_eq!(chunks.first().unwrap().0.as_string(), "IHDR");
+	assert_eq!(chunks.first().unwrap().1, 8);
+
+	// Each offset points to the length and type fields of its chunk, and 
+	// the last chunk ends with the file
+	for (chunk, offset) in &chunks
+	{
+		let offset = *offset as usize;
+		assert_eq!(u32::from_be_bytes(file_data[offset..offset+4].try_into().unwrap()), chunk.length());
+		assert_eq!(&file_data[offset+4..offset+8], chunk.as_string().as_bytes());
+	}
+
+	let (last_chunk, last_offset) = chunks.last().unwrap();
+	assert_eq!(last_chunk.as_string(), "IEND");
+	assert_eq!(*last_offset as usize + 12, file_data.len());
+
+	Ok(())
+}
+
+#[test]
+fn
+png_raw_profile_keyword_variants()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::png::PngReadOptions;
+	use little_exif::png::PngTextMode;
+	use little_exif::png::PngWriteOptions;
+
+	let metadata = get_test_metadata()?;
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	metadata.write_to_png_vec(
+		&mut image_data, 
+		&PngWriteOptions { text_mode: PngTextMode::TextUncompressed, ..Default::default() }
+	)?;
+
+	// Get the encoded text of the new tEXt chunk after the IHDR chunk
+	let ihdr_length  = u32::from_be_bytes(image_data[8..12].try_into().unwrap()) as usize;
+	let chunk_start  = 8 + 12 + ihdr_length;
+	let chunk_length = u32::from_be_bytes(image_data[chunk_start..chunk_start+4].try_into().unwrap()) as usize;
+	let chunk_end    = chunk_start + 12 + chunk_length;
+	let text         = image_data[chunk_start+8+22..chunk_end-4].to_vec();
+
+	let with_keyword = |keyword: &[u8]| -> Vec<u8> {
+		let mut modified = image_data.clone();
+		modified.splice(chunk_start..chunk_end, get_png_chunk("tEXt", &[keyword, b"\0", &text].concat()));
+		return modified;
+	};
+
+	// Casing and trailing whitespace of the keyword don't matter
+	let modified = with_keyword(b"RAW PROFILE TYPE EXIF \n");
+	assert_eq!(Metadata::new_from_vec(&modified, FileExtension::PNG { as_zTXt_chunk: true })?, metadata);
+
+	// The APP1 variant is only read if enabled
+	let modified = with_keyword(b"Raw profile type APP1");
+	assert!(Metadata::new_from_vec(&modified, FileExtension::PNG { as_zTXt_chunk: true })?.get_ifds().is_empty());
+
+	let options = PngReadOptions { match_raw_profile_app1: true, ..Default::default() };
+	assert_eq!(Metadata::new_from_png_vec(&modified, &options)?, metadata);
+
+	// Writing only replaces the APP1 variant if enabled as well
+	let mut rewritten = modified.clone();
+	metadata.write_to_png_vec(&mut rewritten, &PngWriteOptions::default())?;
+	assert_eq!(rewritten.windows(21).filter(|window| window == b"Raw profile type APP1").count(), 1);
+
+	let mut rewritten = modified.clone();
+	metadata.write_to_png_vec(&mut rewritten, &PngWriteOptions { match_raw_profile_app1: true, ..Default::default() })?;
+	assert_eq!(rewritten.windows(21).filter(|window| window == b"Raw profile type APP1").count(), 0);
+
+	// Keywords merely starting with the expected one don't match
+	let modified = with_keyword(b"Raw profile type exif2");
+	assert!(Metadata::new_from_vec(&modified, FileExtension::PNG { as_zTXt_chunk: true })?.get_ifds().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+png_max_decompressed_size()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::limits::DEFAULT_MAX_DECOMPRESSED_SIZE;
+	use little_exif::png::PngReadOptions;
+
+	let metadata = get_test_metadata()?;
+
+	let mut image_data = read("tests/sample2.png")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::PNG { as_zTXt_chunk: true })?;
+
+	// The limit only applies to the given read
+	let options = PngReadOptions { max_decompressed_size: 16, ..Default::default() };
+	assert!(Metadata::new_from_png_vec(&image_data, &options).is_err());
+
+	let options = PngReadOptions::default();
+	assert_eq!(options.max_decompressed_size, DEFAULT_MAX_DECOMPRESSED_SIZE);
+	assert_eq!(Metadata::new_from_png_vec(&image_data, &options)?, metadata);
+
+	Ok(())
+}
+
+#[test]
+fn
+display_metadata()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::rational::uR64;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()))?;
+	metadata.set_tag(ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 200 }]))?;
+	metadata.set_tag(ExifTag::ISO(vec![100]))?;
+	metadata.set_tag(ExifTag::ExifVersion(b"0232".to_vec()))?;
+	metadata.set_tag(ExifTag::UnknownINT8U(vec![0x01, 0xab], 0xc000, ExifTagGroup::GENERIC))?;
+	metadata.set_tag(ExifTag::UnknownUNDEF(vec![0x00; 100], 0xc001, ExifTagGroup::GENERIC))?;
+	metadata.set_tag(ExifTag::GPSLatitudeRef("N".to_string()))?;
+
+	let output = metadata.to_string();
+	let lines  = output.lines().collect::<Vec<&str>>();
+
+	assert_eq!(lines[0], "---- IFD0 ----");
+	assert!(lines.contains(&"ImageDescription                : Hello World!"));
+	assert!(lines.contains(&"0xc000                          : 01 ab"));
+	assert!(lines.contains(&"0xc001                          : (Binary data 100 bytes)"));
+	assert!(lines.contains(&"---- ExifIFD ----"));
+	assert!(lines.contains(&"ExposureTime                    : 1/200"));
+	assert!(lines.contains(&"ISO                             : 100"));
+	assert!(lines.contains(&"ExifVersion                     : 0232"));
+	assert!(lines.contains(&"---- GPS ----"));
+	assert!(lines.contains(&"GPSLatitudeRef                  : N"));
+
+	Ok(())
+}
+
+#[test]
+fn
+drop_thumbnail_if_too_large()
+-> Result<(), Box<dyn std::error::Error>>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::thumbnail::ThumbnailPolicy;
+	use little_exif::metadata::warning::MetadataWarning;
+
+	let image_data = read("tests/sample2.jpg")?;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()))?;
+	metadata.set_thumbnail(&vec![0xff; 70000]);
+
+	// By default, the thumbnail is kept and the metadata does not fit
+	assert_eq!(metadata.get_thumbnail_policy(), ThumbnailPolicy::Keep);
+	assert!(metadata.to_file_buffer(image_data.clone(), FileExtension::JPEG).is_err());
+
+	metadata.set_thumbnail_policy(ThumbnailPolicy::DropIfTooLarge);
+	let mut new_image_data = image_data.clone();
+	let warnings = metadata.write_to_vec_with_warnings(&mut new_image_data, FileExtension::JPEG)?;
+	assert_eq!(warnings, vec![MetadataWarning::ThumbnailDropped { size: 70000 }]);
+
+	let written = Metadata::new_from_vec(&new_image_data, FileExtension::JPEG)?;
+	assert_eq!(written.get_thumbnail(), None);
+	assert_eq!(
+		written.get_tag(&ExifTag::ImageDescription(String::new())).next(), 
+		Some(&ExifTag::ImageDescription("Hello World!".to_string()))
+	);
+
+	// A thumbnail that fits is kept without warnings
+	metadata.set_thumbnail(&vec![0xff; 1000]);
+	let mut new_image_data = image_data.clone();
+	assert!(metadata.write_to_vec_with_warnings(&mut new_image_data, FileExtension::JPEG)?.is_empty());
+	assert_eq!(Metadata::new_from_vec(&new_image_data, FileExtension::JPEG)?.get_thumbnail(), Some(vec![0xff; 1000]));
+
+	Ok(())
+}
+
+#[test]
+fn
+ifd_get_tag_mut()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	let ifd = metadata.get_ifd_mut(ExifTagGroup::EXIF, 0);
+
+	match ifd.get_tag_mut(ExifTag::ISO(Vec::new()).as_u16())
+	{
+		Some(ExifTag::ISO(values)) => values[0] += 100,
+		_                          => panic!("ISO tag not found!"),
+	}
+	assert!(ifd.get_tag_mut(ExifTag::Flash(Vec::new()).as_u16()).is_none());
+
+	let iso = metadata.get_tag(&ExifTag::ISO(Vec::new())).next().cloned();
+	assert_eq!(iso, Some(ExifTag::ISO(vec![2806])));
+
+	Ok(())
+}
+
+#[test]
+fn
+set_dimensions_from_buffer()
+-> Result<(), Box<dyn std::error::Error>>
+{
+	use little_exif::filetype::FileExtension;
+
+	let width_tag  = ExifTag::ExifImageWidth( Vec::new());
+	let height_tag = ExifTag::ExifImageHeight(Vec::new());
+
+	for (path, file_type, expected_dimensions) in [
+		("tests/sample2.jpg",                    FileExtension::JPEG,                          (1000, 1000)),
+		("tests/read_sample.jpg",                FileExtension::JPEG,                          (1,    1   )),
+		("tests/sample2.png",                    FileExtension::PNG { as_zTXt_chunk: false }, (1000, 1000)),
+		("tests/sample2_extended.webp",          FileExtension::WEBP,                          (1000, 1000)),
+		("tests/sample2_simple_lossy.webp",      FileExtension::WEBP,                          (1000, 1000)),
+		("tests/sample2_simple_lossless.webp",   FileExtension::WEBP,                          (1000, 1000)),
+	]
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_dimensions_from_buffer(&read(path)?, file_type)?;
+		assert_eq!(metadata.get_tag(&width_tag ).next(), Some(&ExifTag::ExifImageWidth( vec![expected_dimensions.0])));
+		assert_eq!(metadata.get_tag(&height_tag).next(), Some(&ExifTag::ExifImageHeight(vec![expected_dimensions.1])));
+	}
+
+	// Width and height are not mixed up
+	let mut png_data = vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+	png_data.extend(get_png_chunk("IHDR", &[0, 0, 2, 128, 0, 0, 1, 224, 8, 2, 0, 0, 0]));
+
+	let mut metadata = Metadata::new();
+	metadata.set_dimensions_from_buffer(&png_data, FileExtension::PNG { as_zTXt_chunk: false })?;
+	assert_eq!(metadata.get_tag(&width_tag ).next(), Some(&ExifTag::ExifImageWidth( vec![640])));
+	assert_eq!(metadata.get_tag(&height_tag).next(), Some(&ExifTag::ExifImageHeight(vec![480])));
+
+	// Invalid data results in an error
+	assert!(metadata.set_dimensions_from_buffer(&png_data, FileExtension::JPEG).is_err());
+	assert!(metadata.set_dimensions_from_buffer(&png_data[..20], FileExtension::PNG { as_zTXt_chunk: false }).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+signed_and_float_formats_round_trip()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::filetype::FileExtension;
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::rational::iR64;
+
+	for endian in [Endian::Little, Endian::Big]
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(endian);
+		metadata.set_tag(ExifTag::ShutterSpeedValue(vec![iR64 { nominator: -1, denominator: 3 }]))?;
+		metadata.set_tag(ExifTag::BrightnessValue(  vec![iR64 { nominator: -25, denominator: 10 }]))?;
+		metadata.set_tag(ExifTag::UnknownINT8S( vec![-128, 127],             0xc000, ExifTagGroup::GENERIC))?;
+		metadata.set_tag(ExifTag::UnknownINT16S(vec![-32768, 32767],         0xc001, ExifTagGroup::GENERIC))?;
+		metadata.set_tag(ExifTag::UnknownINT32S(vec![i32::MIN, -1, i32::MAX], 0xc002, ExifTagGroup::GENERIC))?;
+		metadata.set_tag(ExifTag::UnknownFLOAT( vec![-1.5, 0.25],            0xc003, ExifTagGroup::GENERIC))?;
+		metadata.set_tag(ExifTag::UnknownDOUBLE(vec![-0.1],                  0xc004, ExifTagGroup::GENERIC))?;
+
+		let mut image_data = read("tests/sample2.jpg")?;
+		metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+		let read_metadata = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+
+		assert_eq!(
+			read_metadata.into_iter().collect::<Vec<&ExifTag>>(),
+			metadata.into_iter().collect::<Vec<&ExifTag>>()
+		);
+
+		let get = |tag: ExifTag| read_metadata.get_tag(&tag).next().unwrap().clone();
+
+		let shutter_speed = get(ExifTag::ShutterSpeedValue(Vec::new()));
+		assert_eq!(shutter_speed.as_srational_vec(), Some(vec![(-1, 3)]));
+		assert_eq!(shutter_speed.as_f64_vec(false),  Some(vec![-1.0 / 3.0]));
+		assert_eq!(get(ExifTag::BrightnessValue(Vec::new())).as_f64_vec(false), Some(vec![-2.5]));
+
+		let get_unknown = |hex: u16| read_metadata.get_tag_by_hex(hex, Some(ExifTagGroup::GENERIC)).next().unwrap().clone();
+
+		assert_eq!(get_unknown(0xc000).as_i32_vec(), Some(vec![-128, 127]));
+		assert_eq!(get_unknown(0xc001).as_i32_vec(), Some(vec![-32768, 32767]));
+		assert_eq!(get_unknown(0xc002).as_i32_vec(), Some(vec![i32::MIN, -1, i32::MAX]));
+		assert_eq!(get_unknown(0xc003).as_f64_vec(false), Some(vec![-1.5, 0.25]));
+		assert_eq!(get_unknown(0xc004).as_f64_vec(false), Some(vec![-0.1]));
+		assert_eq!(get_unknown(0xc003).as_i32_vec(), None);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+has_tag_and_group()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+
+	assert!( metadata.has_tag(&ExifTag::ISO(Vec::new())));
+	assert!( metadata.has_tag(&ExifTag::Model(String::new())));
+	assert!(!metadata.has_tag(&ExifTag::Make(String::new())));
+
+	assert!( metadata.has_tag_by_hex(0x010e));
+	assert!(!metadata.has_tag_by_hex(0x010f));
+
+	assert!( metadata.has_group(ExifTagGroup::GENERIC));
+	assert!( metadata.has_group(ExifTagGroup::EXIF));
+	assert!(!metadata.has_group(ExifTagGroup::GPS));
+
+	// An IFD that is kept despite being empty does not count
+	metadata.set_tag(ExifTag::GPSAltitudeRef(vec![0]))?;
+	assert!(metadata.has_group(ExifTagGroup::GPS));
+	metadata.remove_tag(&ExifTag::GPSAltitudeRef(Vec::new()), true);
+	assert!(!metadata.has_group(ExifTagGroup::GPS));
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_read_metadata_raw()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::png::PngReadOptions;
+	use little_exif::png::vec::read_metadata_raw;
+
+	let metadata = get_test_metadata()?;
+	let encoded  = metadata.encode()?;
+
+	// The zTXt chunk is decompressed and decoded, keeping the "Exif" header
+	let mut image_data = read("tests/sample2.png")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::PNG { as_zTXt_chunk: true })?;
+	let raw_exif = read_metadata_raw(&image_data)?;
+	assert_eq!(&raw_exif[..6], b"Exif\0\0");
+	assert_eq!(&raw_exif[6..6+encoded.len()], &encoded[..]);
+
+	// The eXIf chunk payload is returned as it is
+	let mut image_data = read("tests/sample2.png")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::PNG { as_zTXt_chunk: false })?;
+	assert_eq!(read_metadata_raw(&image_data)?, encoded);
+
+	// No metadata at all
+	assert!(read_metadata_raw(&read("tests/sample2.png")?).is_err());
+
+	// Nothing gets synthesized from an orNT chunk, in contrast to reading
+	// the metadata with the orNT option enabled
+	let options = PngReadOptions { orientation_from_orNT: true, ..Default::default() };
+	let mut image_data = read("tests/sample2.png")?;
+	image_data.splice(8+25..8+25, get_png_chunk("orNT", &[6]));
+	assert!(read_metadata_raw(&image_data).is_err());
+	assert_eq!(
+		Metadata::new_from_png_vec(&image_data, &options)?.get_tag(&ExifTag::Orientation(Vec::new())).next(),
+		Some(&ExifTag::Orientation(vec![6]))
+	);
+
+	// With actual EXIF data present, only its payload is returned
+	metadata.write_to_vec(&mut image_data, FileExtension::PNG { as_zTXt_chunk: false })?;
+	assert_eq!(read_metadata_raw(&image_data)?, encoded);
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_multiple_raw_profile_zTXt_chunks()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::png::vec::parse_png_with_offsets;
+
+	let metadata  = get_test_metadata()?;
+	let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+
+	let mut image_data = read("tests/sample2.png")?;
+	metadata.write_to_vec(&mut image_data, file_type)?;
+
+	// Insert two garbage chunks before the valid one, right after the IHDR
+	// chunk: One that can't be inflated and one without an EXIF header
+	let mut garbage = get_png_chunk("zTXt", b"Raw profile type exif\0\0not zlib data");
+	garbage.extend(get_png_chunk("tEXt", b"Raw profile type exif\0\nexif\n       4\n0123abcd\n"));
+	let ihdr_end = 8 + 25;
+	image_data.splice(ihdr_end..ihdr_end, garbage);
+
+	let count_exif_chunks = |data: &Vec<u8>| -> usize {
+		parse_png_with_offsets(data).unwrap()
+			.iter()
+			.filter(|(chunk, _)| chunk.as_string() == "zTXt" || chunk.as_string() == "tEXt")
+			.count()
+	};
+	assert_eq!(count_exif_chunks(&image_data), 3);
+
+	// The valid chunk is found despite the garbage in front of it
+	let read_metadata = Metadata::new_from_vec(&image_data, file_type)?;
+	assert_eq!(
+		read_metadata.into_iter().collect::<Vec<&ExifTag>>(),
+		metadata.into_iter().collect::<Vec<&ExifTag>>()
+	);
+
+	// Writing removes all of them and leaves exactly one
+	metadata.write_to_vec(&mut image_data, file_type)?;
+	assert_eq!(count_exif_chunks(&image_data), 1);
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_debug_bytes_match_encoding()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+
+	for endian in [Endian::Little, Endian::Big]
+	{
+		let tag = ExifTag::ImageDescription("Hello World!".to_string());
+
+		let mut metadata = Metadata::new();
+		metadata.set_endian(endian.clone());
+		metadata.set_tag(tag.clone())?;
+		let encoded = metadata.encode()?;
+
+		// The only entry of IFD0 starts after the header and the entry count
+		let debug_bytes = tag.debug_bytes(&endian);
+		assert_eq!(&debug_bytes[0..8], &encoded[10..18]);
+
+		let offset = match endian
+		{
+			Endian::Little => u32::from_le_bytes(encoded[18..22].try_into().unwrap()),
+			Endian::Big    => u32::from_be_bytes(encoded[18..22].try_into().unwrap()),
+		} as usize;
+		assert_eq!(&debug_bytes[12..], &encoded[offset..offset + 13]);
+
+		assert!(tag.hex_dump(&endian).starts_with("entry: "));
+		assert_eq!(tag.hex_dump(&endian).lines().count(), 2);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+raw_exif_block_with_exif_header()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::detect_file_type;
+	use little_exif::filetype::FileExtension;
+
+	let metadata = get_test_metadata()?;
+	let tiff_data = metadata.encode()?;
+
+	let mut exif_block = b"Exif\0\0".to_vec();
+	exif_block.extend(&tiff_data);
+
+	for data in [&tiff_data, &exif_block]
+	{
+		assert_eq!(detect_file_type(data), Some(FileExtension::TIFF));
+
+		let read_metadata = Metadata::new_from_vec(data, FileExtension::TIFF)?;
+		assert_eq!(
+			read_metadata.into_iter().collect::<Vec<&ExifTag>>(),
+			metadata.into_iter().collect::<Vec<&ExifTag>>()
+		);
+		assert_eq!(Metadata::new_from_vec_autodetect(data)?, read_metadata);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_type_code_and_count_match_encoding()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::rational::uR64;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::Orientation(vec![1]))?;
+	metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 72, denominator: 1 }]))?;
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![1, 2, 3], 0xc000, ExifTagGroup::GENERIC))?;
+
+	let encoded = metadata.encode()?;
+	let ifd0    = metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap();
+
+	// Walk through the entries of IFD0 (little endian), starting at offset 8
+	let entry_count = u16::from_le_bytes([encoded[8], encoded[9]]) as usize;
+	let mut checked = 0;
+	for entry in encoded[10..10 + 12 * entry_count].chunks(12)
+	{
+		let hex = u16::from_le_bytes([entry[0], entry[1]]);
+
+		// The ExifOffset tag is only created while encoding
+		if let Some(tag) = ifd0.get_tags().iter().find(|tag| tag.as_u16() == hex)
+		{
+			assert_eq!(tag.type_code(), u16::from_le_bytes([entry[2], entry[3]]));
+			assert_eq!(tag.count(),     u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]));
+			checked += 1;
+		}
+	}
+	assert_eq!(checked, ifd0.get_tags().len());
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_orientation_from_orNT()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::png::PngReadOptions;
+
+	let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+	let options   = PngReadOptions { orientation_from_orNT: true, ..Default::default() };
+
+	// Insert an orNT chunk right after the IHDR chunk
+	let mut image_data = read("tests/sample2.png")?;
+	let ihdr_end = 8 + 25;
+	image_data.splice(ihdr_end..ihdr_end, get_png_chunk("orNT", &[6]));
+
+	// Disabled by default, so there is no metadata
+	assert!(Metadata::new_from_vec(&image_data, file_type)?.get_tag(&ExifTag::Orientation(Vec::new())).next().is_none());
+
+	let orientation = Metadata::new_from_png_vec(&image_data, &options)?
+		.get_tag(&ExifTag::Orientation(Vec::new()))
+		.next()
+		.cloned();
+
+	// Actual EXIF data takes precedence over the orNT chunk
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::Orientation(vec![3]))?;
+	metadata.write_to_vec(&mut image_data, file_type)?;
+	let exif_orientation = Metadata::new_from_png_vec(&image_data, &options)?
+		.get_tag(&ExifTag::Orientation(Vec::new()))
+		.next()
+		.cloned();
+
+	assert_eq!(orientation,      Some(ExifTag::Orientation(vec![6])));
+	assert_eq!(exif_orientation, Some(ExifTag::Orientation(vec![3])));
+
+	Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn
+serde_json_round_trip()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::rational::iR64;
+	use little_exif::rational::uR64;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 72, denominator: 1 }]))?;
+	metadata.set_tag(ExifTag::ExposureCompensation(vec![iR64 { nominator: -2, denominator: 3 }]))?;
+	metadata.set_tag(ExifTag::UnknownFLOAT(vec![0.1, -2.5], 0xc000, ExifTagGroup::EXIF))?;
+	metadata.set_tag(ExifTag::ThumbnailOffset(vec![0], vec![0xff, 0xd8, 0xff, 0xd9]))?;
+	metadata.set_tag(ExifTag::ThumbnailLength(vec![4]))?;
+
+	let json  = serde_json::to_string(&metadata).unwrap();
+	let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+	// Tag values are stored as typed JSON
+	let tags = value["image_file_directories"].as_array().unwrap().iter()
+		.flat_map(|ifd| ifd["tags"].as_array().unwrap().iter())
+		.collect::<Vec<_>>();
+	let find = |name: &str| tags.iter().find(|tag| tag["name"] == name).unwrap()["value"].clone();
+	assert_eq!(find("ImageDescription"),     serde_json::json!("Hello World!"));
+	assert_eq!(find("ISO"),                  serde_json::json!([2706]));
+	assert_eq!(find("XResolution"),          serde_json::json!([[72, 1]]));
+	assert_eq!(find("ExposureCompensation"), serde_json::json!([[-2, 3]]));
+	assert_eq!(find("Unknown"),              serde_json::json!([0.1, -2.5]));
+
+	// Deserializing gives the same metadata again, also for a real file
+	let deserialized: Metadata = serde_json::from_str(&json).unwrap();
+	assert_eq!(deserialized, metadata);
+	assert_eq!(deserialized.encode()?, metadata.encode()?);
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/read_sample.jpg"))?;
+	let json          = serde_json::to_string(&read_metadata).unwrap();
+	assert_eq!(serde_json::from_str::<Metadata>(&json).unwrap(), read_metadata);
+
+	// A value not matching the format of the tag is rejected
+	let invalid = r#"{"name":"ISO","hex":34855,"group":"EXIF","format":"INT16U","value":"text"}"#;
+	assert!(serde_json::from_str::<ExifTag>(invalid).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+plan_clear_metadata_matches_clear()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Removes the planned ranges, starting at the end so that the positions
+	// of the other ranges stay valid
+	let apply = |image_data: &Vec<u8>, plans: &[little_exif::metadata::removal_plan::RemovalPlan]| -> Vec<u8> {
+		let mut result = image_data.clone();
+		for plan in plans.iter().rev()
+		{
+			result.drain(plan.range.clone());
+		}
+		return result;
+	};
+
+	for (path, file_type, name) in [
+		("tests/sample2.jpg",           FileExtension::JPEG,                         "APP1"),
+		("tests/sample2.png",           FileExtension::PNG { as_zTXt_chunk: true  }, "zTXt"),
+		("tests/sample2.png",           FileExtension::PNG { as_zTXt_chunk: false }, "eXIf"),
+		("tests/sample2_extended.webp", FileExtension::WEBP,                         "EXIF"),
+	]
+	{
+		let mut image_data = read(path)?;
+		get_test_metadata()?.write_to_vec(&mut image_data, file_type)?;
+
+		let plans = Metadata::plan_clear_metadata(&image_data, file_type)?;
+		assert_eq!(plans.len(), 1);
+		assert_eq!(plans[0].name, name);
+
+		// Planning does not modify the image, clearing does the same
+		let planned = apply(&image_data, &plans);
+		Metadata::clear_metadata(&mut image_data, file_type)?;
+		assert!(Metadata::plan_clear_metadata(&image_data, file_type)?.is_empty());
+
+		// WebP additionally updates the file size and VP8X flags
+		match file_type
+		{
+			FileExtension::WEBP => assert_eq!(planned.len(), image_data.len()),
+			_                   => assert_eq!(planned,       image_data),
+		}
+	}
+
+	let image_data = read("tests/with_exif.jxl")?;
+	let plans      = Metadata::plan_clear_metadata(&image_data, FileExtension::JXL)?;
+	let mut cleared = image_data.clone();
+	Metadata::clear_metadata(&mut cleared, FileExtension::JXL)?;
+	assert!(!plans.is_empty());
+	assert_eq!(apply(&image_data, &plans), cleared);
+
+	// TIFF metadata is rewritten instead of removed
+	assert!(Metadata::plan_clear_metadata(&read("tests/read_sample.tif")?, FileExtension::TIFF).is_err());
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_exif_zTXt_after_IDAT()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::png::vec::parse_png_with_offsets;
+
+	// Same as sample2.png, with the EXIF zTXt chunk between IDAT and IEND
+	let file_type  = FileExtension::PNG { as_zTXt_chunk: true };
+	let image_data = read("tests/sample2_exif_after_idat.png")?;
+	let chunk_types = parse_png_with_offsets(&image_data)?
+		.iter()
+		.map(|(chunk, _)| chunk.as_string())
+		.collect::<Vec<String>>();
+	assert_eq!(chunk_types[chunk_types.len() - 2..], ["zTXt", "IEND"]);
+
+	assert_eq!(
+		Metadata::new_from_vec(&image_data, file_type)?.get_tag_value_string(&ExifTag::ImageDescription(String::new())),
+		Some("EXIF after IDAT".to_string())
+	);
+
+	// Clearing removes the chunk, regardless of where it is located
+	let mut cleared = image_data.clone();
+	Metadata::clear_metadata(&mut cleared, file_type)?;
+	assert_eq!(cleared, read("tests/sample2.png")?);
+
+	let path = Path::new("tests/sample2_exif_after_idat_copy.png");
+	copy("tests/sample2_exif_after_idat.png", path)?;
+	Metadata::file_clear_metadata(path)?;
+	let file_cleared = read(path)?;
+	remove_file(path)?;
+	assert_eq!(file_cleared, read("tests/sample2.png")?);
+
+	// Writing replaces the chunk with one right after IHDR
+	let mut written = image_data.clone();
+	get_test_metadata()?.write_to_vec(&mut written, file_type)?;
+	let chunk_types = parse_png_with_offsets(&written)?
+		.iter()
+		.map(|(chunk, _)| chunk.as_string())
+		.collect::<Vec<String>>();
+	assert_eq!(chunk_types.iter().filter(|chunk_type| *chunk_type == "zTXt").count(), 1);
+	assert_eq!(chunk_types[1], "zTXt");
+
+	Ok(())
+}
+
+#[test]
+fn
+user_comment_charsets()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::user_comment::UserCommentCharset;
+
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_user_comment(), None);
+
+	// ASCII is used by default, padding gets removed when reading
+	metadata.set_user_comment("Hello   ");
+	assert_eq!(
+		metadata.get_tag(&ExifTag::UserComment(Vec::new())).next(),
+		Some(&ExifTag::UserComment(b"ASCII\0\0\0Hello   ".to_vec()))
+	);
+	assert_eq!(metadata.get_user_comment(), Some("Hello".to_string()));
+
+	// Non-ASCII comments are stored as UTF-16 using the endianness
+	metadata.set_user_comment("Grüße");
+	assert_eq!(
+		metadata.get_tag(&ExifTag::UserComment(Vec::new())).next(),
+		Some(&ExifTag::UserComment(b"UNICODE\0G\0r\0\xfc\0\xdf\0e\0".to_vec()))
+	);
+
+	let mut image_data = read("tests/sample2.jpg")?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	let read_back = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert_eq!(read_back.get_user_comment(), Some("Grüße".to_string()));
+
+	metadata.set_user_comment_with_charset("ünknown", UserCommentCharset::Undefined)?;
+	assert_eq!(metadata.get_user_comment(), Some("ünknown".to_string()));
+
+	// JIS can't be decoded, neither can data without a character code
+	metadata.set_tag(ExifTag::UserComment(b"JIS\0\0\0\0\0\x30\x21".to_vec()))?;
+	assert_eq!(metadata.get_user_comment(), None);
+	metadata.set_tag(ExifTag::UserComment(b"ASCII".to_vec()))?;
+	assert_eq!(metadata.get_user_comment(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+webp_xmp_independent_of_exif()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Lists the RIFF chunk types of the WebP file
+	let chunk_types = |data: &Vec<u8>| -> Vec<String> {
+		let mut types    = Vec::new();
+		let mut position = 12;
+		while position + 8 <= data.len()
+		{
+			let size = u32::from_le_bytes([data[position+4], data[position+5], data[position+6], data[position+7]]) as usize;
+			types.push(String::from_utf8_lossy(&data[position..position+4]).to_string());
+			position += 8 + size + size % 2;
+		}
+		return types;
+	};
+	let vp8x_flags = |data: &Vec<u8>| -> u8 { data[20] };
+
+	let file_type  = FileExtension::WEBP;
+	let xmp        = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></x:xmpmeta>";
+	let original   = read("tests/sample2_simple_lossless.webp")?;
+	let mut image_data = original.clone();
+	assert!(Metadata::read_xmp(&image_data, file_type).is_err());
+
+	// Writing the XMP data converts the file to the extended format
+	Metadata::write_xmp(&mut image_data, file_type, xmp)?;
+	assert_eq!(Metadata::read_xmp(&image_data, file_type)?, xmp);
+	assert_eq!(chunk_types(&image_data), ["VP8X", "VP8L", "XMP "]);
+	assert_eq!(vp8x_flags(&image_data) & 0x0c, 0x04);
+
+	// The EXIF chunk is placed in front of the XMP chunk, both are kept
+	let metadata = get_test_metadata()?;
+	metadata.write_to_vec(&mut image_data, file_type)?;
+	Metadata::write_xmp(&mut image_data, file_type, "<replaced/>")?;
+	assert_eq!(chunk_types(&image_data), ["VP8X", "VP8L", "EXIF", "XMP "]);
+	assert_eq!(vp8x_flags(&image_data) & 0x0c, 0x0c);
+	assert_eq!(Metadata::read_xmp(&image_data, file_type)?, "<replaced/>");
+	assert_eq!(Metadata::new_from_vec(&image_data, file_type)?, metadata);
+
+	// Clearing one of them keeps the other
+	Metadata::clear_metadata(&mut image_data, file_type)?;
+	assert_eq!(chunk_types(&image_data), ["VP8X", "VP8L", "XMP "]);
+	assert_eq!(vp8x_flags(&image_data) & 0x0c, 0x04);
+	assert_eq!(Metadata::read_xmp(&image_data, file_type)?, "<replaced/>");
+
+	metadata.write_to_vec(&mut image_data, file_type)?;
+	Metadata::clear_xmp(&mut image_data, file_type)?;
+	assert_eq!(chunk_types(&image_data), ["VP8X", "VP8L", "EXIF"]);
+	assert_eq!(vp8x_flags(&image_data) & 0x0c, 0x08);
+	assert!(Metadata::read_xmp(&image_data, file_type).is_err());
+	assert_eq!(Metadata::new_from_vec(&image_data, file_type)?, metadata);
+
+	// The RIFF size information stays consistent
+	let riff_size = u32::from_le_bytes([image_data[4], image_data[5], image_data[6], image_data[7]]) as usize;
+	assert_eq!(riff_size + 8, image_data.len());
+
+	Ok(())
+}
+
+#[test]
+fn
+validate_metadata()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::validate::ValidationSeverity;
+	use little_exif::rational::uR64;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Orientation(vec![1]))?;
+	metadata.set_tag(ExifTag::ExifVersion(vec![0x30, 0x32, 0x33, 0x32]))?;
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024:02:29 13:37:00".to_string()))?;
+	assert!(metadata.validate().is_empty());
+
+	// A GPS IFD without GPSVersionID and with a zero denominator
+	metadata.set_tag(ExifTag::GPSAltitude(vec![uR64 { nominator: 42, denominator: 0 }]))?;
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024-02-29 13:37".to_string()))?;
+	metadata.set_tag(ExifTag::Orientation(vec![0]))?;
+	metadata.set_tag_unchecked(ExifTag::UnknownINT32U(vec![1], 0x0112, ExifTagGroup::GENERIC));
+
+	let issues = metadata.validate();
+	let summary = issues.iter()
+		.map(|issue| (issue.severity, issue.group, issue.tag))
+		.collect::<Vec<_>>();
+	assert_eq!(summary, vec![
+		(ValidationSeverity::Warning, ExifTagGroup::GENERIC, Some(0x0112)),
+		(ValidationSeverity::Error,   ExifTagGroup::EXIF,    Some(0x9003)),
+		(ValidationSeverity::Warning, ExifTagGroup::GPS,     None        ),
+		(ValidationSeverity::Error,   ExifTagGroup::GPS,     Some(0x0006)),
+	]);
+
+	metadata.set_tag(ExifTag::GPSVersionID(vec![2, 3, 0, 0]))?;
+	assert_eq!(metadata.validate().len(), 3);
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+png_sync_pHYs_from_exif()
+-> Result<(), std::io::Error>
+{
+	use little_exif::png::PngWriteOptions;
+	use little_exif::png::vec::parse_png_with_offsets;
+	use little_exif::png::vec::sync_phys_from_exif;
+	use little_exif::rational::uR64;
+
+	// Gets the data of all pHYs chunks
+	let pHYs_chunks = |data: &Vec<u8>| -> Vec<Vec<u8>> {
+		parse_png_with_offsets(data).unwrap()
+			.into_iter()
+			.filter(|(chunk, _)| chunk.as_string() == "pHYs")
+			.map(|(chunk, offset)| data[offset as usize + 8..offset as usize + 8 + chunk.length() as usize].to_vec())
+			.collect()
+	};
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+
+	// Without the resolution tags, nothing changes
+	let mut metadata = get_test_metadata()?;
+	sync_phys_from_exif(&mut image_data, &metadata)?;
+	assert!(pHYs_chunks(&image_data).is_empty());
+
+	// 72 and 300 DPI, ResolutionUnit defaults to inches
+	metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 72,  denominator: 1 }]))?;
+	metadata.set_tag(ExifTag::YResolution(vec![uR64 { nominator: 300, denominator: 1 }]))?;
+	metadata.write_to_png_vec(
+		&mut image_data,
+		&PngWriteOptions { sync_pHYs_from_exif: true, ..Default::default() }
+	)?;
+	assert_eq!(pHYs_chunks(&image_data), vec![[
+		2835u32.to_be_bytes().to_vec(), 
+		11811u32.to_be_bytes().to_vec(), 
+		vec![1]
+	].concat()]);
+
+	// Pixels per centimeter replace the existing chunk
+	metadata.set_tag(ExifTag::ResolutionUnit(vec![3]))?;
+	sync_phys_from_exif(&mut image_data, &metadata)?;
+	assert_eq!(pHYs_chunks(&image_data), vec![[
+		7200u32.to_be_bytes().to_vec(), 
+		30000u32.to_be_bytes().to_vec(), 
+		vec![1]
+	].concat()]);
+
+	// The PNG stays valid, the EXIF data is not affected
+	metadata.remove_tag(&ExifTag::ResolutionUnit(Vec::new()), false);
+	let read_metadata = Metadata::new_from_vec(
+		&image_data, 
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	)?;
+	assert_eq!(read_metadata.encode()?, metadata.encode()?);
+
+	// An invalid unit fails before the file buffer gets modified
+	metadata.set_tag(ExifTag::ResolutionUnit(vec![7]))?;
+	let previous_data = image_data.clone();
+	assert!(metadata.write_to_png_vec(
+		&mut image_data,
+		&PngWriteOptions { sync_pHYs_from_exif: true, ..Default::default() }
+	).is_err());
+	assert_eq!(image_data, previous_data);
+
+	Ok(())
+}
+
+#[test]
+fn
+metadata_set_tags()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::set::SetTagsSummary;
+
+	let mut metadata = get_test_metadata()?;
+	let summary = metadata.set_tags(vec![
+		ExifTag::ImageDescription("Replaced".to_string()),
+		ExifTag::Artist("Jane Doe".to_string()),
+		ExifTag::ISO(vec![200]),
+		ExifTag::GPSVersionID(vec![2, 3, 0, 0]),
+	])?;
+	assert_eq!(summary, SetTagsSummary { added: 2, replaced: 2 });
+
+	// Each tag ends up in the IFD of its group
+	for (hex, group) in [(0x010e, ExifTagGroup::GENERIC), (0x013b, ExifTagGroup::GENERIC), (0x8827, ExifTagGroup::EXIF), (0x0000, ExifTagGroup::GPS)]
+	{
+		assert_eq!(metadata.get_tag_by_hex(hex, Some(group)).count(), 1);
+	}
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(), 
+		Some(&ExifTag::ImageDescription("Replaced".to_string()))
+	);
+
+	// A tag with a mismatching format results in an error
+	assert!(metadata.set_tags(vec![ExifTag::UnknownINT16U(vec![1], 0x829a, ExifTagGroup::EXIF)]).is_err());
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+jpg_preserve_adobe_APP14()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// Lists the marker, start position and data of each segment up to SOS
+	let segments = |data: &Vec<u8>| -> Vec<(u8, usize, Vec<u8>)> {
+		let mut segments = Vec::new();
+		let mut position = 2;
+		while data[position + 1] != 0xda
+		{
+			let length = u16::from_be_bytes([data[position+2], data[position+3]]) as usize;
+			segments.push((data[position + 1], position, data[position+4..position+2+length].to_vec()));
+			position += 2 + length;
+		}
+		return segments;
+	};
+	let is_exif = |(marker, _, data): &(u8, usize, Vec<u8>)| *marker == 0xe1 && data.starts_with(b"Exif\0\0");
+
+	let original   = read("tests/sample2_adobe_app14.jpg")?;
+	let mut image_data = original.clone();
+
+	let metadata = get_test_metadata()?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+	assert_eq!(Metadata::new_from_vec(&image_data, FileExtension::JPEG)?, metadata);
+
+	let segments_before = segments(&original);
+	let segments_after  = segments(&image_data);
+
+	// Apart from the EXIF segment, all segments are kept in the same order
+	// and with the same data
+	let other_segments = |segments: &Vec<(u8, usize, Vec<u8>)>| -> Vec<(u8, Vec<u8>)> {
+		segments.iter()
+			.filter(|segment| !is_exif(segment))
+			.map(|(marker, _, data)| (*marker, data.clone()))
+			.collect()
+	};
+	assert_eq!(other_segments(&segments_before), other_segments(&segments_after));
+
+	// The APP14 segment only got shifted by the change in size of the EXIF 
+	// segment, so its byte range in the file is otherwise unchanged
+	let exif_size  = |segments: &Vec<(u8, usize, Vec<u8>)>| -> usize {
+		segments.iter().filter(|segment| is_exif(segment)).map(|(_, _, data)| data.len() + 4).sum()
+	};
+	let app14_of   = |segments: &Vec<(u8, usize, Vec<u8>)>| -> (usize, Vec<u8>) {
+		segments.iter().find(|(marker, _, _)| *marker == 0xee).map(|(_, position, data)| (*position, data.clone())).unwrap()
+	};
+	let (position_before, data_before) = app14_of(&segments_before);
+	let (position_after,  data_after ) = app14_of(&segments_after);
+
+	assert!(data_before.starts_with(b"Adobe"));
+	assert_eq!(data_before, data_after);
+	assert_eq!(position_after + exif_size(&segments_before), position_before + exif_size(&segments_after));
+	assert_eq!(original[position_before..position_before + 16], image_data[position_after..position_after + 16]);
+
+	// The image data starting with the SOS segment is untouched as well
+	let (_, last_position, last_data) = segments_before.last().unwrap();
+	assert!(image_data.ends_with(&original[last_position + 4 + last_data.len()..]));
+
+	Ok(())
+}
+
+#[test]
+fn
+exif_tag_expected_count()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	// For known tags without data, the number of components falls back to
+	// the expected count (except for strings, which only count their data)
+	for group in ExifTagGroup::all()
+	{
+		for hex in 0..=u16::MAX
+		{
+			if let Ok(tag) = ExifTag::from_u16(hex, group)
+			{
+				if tag.is_string()
+				{
+					continue;
+				}
+				assert_eq!(tag.number_of_components(), tag.expected_count().unwrap_or(0), "{}", tag.name());
+			}
+		}
+	}
+
+	// The expected count does not depend on the data stored in the tag
+	assert_eq!(ExifTag::GPSLatitudeRef("N".to_string()).expected_count(), Some(2));
+	assert_eq!(ExifTag::ISO(vec![100, 200]).expected_count(),             None);
+	assert_eq!(ExifTag::UnknownINT16U(vec![1], 0x0112, ExifTagGroup::GENERIC).expected_count(), None);
+
+	// Values not matching the expected count are rejected
+	assert!(ExifTag::new_rational(&ExifTag::FNumber(Vec::new()), vec![(28, 10), (1, 1)]).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_psd()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// The PSD stores the same EXIF data as the JPEG it was created from
+	let jpg_metadata = Metadata::new_from_path(Path::new("tests/sample2.jpg"))?;
+	let psd_metadata = Metadata::new_from_path(Path::new("tests/sample2.psd"))?;
+	assert!(psd_metadata.into_iter().count() > 0);
+	assert_eq!(psd_metadata, jpg_metadata);
+
+	let image_data = read("tests/sample2.psd")?;
+	assert_eq!(Metadata::new_from_vec(&image_data, FileExtension::PSD)?, jpg_metadata);
+	assert_eq!(Metadata::new_from_vec_autodetect(&image_data)?, jpg_metadata);
+
+	// Writing is not supported
+	let mut image_data = image_data;
+	assert!(jpg_metadata.write_to_vec(&mut image_data, FileExtension::PSD).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+encode_sorted_tag_order()
+-> Result<(), std::io::Error>
+{
+	use little_exif::rational::uR64;
+
+	// Collects the tag ids of each IFD of little endian TIFF data, following
+	// the links to the next IFD as well as the ExifIFD, GPS and Interop 
+	// offsets
+	fn
+	collect_tag_ids
+	(
+		data:   &[u8],
+		offset: usize,
+		result: &mut Vec<Vec<u16>>
+	)
+	{
+		let read_u16 = |position: usize| u16::from_le_bytes([data[position], data[position+1]]);
+		let read_u32 = |position: usize| u32::from_le_bytes([data[position], data[position+1], data[position+2], data[position+3]]) as usize;
+
+		let count   = read_u16(offset) as usize;
+		let mut ids = Vec::new();
+		for entry in 0..count
+		{
+			let position = offset + 2 + 12 * entry;
+			ids.push(read_u16(position));
+			if [0x8769, 0x8825, 0xa005].contains(&read_u16(position))
+			{
+				collect_tag_ids(data, read_u32(position + 8), result);
+			}
+		}
+		result.push(ids);
+
+		let next_ifd = read_u32(offset + 2 + 12 * count);
+		if next_ifd != 0
+		{
+			collect_tag_ids(data, next_ifd, result);
+		}
+	}
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::XPTitle(vec![0x41, 0x00, 0x00, 0x00]))?;
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()))?;
+	metadata.set_tag(ExifTag::GPSAltitude(vec![uR64 { nominator: 42, denominator: 1 }]))?;
+	metadata.set_tag(ExifTag::ISO(vec![100]))?;
+	metadata.set_tag(ExifTag::Model("Testcam".to_string()))?;
+	metadata.set_tag(ExifTag::GPSVersionID(vec![2, 3, 0, 0]))?;
+	metadata.set_tag(ExifTag::ExposureProgram(vec![1]))?;
+	metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()))?;
+	metadata.sort_data();
+
+	let encoded = metadata.encode()?;
+	let mut tag_ids = Vec::new();
+	collect_tag_ids(&encoded, 8, &mut tag_ids);
+
+	// IFD0 (including the offset tags), ExifIFD, Interop and GPS IFD
+	assert_eq!(tag_ids.len(), 4);
+	for ids in &tag_ids
+	{
+		assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{:x?}", ids);
+	}
+	assert!(tag_ids.iter().any(|ids| ids == &vec![0x010e, 0x0110, 0x8769, 0x8825, 0x9c9b]));
+
+	// Decoding and encoding again results in the same data
+	let decoded = Metadata::new_from_vec(&encoded, little_exif::filetype::FileExtension::TIFF)?;
+	assert_eq!(decoded.encode()?, encoded);
+
+	Ok(())
+}